@@ -0,0 +1,8 @@
+//! Peripheral glue the examples share, on top of esp-hal.
+//!
+//! The logic lives in esp32-kit and is tested there, this crate only ties it
+//! to the peripherals.
+
+#![no_std]
+
+pub mod tone;
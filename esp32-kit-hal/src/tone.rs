@@ -0,0 +1,70 @@
+//! [`Tone`] output on the LEDC peripheral for passive buzzers.
+
+use esp32_kit::music::player::Tone;
+use esp_hal::gpio::AnyPin;
+use esp_hal::ledc::{
+    channel::{self, ChannelIFace},
+    timer::{self, TimerIFace},
+    HighSpeed, Ledc,
+};
+use esp_hal::time::Rate;
+
+/// A passive buzzer on its own LEDC high-speed timer and channel.
+///
+/// Each buzzer needs a timer of its own to play its own pitch, there are 4 of them.
+pub struct LedcTone {
+    ledc: &'static Ledc<'static>,
+    timer: timer::Number,
+    channel: channel::Number,
+    pin: AnyPin<'static>,
+}
+
+impl LedcTone {
+    pub fn new(
+        ledc: &'static Ledc<'static>,
+        timer: timer::Number,
+        channel: channel::Number,
+        pin: impl Into<AnyPin<'static>>,
+    ) -> Self {
+        Self {
+            ledc,
+            timer,
+            channel,
+            pin: pin.into(),
+        }
+    }
+
+    // The channel borrows its timer, so a new frequency means configuring both again
+    fn output(&mut self, frequency: Rate, duty_pct: u8) -> Option<()> {
+        let mut hstimer = self.ledc.timer::<HighSpeed>(self.timer);
+        hstimer
+            .configure(timer::config::Config {
+                duty: timer::config::Duty::Duty10Bit,
+                clock_source: timer::HSClockSource::APBClk,
+                frequency,
+            })
+            .ok()?;
+
+        let mut channel = self.ledc.channel(self.channel, self.pin.reborrow());
+        channel
+            .configure(channel::config::Config {
+                timer: &hstimer,
+                duty_pct,
+                pin_config: channel::config::PinConfig::PushPull,
+            })
+            .ok()
+    }
+}
+
+impl Tone for LedcTone {
+    fn tone(&mut self, freq_hz: u32) {
+        // A pitch the timer cannot divide down to rests instead
+        if self.output(Rate::from_hz(freq_hz), 50).is_none() {
+            self.no_tone();
+        }
+    }
+
+    fn no_tone(&mut self) {
+        self.output(Rate::from_khz(1), 0);
+    }
+}
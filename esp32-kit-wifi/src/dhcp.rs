@@ -0,0 +1,53 @@
+//! DHCP server for the access point, see [`esp32_kit::dhcp`].
+
+use core::net::Ipv4Addr;
+
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpAddress, IpEndpoint, Stack};
+use embassy_time::Instant;
+use esp32_kit::dhcp::{self, ServerConfig};
+use esp_println::println;
+
+/// Stations served at the same time.
+pub const POOL_SIZE: usize = 8;
+
+/// Hands out the addresses of `config` to the stations joining the AP on `stack`.
+#[embassy_executor::task]
+pub async fn dhcp_task(stack: Stack<'static>, config: ServerConfig) {
+    let mut server = match dhcp::Server::<POOL_SIZE>::new(config) {
+        Ok(server) => server,
+        Err(e) => {
+            println!("DHCP server not started: {:?}", e);
+            return;
+        }
+    };
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0; 1536];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_buffer = [0; 1536];
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    socket.bind(dhcp::SERVER_PORT).unwrap();
+
+    let mut request = [0; 576];
+    let mut reply = [0; dhcp::MAX_REPLY_LEN];
+    // Clients without an address only hear broadcasts
+    let broadcast = IpEndpoint::new(IpAddress::Ipv4(Ipv4Addr::BROADCAST), dhcp::CLIENT_PORT);
+    loop {
+        let Ok((len, _)) = socket.recv_from(&mut request).await else {
+            continue;
+        };
+        let now_s = Instant::now().as_secs();
+        if let Some(len) = server.handle(now_s, &request[..len], &mut reply) {
+            if let Err(e) = socket.send_to(&reply[..len], broadcast).await {
+                println!("DHCP reply failed: {:?}", e);
+            }
+        }
+    }
+}
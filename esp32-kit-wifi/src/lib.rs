@@ -0,0 +1,11 @@
+//! WiFi plumbing the WiFi examples share, on top of esp-hal and esp-wifi.
+//!
+//! The logic lives in esp32-kit and is tested there, this crate only ties it
+//! to the peripherals and spawns the embassy tasks.
+
+#![no_std]
+
+pub mod dhcp;
+pub mod mdns;
+pub mod station;
+pub mod status;
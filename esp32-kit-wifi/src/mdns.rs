@@ -0,0 +1,92 @@
+//! mDNS responder, so the web UI is reachable by name.
+
+use core::fmt::Write;
+
+use embassy_futures::select::{select, Either};
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpAddress, IpEndpoint, Stack};
+use embassy_time::{Duration, Timer};
+use esp32_kit::mdns::{self, Responder, Service};
+use esp_hal::efuse::Efuse;
+use esp_println::println;
+
+// The board answers as http://esp32.local/ unless MDNS_HOSTNAME says otherwise
+const HOSTNAME: &str = match option_env!("MDNS_HOSTNAME") {
+    Some(hostname) => hostname,
+    None => "esp32",
+};
+
+/// Answers mDNS queries for [`HOSTNAME`] and advertises the web server,
+/// `version` goes into its TXT record.
+#[embassy_executor::task]
+pub async fn mdns_task(stack: Stack<'static>, version: &'static str) {
+    // The MAC address tells boards with the same name apart
+    let mut device_id = heapless::String::<12>::new();
+    for byte in Efuse::read_base_mac_address() {
+        write!(device_id, "{:02x}", byte).ok();
+    }
+    let txt = [("version", version), ("id", device_id.as_str())];
+    let services = [Service {
+        instance: HOSTNAME,
+        service_type: "_http._tcp",
+        port: 80,
+        txt: &txt,
+    }];
+    let responder = Responder::new(HOSTNAME, &services);
+
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0; 1024];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_buffer = [0; 1024];
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    socket.bind(mdns::PORT).unwrap();
+    if let Err(e) = stack.join_multicast_group(mdns::GROUP) {
+        println!("Could not join the mDNS group: {:?}", e);
+        return;
+    }
+    let group = IpEndpoint::new(IpAddress::Ipv4(mdns::GROUP), mdns::PORT);
+
+    let mut query = [0; 512];
+    let mut reply = [0; 512];
+    loop {
+        stack.wait_config_up().await;
+        let Some(address) = stack.config_v4().map(|config| config.address.address()) else {
+            continue;
+        };
+        println!("Reachable as http://{}.local/", responder.hostname());
+        // Tell caches about the (new) address, twice as RFC 6762 asks
+        for _ in 0..2 {
+            if let Some(len) = responder.announce(address, &mut reply) {
+                socket.send_to(&reply[..len], group).await.ok();
+            }
+            Timer::after(Duration::from_secs(1)).await;
+        }
+
+        loop {
+            let received =
+                match select(socket.recv_from(&mut query), stack.wait_config_down()).await {
+                    Either::First(received) => received,
+                    Either::Second(()) => break,
+                };
+            let Ok((len, from)) = received else {
+                continue;
+            };
+            let source_port = from.endpoint.port;
+            if let Some(len) = responder.respond(&query[..len], source_port, address, &mut reply) {
+                // One-shot resolvers get the answer sent back to them
+                let to = if source_port == mdns::PORT {
+                    group
+                } else {
+                    from.endpoint
+                };
+                socket.send_to(&reply[..len], to).await.ok();
+            }
+        }
+    }
+}
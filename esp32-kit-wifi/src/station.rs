@@ -0,0 +1,303 @@
+//! Station mode: [`WifiManager`] on top of the esp-wifi controller.
+//!
+//! ```ignore
+//! let net_config = embassy_net::Config::dhcpv4(DhcpConfig::default());
+//! let stack = station::start_station(
+//!     esp_wifi_ctrl,
+//!     peripherals.WIFI,
+//!     rng,
+//!     net_config,
+//!     mk_static!(StackResources<4>, StackResources::<4>::new()),
+//!     esp32_kit_wifi::known_networks!(),
+//!     &spawner,
+//! );
+//! station::wait_for_connection(stack).await;
+//! ```
+
+use embassy_executor::Spawner;
+use embassy_futures::select::select;
+use embassy_net::{Runner, Stack, StackResources};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::{Duration, Timer};
+use esp32_kit::mk_static;
+use esp32_kit::wifi::manager::{EventSubscriber, Station, WifiManager, MAX_SCAN};
+use esp32_kit::wifi::{AccessPoint, BackoffConfig, Event, MAX_SSID_LEN};
+use esp_hal::peripherals::WIFI as WifiPeripheral;
+use esp_hal::rng::Rng;
+use esp_println::println;
+use esp_wifi::wifi::{
+    self, AccessPointConfiguration, AuthMethod, WifiController, WifiDevice, WifiError, WifiEvent,
+};
+use esp_wifi::EspWifiController;
+
+use crate::status;
+
+pub use esp32_kit::wifi::Network;
+
+/// Keeps the station on the best known network, other tasks can follow its events.
+pub static WIFI: WifiManager<CriticalSectionRawMutex> = WifiManager::new();
+
+/// The networks to pick from, e.g. from [`known_networks!`](crate::known_networks).
+pub type Networks = heapless::Vec<Network<'static>, 2>;
+
+/// The networks given at build time in `SSID` and `PASSWORD`.
+///
+/// A second network, e.g. a phone hotspot, can be given with `SSID2` and `PASSWORD2`.
+#[macro_export]
+macro_rules! known_networks {
+    () => {{
+        let mut networks = $crate::station::Networks::new();
+        networks
+            .push($crate::station::Network {
+                ssid: env!("SSID"),
+                password: env!("PASSWORD"),
+            })
+            .ok();
+        if let Some(ssid) = option_env!("SSID2") {
+            networks
+                .push($crate::station::Network {
+                    ssid,
+                    password: option_env!("PASSWORD2").unwrap_or(""),
+                })
+                .ok();
+        }
+        networks
+    }};
+}
+
+#[derive(Debug)]
+pub enum StationError {
+    /// The SSID or password is longer than esp-wifi takes.
+    InvalidNetwork,
+    Wifi(WifiError),
+}
+
+impl From<WifiError> for StationError {
+    fn from(e: WifiError) -> Self {
+        StationError::Wifi(e)
+    }
+}
+
+/// `Station` on top of the esp-wifi controller, optionally next to an access point.
+pub struct EspStation {
+    controller: WifiController<'static>,
+    access_point: Option<AccessPointConfiguration>,
+    // Network the station half is configured for
+    configured: heapless::String<MAX_SSID_LEN>,
+}
+
+impl EspStation {
+    pub fn new(controller: WifiController<'static>) -> Self {
+        Self {
+            controller,
+            access_point: None,
+            configured: heapless::String::new(),
+        }
+    }
+
+    /// Runs the access point `ap` all the time, the station joins networks next to it.
+    pub fn with_access_point(
+        controller: WifiController<'static>,
+        ap: AccessPointConfiguration,
+    ) -> Self {
+        Self {
+            access_point: Some(ap),
+            ..Self::new(controller)
+        }
+    }
+
+    fn configure(&mut self, client: wifi::ClientConfiguration) -> Result<(), WifiError> {
+        let config = match &self.access_point {
+            // The AP half is always the same, so its stations stay connected
+            Some(ap) => wifi::Configuration::Mixed(client, ap.clone()),
+            None => wifi::Configuration::Client(client),
+        };
+        self.controller.set_configuration(&config)
+    }
+
+    async fn start(&mut self) -> Result<(), WifiError> {
+        if !matches!(self.controller.is_started(), Ok(true)) {
+            self.configure(Default::default())?;
+            println!("Starting wifi");
+            self.controller.start_async().await?;
+            println!("Wifi started!");
+            if self.access_point.is_some() {
+                status::publish(esp32_kit::status::Event::ApStarted);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Station for EspStation {
+    type Error = StationError;
+
+    async fn scan(&mut self) -> Result<heapless::Vec<AccessPoint, MAX_SCAN>, StationError> {
+        self.start().await?;
+        let found = self.controller.scan_n_async(MAX_SCAN).await?;
+        Ok(found
+            .into_iter()
+            .take(MAX_SCAN)
+            .filter_map(|ap| {
+                Some(AccessPoint {
+                    ssid: ap.ssid.as_str().try_into().ok()?,
+                    rssi: ap.signal_strength,
+                    channel: ap.channel,
+                    secured: ap.auth_method.is_some_and(|auth| auth != AuthMethod::None),
+                })
+            })
+            .collect())
+    }
+
+    async fn connect(&mut self, network: &Network<'_>) -> Result<(), StationError> {
+        self.start().await?;
+        // Only switching networks touches the configuration
+        if self.configured != network.ssid {
+            let auth_method = if network.password.is_empty() {
+                AuthMethod::None
+            } else {
+                AuthMethod::WPA2Personal
+            };
+            self.configure(wifi::ClientConfiguration {
+                ssid: network
+                    .ssid
+                    .try_into()
+                    .map_err(|_| StationError::InvalidNetwork)?,
+                password: network
+                    .password
+                    .try_into()
+                    .map_err(|_| StationError::InvalidNetwork)?,
+                auth_method,
+                ..Default::default()
+            })?;
+            self.configured = network
+                .ssid
+                .try_into()
+                .map_err(|_| StationError::InvalidNetwork)?;
+        }
+        Ok(self.controller.connect_async().await?)
+    }
+
+    async fn wait_disconnected(&mut self) {
+        if matches!(self.controller.is_connected(), Ok(true)) {
+            self.controller
+                .wait_for_event(WifiEvent::StaDisconnected)
+                .await;
+        }
+    }
+
+    fn rssi(&mut self) -> Option<i8> {
+        let rssi = self.controller.rssi().ok()?;
+        Some(rssi.clamp(i8::MIN as i32, 0) as i8)
+    }
+}
+
+#[embassy_executor::task]
+async fn wifi_task(mut station: EspStation, networks: &'static Networks, mut rng: Rng) {
+    println!(
+        "Device capabilities: {:?}",
+        station.controller.capabilities()
+    );
+    WIFI.run(&mut station, networks, BackoffConfig::default(), || {
+        rng.random()
+    })
+    .await
+}
+
+#[embassy_executor::task]
+async fn events_task(
+    mut events: EventSubscriber<'static, CriticalSectionRawMutex>,
+    networks: &'static Networks,
+) {
+    loop {
+        let event = events.next_message_pure().await;
+        match event {
+            Event::Connecting { network } => {
+                println!("About to connect to {}...", networks[network].ssid)
+            }
+            Event::Connected { network, rssi } => {
+                println!(
+                    "Wifi connected to {} (RSSI {:?} dBm)",
+                    networks[network].ssid, rssi
+                )
+            }
+            Event::Disconnected => println!("Wifi disconnected"),
+            Event::GotIp(address) => println!("Got IP: {}", address),
+            Event::LostIp => println!("Lost IP address"),
+        }
+        status::publish(event.into());
+    }
+}
+
+// An address only counts while the link is up, a static one is configured all the time
+#[embassy_executor::task]
+async fn ip_task(stack: Stack<'static>) {
+    loop {
+        stack.wait_link_up().await;
+        stack.wait_config_up().await;
+        WIFI.set_address(stack.config_v4().map(|config| config.address.address()));
+        select(stack.wait_link_down(), stack.wait_config_down()).await;
+        WIFI.set_address(None);
+    }
+}
+
+/// Drives one network interface, the station and the access point each need one.
+#[embassy_executor::task(pool_size = 2)]
+pub async fn net_task(mut runner: Runner<'static, WifiDevice<'static>>) {
+    runner.run().await
+}
+
+/// Keeps `station` on one of `networks` and reports the address of `stack`,
+/// whose runner is left to the caller.
+pub fn spawn_station(
+    station: EspStation,
+    networks: Networks,
+    stack: Stack<'static>,
+    rng: Rng,
+    spawner: &Spawner,
+) {
+    let networks: &'static Networks = mk_static!(Networks, networks);
+    // Subscribe before the manager starts, so the first events are seen
+    let events = WIFI.subscriber().unwrap();
+    spawner.must_spawn(events_task(events, networks));
+    spawner.must_spawn(wifi_task(station, networks, rng));
+    spawner.must_spawn(ip_task(stack));
+}
+
+/// Joins one of `networks` with `config` on the returned stack.
+pub fn start_station<const SOCKETS: usize>(
+    esp_wifi_ctrl: &'static EspWifiController<'static>,
+    wifi: WifiPeripheral<'static>,
+    mut rng: Rng,
+    config: embassy_net::Config,
+    resources: &'static mut StackResources<SOCKETS>,
+    networks: Networks,
+    spawner: &Spawner,
+) -> Stack<'static> {
+    let (controller, interfaces) = esp_wifi::wifi::new(esp_wifi_ctrl, wifi).unwrap();
+    let net_seed = rng.random() as u64 | ((rng.random() as u64) << 32);
+    let (stack, runner) = embassy_net::new(interfaces.sta, config, resources, net_seed);
+
+    spawn_station(EspStation::new(controller), networks, stack, rng, spawner);
+    spawner.must_spawn(net_task(runner));
+    stack
+}
+
+pub async fn wait_for_connection(stack: Stack<'_>) {
+    println!("Waiting for link to be up");
+    loop {
+        if stack.is_link_up() {
+            break;
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+
+    println!("Waiting to get IP address...");
+    loop {
+        if let Some(config) = stack.config_v4() {
+            println!("Got IP: {}", config.address);
+            break;
+        }
+        Timer::after(Duration::from_millis(500)).await;
+    }
+}
@@ -0,0 +1,60 @@
+//! Connection status on the on-board LED.
+
+use embassy_executor::Spawner;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use esp32_kit::mk_static;
+use esp32_kit::status::{Event, StatusLed, StatusSubscriber};
+use esp_hal::ledc::{
+    channel::{self, ChannelIFace},
+    timer::{self, TimerIFace},
+    HighSpeed, Ledc,
+};
+use esp_hal::peripherals::{GPIO2, LEDC};
+use esp_hal::time::Rate;
+
+// Connection events, published by the WiFi tasks
+static STATUS: StatusLed<CriticalSectionRawMutex> = StatusLed::new();
+
+#[embassy_executor::task]
+async fn status_task(
+    events: StatusSubscriber<'static, CriticalSectionRawMutex>,
+    mut channel: channel::Channel<'static, HighSpeed>,
+) {
+    STATUS.run(events, &mut channel).await
+}
+
+/// Shows the connection status on the on-board LED, start it before the WiFi.
+pub fn start_status_led(ledc: LEDC<'static>, led: GPIO2<'static>, spawner: &Spawner) {
+    let ledc = mk_static!(Ledc<'static>, Ledc::new(ledc));
+    let hstimer0 = mk_static!(
+        timer::Timer<'static, HighSpeed>,
+        ledc.timer::<HighSpeed>(timer::Number::Timer0)
+    );
+    hstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency: Rate::from_khz(5),
+        })
+        .unwrap();
+    let mut channel0 = ledc.channel(channel::Number::Channel0, led);
+    channel0
+        .configure(channel::config::Config {
+            timer: hstimer0,
+            duty_pct: 0,
+            pin_config: channel::config::PinConfig::PushPull,
+        })
+        .unwrap();
+
+    // Subscribe right away, so no event published from now on is lost
+    spawner.must_spawn(status_task(STATUS.subscriber().unwrap(), channel0));
+}
+
+pub fn publish(event: Event) {
+    STATUS.publish(event);
+}
+
+/// Shows SOS and parks the calling task, for errors the example cannot recover from.
+pub async fn fatal() -> ! {
+    STATUS.fatal().await
+}
@@ -0,0 +1,86 @@
+//! Compresses the ADC calibration CSVs into small piecewise-linear tables.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+// Largest deviation from a calibration point, leaving headroom for the fixed-point rounding
+const MAX_ERROR_LSB: f64 = 0.9;
+// Corrected readings are stored in 1/16 LSB
+const FIXED_POINT_SCALE: f64 = 16.0;
+
+const TABLES: [(&str, &str); 4] = [
+    ("ADC_0DB", "calibration/adc_0db.csv"),
+    ("ADC_2P5DB", "calibration/adc_2p5db.csv"),
+    ("ADC_6DB", "calibration/adc_6db.csv"),
+    ("ADC_11DB", "calibration/adc_11db.csv"),
+];
+
+fn main() {
+    let mut out = String::new();
+    for (name, path) in TABLES {
+        println!("cargo:rerun-if-changed={path}");
+        let csv = fs::read_to_string(path).unwrap_or_else(|e| panic!("{path}: {e}"));
+        let points = parse_csv(path, &csv);
+        writeln!(out, "pub(crate) const {name}: &[(u16, u16)] = &[").unwrap();
+        for (raw, corrected) in fit(&points) {
+            let fixed = (corrected * FIXED_POINT_SCALE).round() as u16;
+            writeln!(out, "    ({raw}, {fixed}),").unwrap();
+        }
+        writeln!(out, "];").unwrap();
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("adc_calibration.rs"), out).unwrap();
+}
+
+/// Reads `raw,corrected` rows, skipping `#` comments and the header.
+fn parse_csv(path: &str, csv: &str) -> Vec<(u16, f64)> {
+    let mut points: Vec<(u16, f64)> = csv
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("raw"))
+        .map(|line| {
+            let (raw, corrected) = line
+                .split_once(',')
+                .unwrap_or_else(|| panic!("{path}: malformed row `{line}`"));
+            let raw = raw.trim().parse().unwrap_or_else(|e| panic!("{path}: {e}"));
+            let corrected = corrected
+                .trim()
+                .parse()
+                .unwrap_or_else(|e| panic!("{path}: {e}"));
+            (raw, corrected)
+        })
+        .collect();
+    points.sort_by_key(|&(raw, _)| raw);
+    points.dedup_by_key(|&mut (raw, _)| raw);
+    assert!(
+        points.len() >= 2,
+        "{path}: needs at least two calibration points"
+    );
+    points
+}
+
+/// Greedily keeps the fewest points whose linear interpolation stays within `MAX_ERROR_LSB`.
+fn fit(points: &[(u16, f64)]) -> Vec<(u16, f64)> {
+    let interpolate = |(x0, y0): (u16, f64), (x1, y1): (u16, f64), x: u16| {
+        y0 + (y1 - y0) * f64::from(x - x0) / f64::from(x1 - x0)
+    };
+
+    let mut knots = vec![points[0]];
+    let mut start = 0;
+    while start < points.len() - 1 {
+        let mut end = start + 1;
+        while end + 1 < points.len() {
+            let candidate = end + 1;
+            let fits = points[start + 1..candidate].iter().all(|&(x, y)| {
+                (interpolate(points[start], points[candidate], x) - y).abs() <= MAX_ERROR_LSB
+            });
+            if !fits {
+                break;
+            }
+            end = candidate;
+        }
+        knots.push(points[end]);
+        start = end;
+    }
+    knots
+}
@@ -0,0 +1,104 @@
+include!(concat!(env!("OUT_DIR"), "/adc_calibration.rs"));
+
+// Corrected readings are stored in 1/16 LSB, see build.rs
+const FIXED_POINT_SCALE: f64 = 16.0;
+
+/// Mirrors `esp_hal::analog::adc::Attenuation`.
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Attenuation {
+    _0dB,
+    _2p5dB,
+    _6dB,
+    _11dB,
+}
+
+/// Piecewise-linear correction for the ESP32 ADC non-linearity.
+///
+/// The tables are generated by `build.rs` from `calibration/*.csv`. Corrected
+/// readings are on the ideal 0-4095 scale for 0-3.3V, so ratios keep 4095 as
+/// their reference. A saturated ADC only corrects to about 4027 though, so
+/// check for the rails on the raw reading, e.g. with
+/// [`Thermistor::correct`](crate::thermistor::Thermistor::correct).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AdcCalibration {
+    // (raw reading, corrected reading in 1/16 LSB)
+    points: &'static [(u16, u16)],
+}
+
+impl AdcCalibration {
+    pub const fn new(attenuation: Attenuation) -> Self {
+        let points = match attenuation {
+            Attenuation::_0dB => ADC_0DB,
+            Attenuation::_2p5dB => ADC_2P5DB,
+            Attenuation::_6dB => ADC_6DB,
+            Attenuation::_11dB => ADC_11DB,
+        };
+        Self { points }
+    }
+
+    /// Corrected reading for a raw 12-bit ADC value.
+    pub fn correct(&self, raw: u16) -> f64 {
+        let idx = self
+            .points
+            .partition_point(|&(x, _)| x <= raw)
+            .clamp(1, self.points.len() - 1);
+        let (x0, y0) = self.points[idx - 1];
+        let (x1, y1) = self.points[idx];
+        let (y0, y1) = (
+            f64::from(y0) / FIXED_POINT_SCALE,
+            f64::from(y1) / FIXED_POINT_SCALE,
+        );
+        let t = (f64::from(raw) - f64::from(x0)) / f64::from(x1 - x0);
+        y0 + (y1 - y0) * t
+    }
+
+    /// Like [`correct`](Self::correct), rounded back to whole LSBs.
+    pub fn correct_rounded(&self, raw: u16) -> u16 {
+        libm::round(self.correct(raw)) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LUT_11DB: &str = include_str!("../calibration/adc_11db.csv");
+
+    fn lut_11db() -> impl Iterator<Item = (u16, f64)> {
+        LUT_11DB
+            .lines()
+            .filter(|line| !line.starts_with('#') && !line.starts_with("raw"))
+            .map(|line| {
+                let (raw, corrected) = line.split_once(',').unwrap();
+                (raw.parse().unwrap(), corrected.parse().unwrap())
+            })
+    }
+
+    #[test]
+    fn matches_lut_within_one_lsb() {
+        let calibration = AdcCalibration::new(Attenuation::_11dB);
+        assert_eq!(lut_11db().count(), 4096);
+        for (raw, expected) in lut_11db() {
+            let corrected = calibration.correct(raw);
+            assert!(
+                (corrected - expected).abs() < 1.0,
+                "raw {raw}: {corrected} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn table_is_compact() {
+        let size = core::mem::size_of_val(ADC_11DB);
+        assert!(size < 1024, "{size} bytes");
+    }
+
+    #[test]
+    fn uncalibrated_attenuations_pass_through() {
+        let calibration = AdcCalibration::new(Attenuation::_6dB);
+        for raw in [0, 1, 2000, 4095] {
+            assert_eq!(calibration.correct_rounded(raw), raw);
+        }
+    }
+}
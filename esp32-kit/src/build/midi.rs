@@ -0,0 +1,470 @@
+//! Converts standard MIDI files into the `TEMPO`/`MELODY` tables played with
+//! [`crate::music::Song`], so melodies no longer have to be transcribed by hand.
+//!
+//! ```ignore
+//! // build.rs
+//! esp32_kit::build::midi::midi_to_melody(
+//!     "melodies/ode_to_joy.mid",
+//!     "ode_to_joy.rs",
+//!     Selection { track: Some(1), channel: None },
+//! );
+//! ```
+//!
+//! Only the first tempo of the file is used, and where several notes sound at
+//! once the highest one wins.
+
+use std::{env, fmt, fmt::Write as _, fs, path::Path};
+
+use midly::{MetaMessage, MidiMessage, Smf, Timing, TrackEventKind};
+
+// Note values `Song::calc_note_duration` understands, negative ones are dotted
+const DIVIDERS: [i16; 12] = [1, -1, 2, -2, 4, -4, 8, -8, 16, -16, 32, -32];
+// A dotted whole
+const LONGEST_DIVIDER: i16 = -1;
+// Used when the file does not set a tempo
+const DEFAULT_TEMPO: u16 = 120;
+// MIDI keys covered by the `NOTE_*` constants, B0 to DS8
+const LOWEST_KEY: u8 = 23;
+const HIGHEST_KEY: u8 = 111;
+const NOTE_NAMES: [&str; 12] = [
+    "C", "CS", "D", "DS", "E", "F", "FS", "G", "GS", "A", "AS", "B",
+];
+
+/// Which part of the file to import, `None` takes all tracks or channels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Selection {
+    pub track: Option<usize>,
+    pub channel: Option<u8>,
+}
+
+#[derive(Debug)]
+pub enum MidiError {
+    Parse(midly::Error),
+    /// SMPTE timecode timing instead of ticks per beat.
+    Timecode,
+    NoSuchTrack(usize),
+    /// The selection does not contain a single note.
+    NoNotes,
+}
+
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiError::Parse(e) => write!(f, "invalid MIDI file: {e}"),
+            MidiError::Timecode => write!(f, "timecode based MIDI files are not supported"),
+            MidiError::NoSuchTrack(track) => write!(f, "there is no track {track}"),
+            MidiError::NoNotes => write!(f, "no notes in the selected track/channel"),
+        }
+    }
+}
+
+impl std::error::Error for MidiError {}
+
+/// A monophonic melody as MIDI keys (`None` for a rest) and `Song` dividers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedMelody {
+    pub tempo: u16,
+    pub notes: Vec<(Option<u8>, i16)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum NoteEvent {
+    // Ordered so notes end before new ones start on the same tick
+    Off(u8),
+    On(u8),
+}
+
+pub fn convert(smf: &[u8], selection: Selection) -> Result<ImportedMelody, MidiError> {
+    let smf = Smf::parse(smf).map_err(MidiError::Parse)?;
+    let ticks_per_beat = match smf.header.timing {
+        Timing::Metrical(ticks) => ticks.as_int() as u64,
+        Timing::Timecode(..) => return Err(MidiError::Timecode),
+    };
+    if let Some(track) = selection.track {
+        if track >= smf.tracks.len() {
+            return Err(MidiError::NoSuchTrack(track));
+        }
+    }
+
+    let mut tempo = None;
+    let mut events = Vec::new();
+    for (index, track) in smf.tracks.iter().enumerate() {
+        let selected = selection.track.is_none_or(|t| t == index);
+        let mut tick = 0u64;
+        for event in track {
+            tick += event.delta.as_int() as u64;
+            match event.kind {
+                // The tempo usually lives in the first track, whatever is selected
+                TrackEventKind::Meta(MetaMessage::Tempo(us_per_beat)) => {
+                    let bpm = (60_000_000.0 / us_per_beat.as_int() as f64).round();
+                    tempo.get_or_insert(bpm.clamp(1.0, u16::MAX as f64) as u16);
+                }
+                TrackEventKind::Midi { channel, message }
+                    if selected && selection.channel.is_none_or(|c| c == channel.as_int()) =>
+                {
+                    match message {
+                        MidiMessage::NoteOn { key, vel } if vel.as_int() > 0 => {
+                            events.push((tick, NoteEvent::On(key.as_int())))
+                        }
+                        MidiMessage::NoteOn { key, .. } | MidiMessage::NoteOff { key, .. } => {
+                            events.push((tick, NoteEvent::Off(key.as_int())))
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    if !events.iter().any(|(_, e)| matches!(e, NoteEvent::On(_))) {
+        return Err(MidiError::NoNotes);
+    }
+    events.sort();
+
+    // Reduce to the highest sounding note: a new segment starts whenever it changes or is struck again
+    let mut active = [0u8; 128];
+    let mut top: Option<u8> = None;
+    let mut segments: Vec<(u64, Option<u8>)> = vec![(0, None)];
+    let mut i = 0;
+    while i < events.len() {
+        let tick = events[i].0;
+        let mut struck = [false; 128];
+        while i < events.len() && events[i].0 == tick {
+            match events[i].1 {
+                NoteEvent::On(key) => {
+                    active[key as usize] = active[key as usize].saturating_add(1);
+                    struck[key as usize] = true;
+                }
+                NoteEvent::Off(key) => {
+                    active[key as usize] = active[key as usize].saturating_sub(1);
+                }
+            }
+            i += 1;
+        }
+        let new_top = (0..128u8).rev().find(|&key| active[key as usize] > 0);
+        if new_top != top || new_top.is_some_and(|key| struck[key as usize]) {
+            segments.push((tick, new_top));
+            top = new_top;
+        }
+    }
+    // Close the last note at the final event
+    if let Some(&(_, Some(_))) = segments.last() {
+        let end = events.last().map_or(0, |e| e.0);
+        segments.push((end, None));
+    }
+
+    let whole = 4.0 * ticks_per_beat as f64;
+    let shortest = whole / 32.0;
+    let mut notes = Vec::new();
+    // Quantization error is carried over so the melody as a whole stays in time
+    let mut emitted = 0.0;
+    for pair in segments.windows(2) {
+        let ((_, key), (end, _)) = (pair[0], pair[1]);
+        let end = end as f64;
+        loop {
+            let remaining = end - emitted;
+            if remaining < shortest / 2.0 {
+                break;
+            }
+            let divider = nearest_divider(remaining / whole);
+            notes.push((key, divider));
+            emitted += whole * divider_fraction(divider);
+            // A note can not be tied to itself, so one longer than a dotted whole
+            // is struck again for the rest. Shorter ones leave the rounding to the carry
+            if key.is_some() && remaining <= whole * divider_fraction(LONGEST_DIVIDER) {
+                break;
+            }
+        }
+    }
+
+    Ok(ImportedMelody {
+        tempo: tempo.unwrap_or(DEFAULT_TEMPO),
+        notes,
+    })
+}
+
+fn divider_fraction(divider: i16) -> f64 {
+    let fraction = 1.0 / divider.unsigned_abs() as f64;
+    if divider < 0 {
+        fraction * 1.5
+    } else {
+        fraction
+    }
+}
+
+fn nearest_divider(fraction: f64) -> i16 {
+    DIVIDERS
+        .into_iter()
+        .min_by(|&a, &b| {
+            let error = |d| (divider_fraction(d) - fraction).abs();
+            error(a).total_cmp(&error(b))
+        })
+        .unwrap()
+}
+
+/// Name of the `NOTE_*` constant for a MIDI key, moved by octaves into the buzzer's range.
+pub fn note_name(mut key: u8) -> String {
+    while key < LOWEST_KEY {
+        key += 12;
+    }
+    while key > HIGHEST_KEY {
+        key -= 12;
+    }
+    let octave = key / 12 - 1;
+    format!("NOTE_{}{}", NOTE_NAMES[(key % 12) as usize], octave)
+}
+
+impl ImportedMelody {
+    /// `TEMPO` and `MELODY` items, expecting `esp32_kit::music::*` to be in scope.
+    pub fn to_rust(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "pub const TEMPO: u16 = {};", self.tempo).unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "pub const MELODY: [(f64, i16); {}] = [",
+            self.notes.len()
+        )
+        .unwrap();
+        for &(key, divider) in &self.notes {
+            let note = key.map_or_else(|| "REST".to_string(), note_name);
+            writeln!(out, "    ({note}, {divider}),").unwrap();
+        }
+        writeln!(out, "];").unwrap();
+        out
+    }
+}
+
+/// Build script entry point: converts `midi_path` into `$OUT_DIR/out_file`.
+pub fn midi_to_melody(midi_path: &str, out_file: &str, selection: Selection) {
+    println!("cargo:rerun-if-changed={midi_path}");
+    let smf = fs::read(midi_path).unwrap_or_else(|e| panic!("{midi_path}: {e}"));
+    let melody = convert(&smf, selection).unwrap_or_else(|e| panic!("{midi_path}: {e}"));
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join(out_file), melody.to_rust()).unwrap();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use midly::num::{u15, u24, u28, u4, u7};
+    use midly::{Format, Header, TrackEvent};
+
+    const TICKS_PER_BEAT: u16 = 480;
+
+    // Start of pink_panther::MELODY as MIDI keys
+    const PINK_PANTHER: [(Option<u8>, i16); 12] = [
+        (Some(63), 8),
+        (Some(64), -4),
+        (None, 8),
+        (Some(66), 8),
+        (Some(67), -4),
+        (None, 8),
+        (Some(63), 8),
+        (Some(64), -8),
+        (Some(66), 8),
+        (Some(67), -8),
+        (Some(72), 8),
+        (Some(71), -8),
+    ];
+
+    fn ticks(divider: i16) -> u32 {
+        (4.0 * TICKS_PER_BEAT as f64 * divider_fraction(divider)) as u32
+    }
+
+    fn note_on(delta: u32, channel: u8, key: u8) -> TrackEvent<'static> {
+        TrackEvent {
+            delta: u28::new(delta),
+            kind: TrackEventKind::Midi {
+                channel: u4::new(channel),
+                message: MidiMessage::NoteOn {
+                    key: u7::new(key),
+                    vel: u7::new(100),
+                },
+            },
+        }
+    }
+
+    fn note_off(delta: u32, channel: u8, key: u8) -> TrackEvent<'static> {
+        TrackEvent {
+            delta: u28::new(delta),
+            kind: TrackEventKind::Midi {
+                channel: u4::new(channel),
+                message: MidiMessage::NoteOff {
+                    key: u7::new(key),
+                    vel: u7::new(0),
+                },
+            },
+        }
+    }
+
+    fn tempo_track(bpm: u32) -> Vec<TrackEvent<'static>> {
+        vec![TrackEvent {
+            delta: u28::new(0),
+            kind: TrackEventKind::Meta(MetaMessage::Tempo(u24::new(60_000_000 / bpm))),
+        }]
+    }
+
+    fn melody_track(melody: &[(Option<u8>, i16)], channel: u8) -> Vec<TrackEvent<'static>> {
+        let mut track = Vec::new();
+        let mut rest = 0;
+        for &(key, divider) in melody {
+            match key {
+                Some(key) => {
+                    track.push(note_on(rest, channel, key));
+                    track.push(note_off(ticks(divider), channel, key));
+                    rest = 0;
+                }
+                None => rest += ticks(divider),
+            }
+        }
+        track
+    }
+
+    fn write(tracks: Vec<Vec<TrackEvent<'static>>>) -> Vec<u8> {
+        let mut smf = Smf::new(Header::new(
+            Format::Parallel,
+            Timing::Metrical(u15::new(TICKS_PER_BEAT)),
+        ));
+        smf.tracks = tracks;
+        let mut bytes = Vec::new();
+        smf.write_std(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = write(vec![tempo_track(120), melody_track(&PINK_PANTHER, 0)]);
+        let melody = convert(&bytes, Selection::default()).unwrap();
+        assert_eq!(melody.tempo, 120);
+        assert_eq!(melody.notes, PINK_PANTHER);
+    }
+
+    #[test]
+    fn keeps_highest_note_of_chords() {
+        // C major chord held for a half note, then E alone
+        let track = vec![
+            note_on(0, 0, 60),
+            note_on(0, 0, 64),
+            note_on(0, 0, 67),
+            note_off(960, 0, 67),
+            note_off(0, 0, 64),
+            note_off(0, 0, 60),
+            note_on(0, 0, 64),
+            note_off(480, 0, 64),
+        ];
+        let bytes = write(vec![tempo_track(100), track]);
+        let melody = convert(&bytes, Selection::default()).unwrap();
+        assert_eq!(melody.tempo, 100);
+        assert_eq!(melody.notes, [(Some(67), 2), (Some(64), 4)]);
+    }
+
+    #[test]
+    fn falls_back_to_lower_note_and_restrikes() {
+        // Melody on top of a held bass note, then the top note repeated
+        let track = vec![
+            note_on(0, 0, 48),
+            note_on(0, 0, 72),
+            note_off(480, 0, 72),
+            note_on(0, 0, 72),
+            note_off(480, 0, 72),
+            note_off(480, 0, 48),
+        ];
+        let bytes = write(vec![track]);
+        let melody = convert(&bytes, Selection::default()).unwrap();
+        assert_eq!(melody.tempo, DEFAULT_TEMPO);
+        assert_eq!(melody.notes, [(Some(72), 4), (Some(72), 4), (Some(48), 4)]);
+    }
+
+    #[test]
+    fn selects_track_and_channel() {
+        let bass = [(Some(36), 2), (Some(43), 2)];
+        let tracks = vec![
+            tempo_track(90),
+            melody_track(&PINK_PANTHER, 0),
+            melody_track(&bass, 1),
+        ];
+        let bytes = write(tracks);
+
+        let only_bass = Selection {
+            track: Some(2),
+            channel: None,
+        };
+        assert_eq!(convert(&bytes, only_bass).unwrap().notes, bass);
+        let channel_0 = Selection {
+            track: None,
+            channel: Some(0),
+        };
+        assert_eq!(convert(&bytes, channel_0).unwrap().notes, PINK_PANTHER);
+        assert!(matches!(
+            convert(
+                &bytes,
+                Selection {
+                    track: Some(3),
+                    channel: None
+                }
+            ),
+            Err(MidiError::NoSuchTrack(3))
+        ));
+        assert!(matches!(
+            convert(
+                &bytes,
+                Selection {
+                    track: Some(0),
+                    channel: None
+                }
+            ),
+            Err(MidiError::NoNotes)
+        ));
+    }
+
+    #[test]
+    fn odd_lengths_are_quantized_without_drift() {
+        // Five notes of 1.2 beats each, the carry makes some of the quarters dotted
+        let track: Vec<_> = (0..5)
+            .flat_map(|_| [note_on(0, 0, 60), note_off(576, 0, 60)])
+            .collect();
+        let bytes = write(vec![track]);
+        let melody = convert(&bytes, Selection::default()).unwrap();
+        let total: f64 = melody
+            .notes
+            .iter()
+            .map(|&(_, d)| 4.0 * divider_fraction(d))
+            .sum();
+        assert_eq!(total, 6.0);
+    }
+
+    #[test]
+    fn repeats_notes_longer_than_a_dotted_whole() {
+        // Two wholes, then a quarter that must not lose half a whole to the first note
+        let track = vec![
+            note_on(0, 0, 60),
+            note_off(3840, 0, 60),
+            note_on(0, 0, 64),
+            note_off(480, 0, 64),
+        ];
+        let bytes = write(vec![track]);
+        let melody = convert(&bytes, Selection::default()).unwrap();
+        assert_eq!(melody.notes, [(Some(60), -1), (Some(60), 2), (Some(64), 4)]);
+    }
+
+    #[test]
+    fn generates_note_constants() {
+        assert_eq!(note_name(60), "NOTE_C4");
+        assert_eq!(note_name(63), "NOTE_DS4");
+        assert_eq!(note_name(23), "NOTE_B0");
+        assert_eq!(note_name(111), "NOTE_DS8");
+        // Outside the table, moved by octaves
+        assert_eq!(note_name(12), "NOTE_C1");
+        assert_eq!(note_name(120), "NOTE_C8");
+
+        let melody = ImportedMelody {
+            tempo: 120,
+            notes: vec![(Some(63), 8), (None, -4)],
+        };
+        assert_eq!(
+            melody.to_rust(),
+            "pub const TEMPO: u16 = 120;\n\npub const MELODY: [(f64, i16); 2] = [\n    (NOTE_DS4, 8),\n    (REST, -4),\n];\n"
+        );
+    }
+}
@@ -0,0 +1,405 @@
+//! Push button debouncing and gestures.
+//!
+//! The raw level goes in through [`Gestures::input`] (on every edge or every
+//! sample), events come out of [`Gestures::poll`]. Like [`crate::motion`],
+//! time is passed in as milliseconds so edge lists can be replayed on the host.
+//! With the `embassy` feature, [`Button`] runs the same state machine on the
+//! edge interrupts of an input pin.
+
+use core::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GestureConfig {
+    /// A level has to be stable this long to count.
+    pub debounce_ms: u64,
+    /// Longest gap between two clicks of a double click.
+    pub double_click_ms: u64,
+    /// Holding at least this long is a long press instead of a click.
+    pub long_press_ms: u64,
+    /// Interval of [`Event::Repeat`] while held after a long press, `None` for no repeats.
+    pub repeat_ms: Option<u64>,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            debounce_ms: 30,
+            double_click_ms: 300,
+            long_press_ms: 800,
+            repeat_ms: Some(200),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Debounced press, sent for every press before any gesture.
+    Press,
+    Release,
+    Click,
+    DoubleClick,
+    /// Sent once while the button is still held, with the time held so far.
+    LongPress(Duration),
+    /// Sent every `repeat_ms` while held after the long press, like a held key.
+    Repeat,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Gestures {
+    config: GestureConfig,
+    raw: bool,
+    raw_since: u64,
+    pressed: bool,
+    pressed_at: u64,
+    released_at: u64,
+    // A first click is waiting to see whether a second one follows
+    click_pending: bool,
+    long_press_sent: bool,
+    next_repeat: u64,
+    // Second event of an edge, e.g. the double click after its release
+    queued: Option<Event>,
+}
+
+impl Gestures {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            raw: false,
+            raw_since: 0,
+            pressed: false,
+            pressed_at: 0,
+            released_at: 0,
+            click_pending: false,
+            long_press_sent: false,
+            next_repeat: 0,
+            queued: None,
+        }
+    }
+
+    /// Debounced level.
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// Feeds the raw level, `true` while pressed.
+    pub fn input(&mut self, now_ms: u64, pressed: bool) {
+        if pressed != self.raw {
+            self.raw = pressed;
+            self.raw_since = now_ms;
+        }
+    }
+
+    /// Next event due at `now_ms`. Call again until `None`.
+    pub fn poll(&mut self, now_ms: u64) -> Option<Event> {
+        if let Some(event) = self.queued.take() {
+            return Some(event);
+        }
+        if self.raw != self.pressed && now_ms >= self.raw_since + self.config.debounce_ms {
+            // The edge happened when the level started to settle
+            let at = self.raw_since;
+            self.pressed = self.raw;
+            if self.pressed {
+                self.pressed_at = at;
+                self.long_press_sent = false;
+                return Some(Event::Press);
+            }
+            if !self.long_press_sent {
+                self.released_at = at;
+                if self.click_pending {
+                    self.click_pending = false;
+                    self.queued = Some(Event::DoubleClick);
+                } else {
+                    self.click_pending = true;
+                }
+            }
+            return Some(Event::Release);
+        }
+        if self.pressed && !self.long_press_sent {
+            let long_at = self.pressed_at + self.config.long_press_ms;
+            if now_ms >= long_at {
+                self.long_press_sent = true;
+                self.next_repeat = long_at;
+                let held = Duration::from_millis(now_ms - self.pressed_at);
+                // The first click still counts when the second press turns long
+                if self.click_pending {
+                    self.click_pending = false;
+                    self.queued = Some(Event::LongPress(held));
+                    return Some(Event::Click);
+                }
+                return Some(Event::LongPress(held));
+            }
+        }
+        if let (true, true, Some(repeat_ms)) =
+            (self.pressed, self.long_press_sent, self.config.repeat_ms)
+        {
+            if now_ms >= self.next_repeat + repeat_ms {
+                // Keep the cadence even if polled late, but never send a burst
+                self.next_repeat = now_ms - (now_ms - self.next_repeat) % repeat_ms;
+                return Some(Event::Repeat);
+            }
+        }
+        if self.click_pending
+            && !self.pressed
+            && now_ms >= self.released_at + self.config.double_click_ms
+        {
+            self.click_pending = false;
+            return Some(Event::Click);
+        }
+        None
+    }
+
+    /// When `poll` has to run next, if nothing else changes.
+    pub fn next_deadline(&self) -> Option<u64> {
+        if self.queued.is_some() {
+            return Some(0);
+        }
+        let debounce =
+            (self.raw != self.pressed).then_some(self.raw_since + self.config.debounce_ms);
+        let long = (self.pressed && !self.long_press_sent)
+            .then_some(self.pressed_at + self.config.long_press_ms);
+        let repeat = self
+            .config
+            .repeat_ms
+            .filter(|_| self.pressed && self.long_press_sent)
+            .map(|repeat_ms| self.next_repeat + repeat_ms);
+        let click = (self.click_pending && !self.pressed)
+            .then_some(self.released_at + self.config.double_click_ms);
+        [debounce, long, repeat, click].into_iter().flatten().min()
+    }
+}
+
+impl Default for Gestures {
+    fn default() -> Self {
+        Self::new(GestureConfig::default())
+    }
+}
+
+#[cfg(feature = "embassy")]
+pub use driver::Button;
+
+#[cfg(feature = "embassy")]
+mod driver {
+    use embassy_futures::select::select;
+    use embassy_sync::blocking_mutex::raw::RawMutex;
+    use embassy_sync::channel::Channel;
+    use embassy_time::{Instant, Timer};
+    use embedded_hal::digital::InputPin;
+    use embedded_hal_async::digital::Wait;
+
+    use super::{Event, GestureConfig, Gestures};
+
+    /// Debounced button on the edge interrupts of an input pin, e.g. `esp_hal::gpio::Input`.
+    ///
+    /// ```ignore
+    /// static EVENTS: Channel<CriticalSectionRawMutex, Event, 8> = Channel::new();
+    ///
+    /// #[embassy_executor::task]
+    /// async fn button_task(mut button: Button<Input<'static>>) {
+    ///     button.run(&EVENTS).await
+    /// }
+    /// ```
+    pub struct Button<P> {
+        pin: P,
+        active_low: bool,
+        gestures: Gestures,
+    }
+
+    impl<P: InputPin + Wait> Button<P> {
+        /// `active_low` for a button to GND with a pull-up, like the BOOT button.
+        pub fn new(pin: P, active_low: bool, config: GestureConfig) -> Self {
+            Self {
+                pin,
+                active_low,
+                gestures: Gestures::new(config),
+            }
+        }
+
+        fn is_pressed(&mut self) -> bool {
+            // A pin read cannot fail on the ESP32
+            self.pin.is_low().unwrap_or(false) == self.active_low
+        }
+
+        /// Sends the events of this button to `events` forever.
+        pub async fn run<M: RawMutex, const N: usize>(
+            &mut self,
+            events: &Channel<M, Event, N>,
+        ) -> ! {
+            loop {
+                let pressed = self.is_pressed();
+                let now_ms = Instant::now().as_millis();
+                self.gestures.input(now_ms, pressed);
+                while let Some(event) = self.gestures.poll(now_ms) {
+                    events.send(event).await;
+                }
+
+                // Wake on the next edge, or when debouncing or a gesture is due
+                let edge = self.pin.wait_for_any_edge();
+                match self.gestures.next_deadline() {
+                    Some(deadline) => {
+                        select(edge, Timer::at(Instant::from_millis(deadline))).await;
+                    }
+                    None => {
+                        let _ = edge.await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Replays `(time, pressed)` edges, polling every millisecond
+    fn replay(edges: &[(u64, bool)], until_ms: u64) -> Vec<(u64, Event)> {
+        replay_with(GestureConfig::default(), edges, until_ms)
+    }
+
+    fn replay_with(
+        config: GestureConfig,
+        edges: &[(u64, bool)],
+        until_ms: u64,
+    ) -> Vec<(u64, Event)> {
+        let mut gestures = Gestures::new(config);
+        let mut edges = edges.iter().peekable();
+        let mut out = Vec::new();
+        for now in 0..=until_ms {
+            while let Some(&&(at, level)) = edges.peek() {
+                if at > now {
+                    break;
+                }
+                gestures.input(at, level);
+                edges.next();
+            }
+            while let Some(event) = gestures.poll(now) {
+                out.push((now, event));
+            }
+        }
+        out
+    }
+
+    // Only the gestures, without press and release
+    fn gestures(events: Vec<(u64, Event)>) -> Vec<(u64, Event)> {
+        events
+            .into_iter()
+            .filter(|(_, e)| !matches!(e, Event::Press | Event::Release))
+            .collect()
+    }
+
+    #[test]
+    fn click_after_double_click_window() {
+        let events = replay(&[(100, true), (200, false)], 1000);
+        assert_eq!(
+            events,
+            [
+                (130, Event::Press),
+                (230, Event::Release),
+                (500, Event::Click)
+            ]
+        );
+    }
+
+    #[test]
+    fn bounces_are_ignored() {
+        // Contact bounce on press and on release, plus a 5 ms glitch later on
+        let edges = [
+            (100, true),
+            (102, false),
+            (103, true),
+            (250, false),
+            (251, true),
+            (253, false),
+            (900, true),
+            (905, false),
+        ];
+        let events = replay(&edges, 2000);
+        assert_eq!(
+            events,
+            [
+                (133, Event::Press),
+                (283, Event::Release),
+                (553, Event::Click)
+            ]
+        );
+    }
+
+    #[test]
+    fn double_click() {
+        let edges = [(100, true), (180, false), (300, true), (380, false)];
+        let events = replay(&edges, 2000);
+        assert_eq!(
+            events,
+            [
+                (130, Event::Press),
+                (210, Event::Release),
+                (330, Event::Press),
+                (410, Event::Release),
+                (410, Event::DoubleClick)
+            ]
+        );
+    }
+
+    #[test]
+    fn long_press_then_repeats() {
+        let edges = [(100, true), (1500, false)];
+        let events = replay(&edges, 3000);
+        assert_eq!(
+            gestures(events),
+            [
+                (900, Event::LongPress(Duration::from_millis(800))),
+                (1100, Event::Repeat),
+                (1300, Event::Repeat),
+                (1500, Event::Repeat),
+            ]
+        );
+
+        let config = GestureConfig {
+            repeat_ms: None,
+            ..GestureConfig::default()
+        };
+        let events = replay_with(config, &edges, 3000);
+        assert_eq!(
+            events,
+            [
+                (130, Event::Press),
+                (900, Event::LongPress(Duration::from_millis(800))),
+                (1530, Event::Release)
+            ]
+        );
+    }
+
+    #[test]
+    fn click_then_long_press() {
+        // The second press comes within the double click window but is held
+        let events = gestures(replay(&[(100, true), (200, false), (300, true)], 1200));
+        assert_eq!(
+            events,
+            [
+                (1100, Event::Click),
+                (1100, Event::LongPress(Duration::from_millis(800)))
+            ]
+        );
+    }
+
+    #[test]
+    fn late_polls_keep_the_repeat_cadence() {
+        let mut gestures = Gestures::default();
+        gestures.input(0, true);
+        assert_eq!(gestures.next_deadline(), Some(30));
+        assert_eq!(gestures.poll(30), Some(Event::Press));
+        assert!(gestures.is_pressed());
+        assert_eq!(gestures.next_deadline(), Some(800));
+        // Polled late, the time held is reported as it is
+        assert_eq!(
+            gestures.poll(850),
+            Some(Event::LongPress(Duration::from_millis(850)))
+        );
+        assert_eq!(gestures.poll(850), None);
+        assert_eq!(gestures.next_deadline(), Some(1000));
+        // Three periods overdue, still a single repeat and back on the grid
+        assert_eq!(gestures.poll(1450), Some(Event::Repeat));
+        assert_eq!(gestures.poll(1450), None);
+        assert_eq!(gestures.next_deadline(), Some(1600));
+    }
+}
@@ -0,0 +1,494 @@
+//! Wall clock time on top of a free running microsecond counter.
+//!
+//! The counter, e.g. `Rtc::current_time_us`, is set from time to time, e.g.
+//! with [`crate::sntp`]. [`WallClock`] learns how far it drifts in between
+//! and turns its readings into local time with a [`TimeZone`].
+
+use core::fmt;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+// Shortest time between syncs the drift is measured over
+const MIN_DRIFT_INTERVAL_US: u64 = 60_000_000;
+// Weight of a new drift measurement
+const DRIFT_SMOOTHING: f32 = 0.25;
+// Anything more is a clock that was set by someone else, not drift
+const MAX_DRIFT_PPM: f32 = 500.0;
+
+/// Days since 1970-01-01 of a date in the proleptic Gregorian calendar.
+pub fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = year as i64 - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Year, month and day of a number of days since 1970-01-01.
+pub fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u8;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u8;
+    let year = (era * 400 + year_of_era) as i32 + (month <= 2) as i32;
+    (year, month, day)
+}
+
+// 0 is Sunday, 1970-01-01 was a Thursday
+fn weekday_of(days: i64) -> u8 {
+    (days + 4).rem_euclid(7) as u8
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    let next = if month == 12 {
+        days_from_civil(year + 1, 1, 1)
+    } else {
+        days_from_civil(year, month + 1, 1)
+    };
+    (next - days_from_civil(year, month, 1)) as u8
+}
+
+/// A calendar date and time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DateTime {
+    pub year: i32,
+    /// 1 to 12
+    pub month: u8,
+    /// 1 to 31
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl DateTime {
+    /// The date and time `secs` seconds after 1970-01-01 00:00:00.
+    pub fn from_unix(secs: i64) -> Self {
+        let (year, month, day) = civil_from_days(secs.div_euclid(SECS_PER_DAY));
+        let time = secs.rem_euclid(SECS_PER_DAY);
+        Self {
+            year,
+            month,
+            day,
+            hour: (time / 3600) as u8,
+            minute: (time / 60 % 60) as u8,
+            second: (time % 60) as u8,
+        }
+    }
+
+    pub fn to_unix(&self) -> i64 {
+        days_from_civil(self.year, self.month, self.day) * SECS_PER_DAY
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64
+    }
+
+    /// 0 is Sunday.
+    pub fn weekday(&self) -> u8 {
+        weekday_of(days_from_civil(self.year, self.month, self.day))
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            self.year, self.month, self.day, self.hour, self.minute, self.second
+        )
+    }
+}
+
+/// Which weekday of the month a change happens on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Week {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Last,
+}
+
+/// When daylight saving time starts or ends, e.g. the last Sunday of March.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Transition {
+    pub month: u8,
+    pub week: Week,
+    /// 0 is Sunday.
+    pub weekday: u8,
+    /// Minutes after midnight in local standard time, the end of US summer
+    /// time at 02:00 summer time is 60.
+    pub minute: u16,
+}
+
+impl Transition {
+    // Seconds since the epoch, in local standard time, of the change in `year`
+    fn local_standard_secs(&self, year: i32) -> i64 {
+        let first = days_from_civil(year, self.month, 1);
+        let first_weekday = weekday_of(first);
+        let first_match = (self.weekday + 7 - first_weekday) % 7;
+        let day = match self.week {
+            Week::First => first_match,
+            Week::Second => first_match + 7,
+            Week::Third => first_match + 14,
+            Week::Fourth => first_match + 21,
+            Week::Last => {
+                let last = days_in_month(year, self.month) - 1;
+                last - (weekday_of(first + last as i64) + 7 - self.weekday) % 7
+            }
+        };
+        (first + day as i64) * SECS_PER_DAY + self.minute as i64 * 60
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DstRule {
+    pub start: Transition,
+    pub end: Transition,
+    /// How far clocks go forward, usually 60.
+    pub save_minutes: i16,
+}
+
+/// Offset from UTC and, where clocks change, the rule they change by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeZone {
+    /// Standard time offset east of UTC in minutes.
+    pub offset_minutes: i16,
+    pub dst: Option<DstRule>,
+}
+
+impl TimeZone {
+    pub const UTC: Self = Self::fixed(0);
+    /// China Standard Time, UTC+8 all year.
+    pub const CHINA: Self = Self::fixed(8 * 60);
+    /// CET/CEST, summer time from the last Sunday of March to the last Sunday of October.
+    pub const CENTRAL_EUROPE: Self = Self::european(60);
+    /// EST/EDT, summer time from the second Sunday of March to the first Sunday of November.
+    pub const US_EASTERN: Self = Self::north_american(-5 * 60);
+    /// PST/PDT
+    pub const US_PACIFIC: Self = Self::north_american(-8 * 60);
+
+    /// A zone without daylight saving time.
+    pub const fn fixed(offset_minutes: i16) -> Self {
+        Self {
+            offset_minutes,
+            dst: None,
+        }
+    }
+
+    /// EU rules, where clocks change at 01:00 UTC in every zone.
+    pub const fn european(offset_minutes: i16) -> Self {
+        let minute = (60 + offset_minutes) as u16;
+        Self {
+            offset_minutes,
+            dst: Some(DstRule {
+                start: Transition {
+                    month: 3,
+                    week: Week::Last,
+                    weekday: 0,
+                    minute,
+                },
+                end: Transition {
+                    month: 10,
+                    week: Week::Last,
+                    weekday: 0,
+                    minute,
+                },
+                save_minutes: 60,
+            }),
+        }
+    }
+
+    /// US and Canadian rules, clocks change at 02:00 local time.
+    pub const fn north_american(offset_minutes: i16) -> Self {
+        Self {
+            offset_minutes,
+            dst: Some(DstRule {
+                start: Transition {
+                    month: 3,
+                    week: Week::Second,
+                    weekday: 0,
+                    minute: 120,
+                },
+                end: Transition {
+                    month: 11,
+                    week: Week::First,
+                    weekday: 0,
+                    minute: 60,
+                },
+                save_minutes: 60,
+            }),
+        }
+    }
+
+    /// Whether summer time is in effect at `unix_secs`.
+    pub fn is_dst(&self, unix_secs: i64) -> bool {
+        let Some(rule) = self.dst else {
+            return false;
+        };
+        let standard = unix_secs + self.offset_minutes as i64 * 60;
+        let year = DateTime::from_unix(standard).year;
+        let start = rule.start.local_standard_secs(year);
+        let end = rule.end.local_standard_secs(year);
+        if start < end {
+            (start..end).contains(&standard)
+        } else {
+            // Southern hemisphere, summer spans the new year
+            standard >= start || standard < end
+        }
+    }
+
+    /// Offset from UTC in minutes at `unix_secs`, summer time included.
+    pub fn offset_at(&self, unix_secs: i64) -> i16 {
+        match self.dst {
+            Some(rule) if self.is_dst(unix_secs) => self.offset_minutes + rule.save_minutes,
+            _ => self.offset_minutes,
+        }
+    }
+
+    /// Local date and time at `unix_secs`.
+    pub fn local(&self, unix_secs: i64) -> DateTime {
+        DateTime::from_unix(unix_secs + self.offset_at(unix_secs) as i64 * 60)
+    }
+}
+
+/// How fast a clock runs compared to the time it is set to.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Drift {
+    ppm: Option<f32>,
+}
+
+impl Drift {
+    pub const fn new() -> Self {
+        Self { ppm: None }
+    }
+
+    /// Parts per million the clock gains, negative when it loses time.
+    pub fn ppm(&self) -> Option<f32> {
+        self.ppm
+    }
+
+    /// Records that the clock was `offset_us` behind `elapsed_us` after it was last set.
+    ///
+    /// Measurements over less than a minute are too noisy and are ignored.
+    pub fn update(&mut self, elapsed_us: u64, offset_us: i64) {
+        if elapsed_us < MIN_DRIFT_INTERVAL_US {
+            return;
+        }
+        let ppm = -(offset_us as f32) / elapsed_us as f32 * 1e6;
+        if ppm.abs() > MAX_DRIFT_PPM {
+            return;
+        }
+        self.ppm = Some(match self.ppm {
+            Some(old) => old + DRIFT_SMOOTHING * (ppm - old),
+            None => ppm,
+        });
+    }
+
+    /// Microseconds to add to a reading taken `elapsed_us` after the clock was set.
+    pub fn correction_us(&self, elapsed_us: u64) -> i64 {
+        self.ppm.map_or(0, |ppm| {
+            libm::roundf(-ppm * (elapsed_us as f32 / 1e6)) as i64
+        })
+    }
+}
+
+/// Local time from a microsecond counter that is set to UTC now and then.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallClock {
+    zone: TimeZone,
+    // Counter reading right after it was last set
+    synced_at_us: Option<u64>,
+    drift: Drift,
+}
+
+impl WallClock {
+    pub const fn new(zone: TimeZone) -> Self {
+        Self {
+            zone,
+            synced_at_us: None,
+            drift: Drift::new(),
+        }
+    }
+
+    pub fn zone(&self) -> TimeZone {
+        self.zone
+    }
+
+    pub fn set_zone(&mut self, zone: TimeZone) {
+        self.zone = zone;
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced_at_us.is_some()
+    }
+
+    pub fn drift(&self) -> Drift {
+        self.drift
+    }
+
+    /// Takes a measured offset, `counter_us` plus `offset_us` being the right time.
+    ///
+    /// Returns what to set the counter to, e.g. with `Rtc::set_current_time_us`.
+    pub fn sync(&mut self, counter_us: u64, offset_us: i64) -> u64 {
+        if let Some(synced_at) = self.synced_at_us {
+            self.drift
+                .update(counter_us.saturating_sub(synced_at), offset_us);
+        }
+        let now = counter_us.saturating_add_signed(offset_us);
+        self.synced_at_us = Some(now);
+        now
+    }
+
+    /// UTC in microseconds since 1970 for a counter reading, `None` before the first sync.
+    pub fn utc_us(&self, counter_us: u64) -> Option<u64> {
+        let elapsed = counter_us.saturating_sub(self.synced_at_us?);
+        Some(counter_us.saturating_add_signed(self.drift.correction_us(elapsed)))
+    }
+
+    /// Local date and time for a counter reading, `None` before the first sync.
+    pub fn local(&self, counter_us: u64) -> Option<DateTime> {
+        let utc_secs = (self.utc_us(counter_us)? / 1_000_000) as i64;
+        Some(self.zone.local(utc_secs))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unix(year: i32, month: u8, day: u8, hour: u8, minute: u8) -> i64 {
+        DateTime {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second: 0,
+        }
+        .to_unix()
+    }
+
+    #[test]
+    fn calendar() {
+        assert_eq!(DateTime::from_unix(0).to_string(), "1970-01-01 00:00:00");
+        assert_eq!(
+            DateTime::from_unix(1_709_210_096).to_string(),
+            "2024-02-29 12:34:56"
+        );
+        assert_eq!(DateTime::from_unix(-1).to_string(), "1969-12-31 23:59:59");
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        // Every day of four centuries round trips
+        for days in -146_097..146_097 {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days_from_civil(year, month, day), days);
+        }
+        assert_eq!(DateTime::from_unix(0).weekday(), 4);
+        assert_eq!(days_in_month(2024, 2), 29);
+        assert_eq!(days_in_month(2100, 2), 28);
+    }
+
+    #[test]
+    fn european_summer_time() {
+        let zone = TimeZone::CENTRAL_EUROPE;
+        // 2025 changed on March 30 and October 26, at 01:00 UTC
+        assert!(!zone.is_dst(unix(2025, 3, 30, 0, 59) + 59));
+        assert!(zone.is_dst(unix(2025, 3, 30, 1, 0)));
+        assert_eq!(
+            zone.local(unix(2025, 3, 30, 0, 59)).to_string(),
+            "2025-03-30 01:59:00"
+        );
+        assert_eq!(
+            zone.local(unix(2025, 3, 30, 1, 0)).to_string(),
+            "2025-03-30 03:00:00"
+        );
+        assert!(zone.is_dst(unix(2025, 10, 26, 0, 59)));
+        assert!(!zone.is_dst(unix(2025, 10, 26, 1, 0)));
+        assert_eq!(zone.offset_at(unix(2025, 7, 1, 12, 0)), 120);
+        assert_eq!(zone.offset_at(unix(2025, 12, 1, 12, 0)), 60);
+
+        // Same instant in London
+        let london = TimeZone::european(0);
+        assert!(london.is_dst(unix(2025, 3, 30, 1, 0)));
+        assert!(!london.is_dst(unix(2025, 3, 30, 0, 59)));
+    }
+
+    #[test]
+    fn north_american_and_southern_rules() {
+        let zone = TimeZone::US_EASTERN;
+        // 2025: March 9 at 02:00 EST (07:00 UTC), November 2 at 02:00 EDT (06:00 UTC)
+        assert!(!zone.is_dst(unix(2025, 3, 9, 6, 59)));
+        assert!(zone.is_dst(unix(2025, 3, 9, 7, 0)));
+        assert!(zone.is_dst(unix(2025, 11, 2, 5, 59)));
+        assert!(!zone.is_dst(unix(2025, 11, 2, 6, 0)));
+        assert_eq!(
+            zone.local(unix(2025, 7, 4, 16, 0)).to_string(),
+            "2025-07-04 12:00:00"
+        );
+
+        // Sydney: summer from the first Sunday of October to the first Sunday of April
+        let sydney = TimeZone {
+            offset_minutes: 600,
+            dst: Some(DstRule {
+                start: Transition {
+                    month: 10,
+                    week: Week::First,
+                    weekday: 0,
+                    minute: 120,
+                },
+                end: Transition {
+                    month: 4,
+                    week: Week::First,
+                    weekday: 0,
+                    minute: 120,
+                },
+                save_minutes: 60,
+            }),
+        };
+        assert!(sydney.is_dst(unix(2025, 1, 15, 0, 0)));
+        assert!(!sydney.is_dst(unix(2025, 7, 15, 0, 0)));
+        assert!(sydney.is_dst(unix(2025, 12, 31, 12, 0)));
+        assert!(!TimeZone::CHINA.is_dst(unix(2025, 7, 1, 0, 0)));
+        assert_eq!(TimeZone::CHINA.local(0).to_string(), "1970-01-01 08:00:00");
+    }
+
+    #[test]
+    fn drift_is_learned_between_syncs() {
+        let mut clock = WallClock::new(TimeZone::UTC);
+        assert_eq!(clock.local(0), None);
+
+        let t0: u64 = 1_750_000_000_000_000;
+        // The counter starts at zero after a reset
+        let set = clock.sync(5_000_000, t0 as i64 - 5_000_000);
+        assert_eq!(set, t0);
+        assert_eq!(clock.drift().ppm(), None);
+        assert_eq!(clock.local(t0).unwrap(), DateTime::from_unix(1_750_000_000));
+
+        // An hour later the counter is 72 ms ahead, it gains 20 ppm
+        let hour: u64 = 3_600_000_000;
+        let set = clock.sync(t0 + hour + 72_000, -72_000);
+        assert_eq!(set, t0 + hour);
+        let ppm = clock.drift().ppm().unwrap();
+        assert!((ppm - 20.0).abs() < 0.01);
+        // Which is taken off readings until the next sync
+        let reading = clock.utc_us(set + hour + 72_000).unwrap();
+        assert!((reading as i64 - (set + hour) as i64).abs() < 100);
+
+        // Jumps are not drift, nor are syncs in quick succession
+        let before = clock.drift();
+        let now = t0 + 2 * hour;
+        clock.sync(now, 10_000_000);
+        clock.sync(now + 10_000_000 + 1_000, 5_000);
+        assert_eq!(clock.drift(), before);
+    }
+}
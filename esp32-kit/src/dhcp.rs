@@ -0,0 +1,729 @@
+//! DHCPv4 server for a soft access point.
+//!
+//! [`Server`] hands out addresses from a small pool and keeps one lease per
+//! client MAC address. Requests go in and replies come out as byte slices, the
+//! UDP socket stays with the caller, so the whole exchange runs on the host.
+
+use core::net::Ipv4Addr;
+
+pub const SERVER_PORT: u16 = 67;
+pub const CLIENT_PORT: u16 = 68;
+
+const MAGIC_COOKIE: [u8; 4] = [99, 130, 83, 99];
+const OPTIONS: usize = 240;
+// Some clients drop replies shorter than a BOOTP message
+const MIN_REPLY_LEN: usize = 300;
+/// Room needed for any reply.
+pub const MAX_REPLY_LEN: usize = 576;
+
+// Owner of an address a client found in use
+const DECLINED: [u8; 6] = [0; 6];
+
+const OP_REQUEST: u8 = 1;
+const OP_REPLY: u8 = 2;
+
+const OPTION_PAD: u8 = 0;
+const OPTION_SUBNET_MASK: u8 = 1;
+const OPTION_ROUTER: u8 = 3;
+const OPTION_DNS: u8 = 6;
+const OPTION_REQUESTED_IP: u8 = 50;
+const OPTION_LEASE_TIME: u8 = 51;
+const OPTION_MESSAGE_TYPE: u8 = 53;
+const OPTION_SERVER_ID: u8 = 54;
+const OPTION_END: u8 = 255;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageType {
+    Discover = 1,
+    Offer = 2,
+    Request = 3,
+    Decline = 4,
+    Ack = 5,
+    Nak = 6,
+    Release = 7,
+    Inform = 8,
+}
+
+impl MessageType {
+    fn from_u8(value: u8) -> Option<Self> {
+        Some(match value {
+            1 => Self::Discover,
+            2 => Self::Offer,
+            3 => Self::Request,
+            4 => Self::Decline,
+            5 => Self::Ack,
+            6 => Self::Nak,
+            7 => Self::Release,
+            8 => Self::Inform,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// Address of the access point itself, also sent as router and DNS server.
+    pub address: Ipv4Addr,
+    pub netmask: Ipv4Addr,
+    /// First address of the pool, the others follow it.
+    pub pool_start: Ipv4Addr,
+    pub lease_secs: u32,
+}
+
+impl ServerConfig {
+    /// The usual soft-AP network, 192.168.4.1/24 handing out .2 onwards.
+    pub const fn soft_ap() -> Self {
+        Self {
+            address: Ipv4Addr::new(192, 168, 4, 1),
+            netmask: Ipv4Addr::new(255, 255, 255, 0),
+            pool_start: Ipv4Addr::new(192, 168, 4, 2),
+            lease_secs: 2 * 60 * 60,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Checks that a pool of `pool_size` addresses lies inside the subnet, leaving
+    /// out its network and broadcast addresses and the server's own.
+    pub fn validate(&self, pool_size: usize) -> Result<(), ConfigError> {
+        let mask = u32::from(self.netmask);
+        if mask.leading_ones() + mask.trailing_zeros() != 32 {
+            return Err(ConfigError::InvalidNetmask);
+        }
+        let network = u32::from(self.address) & mask;
+        let broadcast = network | !mask;
+        let address = u32::from(self.address);
+        if address == network || address == broadcast {
+            return Err(ConfigError::InvalidAddress);
+        }
+        if pool_size == 0 {
+            return Ok(());
+        }
+        let first = u32::from(self.pool_start);
+        let last = u32::try_from(pool_size - 1)
+            .ok()
+            .and_then(|n| first.checked_add(n))
+            .ok_or(ConfigError::PoolOutsideSubnet)?;
+        if first <= network || last >= broadcast || first & mask != network {
+            return Err(ConfigError::PoolOutsideSubnet);
+        }
+        if (first..=last).contains(&address) {
+            return Err(ConfigError::PoolContainsServer);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigError {
+    /// The netmask is not a run of ones followed by zeros.
+    InvalidNetmask,
+    /// The server address is the network or broadcast address of its subnet.
+    InvalidAddress,
+    /// The pool starts or ends outside the usable addresses of the subnet.
+    PoolOutsideSubnet,
+    /// The pool includes the server's own address.
+    PoolContainsServer,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self::soft_ap()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lease {
+    pub mac: [u8; 6],
+    pub address: Ipv4Addr,
+    pub expires_s: u64,
+}
+
+// The fields of a request the server looks at
+struct Request<'a> {
+    packet: &'a [u8],
+    message_type: MessageType,
+    mac: [u8; 6],
+    ciaddr: Ipv4Addr,
+    requested: Option<Ipv4Addr>,
+    server_id: Option<Ipv4Addr>,
+}
+
+impl<'a> Request<'a> {
+    fn parse(packet: &'a [u8]) -> Option<Self> {
+        // Ethernet addresses only
+        if packet.len() < OPTIONS || packet[0] != OP_REQUEST || packet[1] != 1 || packet[2] != 6 {
+            return None;
+        }
+        if packet[236..240] != MAGIC_COOKIE {
+            return None;
+        }
+        let mut message_type = None;
+        let mut requested = None;
+        let mut server_id = None;
+        let address = |value: &[u8]| -> Option<Ipv4Addr> {
+            let octets: [u8; 4] = value.try_into().ok()?;
+            Some(octets.into())
+        };
+        let mut i = OPTIONS;
+        while i < packet.len() {
+            let code = packet[i];
+            match code {
+                OPTION_PAD => i += 1,
+                OPTION_END => break,
+                _ => {
+                    let len = *packet.get(i + 1)? as usize;
+                    let value = packet.get(i + 2..i + 2 + len)?;
+                    match code {
+                        OPTION_MESSAGE_TYPE => message_type = MessageType::from_u8(*value.first()?),
+                        OPTION_REQUESTED_IP => requested = address(value),
+                        OPTION_SERVER_ID => server_id = address(value),
+                        _ => {}
+                    }
+                    i += 2 + len;
+                }
+            }
+        }
+        Some(Self {
+            packet,
+            message_type: message_type?,
+            mac: packet[28..34].try_into().ok()?,
+            ciaddr: address(&packet[12..16])?,
+            requested,
+            server_id,
+        })
+    }
+}
+
+/// Hands out up to `N` addresses, starting at [`ServerConfig::pool_start`].
+pub struct Server<const N: usize> {
+    config: ServerConfig,
+    // Slot `i` holds the lease of `pool_start + i`
+    leases: [Option<Lease>; N],
+}
+
+impl<const N: usize> Server<N> {
+    /// How long an offered address is kept for a client that has not requested it yet.
+    pub const OFFER_HOLD_SECS: u64 = 30;
+
+    /// Fails if a pool of `N` does not fit `config`, see [`ServerConfig::validate`].
+    pub fn new(config: ServerConfig) -> Result<Self, ConfigError> {
+        config.validate(N)?;
+        Ok(Self {
+            config,
+            leases: [None; N],
+        })
+    }
+
+    pub fn config(&self) -> &ServerConfig {
+        &self.config
+    }
+
+    /// Current leases, including expired ones not reused yet and declined
+    /// addresses, which have an all zero MAC.
+    pub fn leases(&self) -> impl Iterator<Item = &Lease> {
+        self.leases.iter().flatten()
+    }
+
+    fn pool_address(&self, slot: usize) -> Ipv4Addr {
+        // The config is validated, so the pool neither wraps nor leaves the subnet
+        Ipv4Addr::from(u32::from(self.config.pool_start) + slot as u32)
+    }
+
+    fn slot_of(&self, address: Ipv4Addr) -> Option<usize> {
+        let offset = u32::from(address).checked_sub(u32::from(self.config.pool_start))? as usize;
+        (offset < N).then_some(offset)
+    }
+
+    fn slot_of_mac(&self, mac: [u8; 6]) -> Option<usize> {
+        self.leases
+            .iter()
+            .position(|lease| lease.is_some_and(|lease| lease.mac == mac))
+    }
+
+    // Free for `mac`: unused, expired or already leased to it
+    fn is_available(&self, slot: usize, mac: [u8; 6], now_s: u64) -> bool {
+        match self.leases[slot] {
+            None => true,
+            Some(lease) => lease.mac == mac || lease.expires_s <= now_s,
+        }
+    }
+
+    // Address for a new client: what it asked for if possible, else a free one,
+    // else the one expired longest ago
+    fn pick_slot(&self, mac: [u8; 6], requested: Option<Ipv4Addr>, now_s: u64) -> Option<usize> {
+        if let Some(slot) = self.slot_of_mac(mac) {
+            return Some(slot);
+        }
+        if let Some(slot) = requested.and_then(|address| self.slot_of(address)) {
+            if self.is_available(slot, mac, now_s) {
+                return Some(slot);
+            }
+        }
+        if let Some(slot) = self.leases.iter().position(Option::is_none) {
+            return Some(slot);
+        }
+        (0..N)
+            .filter(|&slot| self.is_available(slot, mac, now_s))
+            .min_by_key(|&slot| self.leases[slot].map_or(0, |lease| lease.expires_s))
+    }
+
+    fn bind(&mut self, slot: usize, mac: [u8; 6], expires_s: u64) {
+        if let Some(old) = self.slot_of_mac(mac).filter(|&old| old != slot) {
+            self.leases[old] = None;
+        }
+        self.leases[slot] = Some(Lease {
+            mac,
+            address: self.pool_address(slot),
+            expires_s,
+        });
+    }
+
+    /// Handles one request received at `now_s` seconds, writes the reply to `out`.
+    ///
+    /// Returns the reply length, `None` if there is nothing to send. Replies go
+    /// to the broadcast address on [`CLIENT_PORT`], as clients without an
+    /// address cannot receive anything else.
+    pub fn handle(&mut self, now_s: u64, request: &[u8], out: &mut [u8]) -> Option<usize> {
+        let request = Request::parse(request)?;
+        let mac = request.mac;
+        match request.message_type {
+            MessageType::Discover => {
+                let slot = self.pick_slot(mac, request.requested, now_s)?;
+                // Keep a current lease as it is, hold a new address for a while
+                let expires_s = match self.leases[slot] {
+                    Some(lease) if lease.mac == mac && lease.expires_s > now_s => lease.expires_s,
+                    _ => now_s + Self::OFFER_HOLD_SECS,
+                };
+                self.bind(slot, mac, expires_s);
+                self.reply(&request, MessageType::Offer, self.pool_address(slot), out)
+            }
+            MessageType::Request => {
+                if request
+                    .server_id
+                    .is_some_and(|id| id != self.config.address)
+                {
+                    // The client took another server's offer
+                    if let Some(slot) = self.slot_of_mac(mac) {
+                        self.leases[slot] = None;
+                    }
+                    return None;
+                }
+                // Selecting or rebooting clients ask for an address, renewing ones use theirs
+                let address = request.requested.unwrap_or(request.ciaddr);
+                match self.slot_of(address) {
+                    Some(slot) if self.is_available(slot, mac, now_s) => {
+                        self.bind(slot, mac, now_s + self.config.lease_secs as u64);
+                        self.reply(&request, MessageType::Ack, address, out)
+                    }
+                    _ => self.reply(&request, MessageType::Nak, Ipv4Addr::UNSPECIFIED, out),
+                }
+            }
+            MessageType::Release => {
+                if let Some(slot) = self.slot_of_mac(mac) {
+                    self.leases[slot] = None;
+                }
+                None
+            }
+            MessageType::Decline => {
+                // Another host uses the address, keep it out of the pool for a lease time
+                let slot = request
+                    .requested
+                    .and_then(|address| self.slot_of(address))
+                    .filter(|&slot| self.leases[slot].is_some_and(|lease| lease.mac == mac))?;
+                self.leases[slot] = Some(Lease {
+                    mac: DECLINED,
+                    address: self.pool_address(slot),
+                    expires_s: now_s + self.config.lease_secs as u64,
+                });
+                None
+            }
+            // A client with an address of its own only wants the options
+            MessageType::Inform => {
+                self.reply(&request, MessageType::Ack, Ipv4Addr::UNSPECIFIED, out)
+            }
+            _ => None,
+        }
+    }
+
+    fn reply(
+        &self,
+        request: &Request<'_>,
+        message_type: MessageType,
+        yiaddr: Ipv4Addr,
+        out: &mut [u8],
+    ) -> Option<usize> {
+        let out = out.get_mut(..MAX_REPLY_LEN)?;
+        out.fill(0);
+        out[0] = OP_REPLY;
+        // htype, hlen, hops, xid
+        out[1..8].copy_from_slice(&request.packet[1..8]);
+        // flags, so a client asking for a broadcast reply gets one
+        out[10..12].copy_from_slice(&request.packet[10..12]);
+        if message_type != MessageType::Nak {
+            out[12..16].copy_from_slice(&request.packet[12..16]);
+            out[16..20].copy_from_slice(&yiaddr.octets());
+            out[20..24].copy_from_slice(&self.config.address.octets());
+        }
+        // giaddr, chaddr
+        out[24..44].copy_from_slice(&request.packet[24..44]);
+        out[236..240].copy_from_slice(&MAGIC_COOKIE);
+
+        let mut i = OPTIONS;
+        let mut option = |code: u8, value: &[u8]| {
+            out[i] = code;
+            out[i + 1] = value.len() as u8;
+            out[i + 2..i + 2 + value.len()].copy_from_slice(value);
+            i += 2 + value.len();
+        };
+        option(OPTION_MESSAGE_TYPE, &[message_type as u8]);
+        option(OPTION_SERVER_ID, &self.config.address.octets());
+        if message_type != MessageType::Nak {
+            if yiaddr != Ipv4Addr::UNSPECIFIED {
+                option(OPTION_LEASE_TIME, &self.config.lease_secs.to_be_bytes());
+            }
+            option(OPTION_SUBNET_MASK, &self.config.netmask.octets());
+            option(OPTION_ROUTER, &self.config.address.octets());
+            option(OPTION_DNS, &self.config.address.octets());
+        }
+        out[i] = OPTION_END;
+        Some((i + 1).max(MIN_REPLY_LEN))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PHONE: [u8; 6] = [0x02, 0x11, 0x22, 0x33, 0x44, 0x55];
+    const LAPTOP: [u8; 6] = [0x02, 0xAA, 0xBB, 0xCC, 0xDD, 0xEE];
+
+    fn request(
+        message_type: MessageType,
+        mac: [u8; 6],
+        options: &[(u8, &[u8])],
+        ciaddr: [u8; 4],
+    ) -> Vec<u8> {
+        let mut packet = vec![0u8; OPTIONS];
+        packet[..4].copy_from_slice(&[OP_REQUEST, 1, 6, 0]);
+        packet[4..8].copy_from_slice(&0x1234_5678u32.to_be_bytes());
+        packet[12..16].copy_from_slice(&ciaddr);
+        packet[28..34].copy_from_slice(&mac);
+        packet[236..240].copy_from_slice(&MAGIC_COOKIE);
+        packet.extend([OPTION_MESSAGE_TYPE, 1, message_type as u8]);
+        for (code, value) in options {
+            packet.extend([*code, value.len() as u8]);
+            packet.extend_from_slice(value);
+        }
+        packet.push(OPTION_END);
+        packet
+    }
+
+    // (message type, yiaddr) of a reply
+    fn parse_reply(reply: &[u8]) -> (MessageType, Ipv4Addr) {
+        assert_eq!(reply[0], OP_REPLY);
+        assert_eq!(reply[236..240], MAGIC_COOKIE);
+        assert_eq!(reply[OPTIONS], OPTION_MESSAGE_TYPE);
+        let yiaddr: [u8; 4] = reply[16..20].try_into().unwrap();
+        (
+            MessageType::from_u8(reply[OPTIONS + 2]).unwrap(),
+            yiaddr.into(),
+        )
+    }
+
+    fn exchange(
+        server: &mut Server<4>,
+        now_s: u64,
+        message_type: MessageType,
+        mac: [u8; 6],
+        options: &[(u8, &[u8])],
+    ) -> Option<(MessageType, Ipv4Addr)> {
+        let mut out = [0u8; MAX_REPLY_LEN];
+        let len = server.handle(
+            now_s,
+            &request(message_type, mac, options, [0; 4]),
+            &mut out,
+        )?;
+        assert!(len >= MIN_REPLY_LEN);
+        Some(parse_reply(&out[..len]))
+    }
+
+    // The DISCOVER/REQUEST exchange of a dhclient in Wireshark's dhcp.pcap sample,
+    // with the all zero sname and file fields and the trailing padding left out
+    const DHCLIENT_MAC: [u8; 6] = [0x00, 0x0b, 0x82, 0x01, 0xfc, 0x42];
+    const RECORDED_DISCOVER: [u8; 73] = [
+        0x01, 0x01, 0x06, 0x00, 0x00, 0x00, 0x3d, 0x1d, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b,
+        0x82, 0x01, 0xfc, 0x42, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // options
+        0x63, 0x82, 0x53, 0x63, 0x35, 0x01, 0x01, 0x3d, 0x07, 0x01, 0x00, 0x0b, 0x82, 0x01, 0xfc,
+        0x42, 0x32, 0x04, 0x00, 0x00, 0x00, 0x00, 0x37, 0x04, 0x01, 0x03, 0x06, 0x2a, 0xff,
+    ];
+    const RECORDED_REQUEST: [u8; 79] = [
+        0x01, 0x01, 0x06, 0x00, 0x00, 0x00, 0x3d, 0x1e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x0b,
+        0x82, 0x01, 0xfc, 0x42, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, //
+        // options
+        0x63, 0x82, 0x53, 0x63, 0x35, 0x01, 0x03, 0x3d, 0x07, 0x01, 0x00, 0x0b, 0x82, 0x01, 0xfc,
+        0x42, 0x32, 0x04, 0xc0, 0xa8, 0x00, 0x0a, 0x36, 0x04, 0xc0, 0xa8, 0x00, 0x01, 0x37, 0x04,
+        0x01, 0x03, 0x06, 0x2a, 0xff,
+    ];
+
+    // Puts back what the recording leaves out
+    fn recorded(packet: &[u8]) -> Vec<u8> {
+        let mut full = packet[..44].to_vec();
+        full.resize(236, 0);
+        full.extend_from_slice(&packet[44..]);
+        full.resize(300, 0);
+        full
+    }
+
+    // Value of `code` in the options of a reply
+    fn option(reply: &[u8], code: u8) -> Option<&[u8]> {
+        let mut i = OPTIONS;
+        while reply[i] != OPTION_END {
+            let len = reply[i + 1] as usize;
+            if reply[i] == code {
+                return Some(&reply[i + 2..i + 2 + len]);
+            }
+            i += 2 + len;
+        }
+        None
+    }
+
+    #[test]
+    fn recorded_exchange() {
+        let config = ServerConfig {
+            address: Ipv4Addr::new(192, 168, 0, 1),
+            netmask: Ipv4Addr::new(255, 255, 255, 0),
+            pool_start: Ipv4Addr::new(192, 168, 0, 10),
+            lease_secs: 3600,
+        };
+        let mut server = Server::<4>::new(config).unwrap();
+        let mut out = [0u8; MAX_REPLY_LEN];
+
+        let len = server
+            .handle(100, &recorded(&RECORDED_DISCOVER), &mut out)
+            .unwrap();
+        let offer = &out[..len];
+        assert_eq!(
+            parse_reply(offer),
+            (MessageType::Offer, Ipv4Addr::new(192, 168, 0, 10))
+        );
+        assert_eq!(offer[4..8], [0x00, 0x00, 0x3d, 0x1d]);
+        assert_eq!(offer[28..34], DHCLIENT_MAC);
+        assert_eq!(option(offer, OPTION_SERVER_ID), Some(&[192, 168, 0, 1][..]));
+        assert_eq!(
+            option(offer, OPTION_SUBNET_MASK),
+            Some(&[255, 255, 255, 0][..])
+        );
+        assert_eq!(option(offer, OPTION_ROUTER), Some(&[192, 168, 0, 1][..]));
+        assert_eq!(option(offer, OPTION_DNS), Some(&[192, 168, 0, 1][..]));
+        assert_eq!(
+            option(offer, OPTION_LEASE_TIME),
+            Some(&3600u32.to_be_bytes()[..])
+        );
+
+        let len = server
+            .handle(101, &recorded(&RECORDED_REQUEST), &mut out)
+            .unwrap();
+        let ack = &out[..len];
+        assert_eq!(
+            parse_reply(ack),
+            (MessageType::Ack, Ipv4Addr::new(192, 168, 0, 10))
+        );
+        assert_eq!(ack[4..8], [0x00, 0x00, 0x3d, 0x1e]);
+        let leases: Vec<_> = server.leases().copied().collect();
+        assert_eq!(
+            leases,
+            [Lease {
+                mac: DHCLIENT_MAC,
+                address: Ipv4Addr::new(192, 168, 0, 10),
+                expires_s: 101 + 3600,
+            }]
+        );
+
+        // Renewing, from the leased address and without the options of the first request
+        let mut renew = recorded(&RECORDED_REQUEST);
+        renew[12..16].copy_from_slice(&[192, 168, 0, 10]);
+        renew[OPTIONS..].fill(0);
+        renew[OPTIONS..OPTIONS + 4].copy_from_slice(&[OPTION_MESSAGE_TYPE, 1, 3, OPTION_END]);
+        let len = server.handle(2000, &renew, &mut out).unwrap();
+        assert_eq!(parse_reply(&out[..len]).0, MessageType::Ack);
+        assert_eq!(out[12..16], [192, 168, 0, 10]);
+        assert_eq!(server.leases().next().unwrap().expires_s, 2000 + 3600);
+    }
+
+    #[test]
+    fn declined_addresses_are_skipped() {
+        let mut server = Server::<4>::new(ServerConfig::soft_ap()).unwrap();
+        let first = Ipv4Addr::new(192, 168, 4, 2);
+        exchange(&mut server, 0, MessageType::Discover, PHONE, &[]);
+        let requested: [(u8, &[u8]); 1] = [(OPTION_REQUESTED_IP, &first.octets())];
+        exchange(&mut server, 0, MessageType::Request, PHONE, &requested);
+        // Someone else's decline does not count
+        assert_eq!(
+            exchange(&mut server, 1, MessageType::Decline, LAPTOP, &requested),
+            None
+        );
+        assert_eq!(server.leases().next().unwrap().mac, PHONE);
+
+        assert_eq!(
+            exchange(&mut server, 1, MessageType::Decline, PHONE, &requested),
+            None
+        );
+        let offer = exchange(&mut server, 2, MessageType::Discover, PHONE, &[]);
+        assert_eq!(
+            offer,
+            Some((MessageType::Offer, Ipv4Addr::new(192, 168, 4, 3)))
+        );
+        let nak = exchange(&mut server, 3, MessageType::Request, LAPTOP, &requested);
+        assert_eq!(nak, Some((MessageType::Nak, Ipv4Addr::UNSPECIFIED)));
+
+        // A client with a fixed address only gets the options
+        let mut out = [0u8; MAX_REPLY_LEN];
+        let inform = request(MessageType::Inform, LAPTOP, &[], [192, 168, 4, 200]);
+        let len = server.handle(4, &inform, &mut out).unwrap();
+        assert_eq!(
+            parse_reply(&out[..len]),
+            (MessageType::Ack, Ipv4Addr::UNSPECIFIED)
+        );
+        assert_eq!(option(&out, OPTION_LEASE_TIME), None);
+        assert_eq!(option(&out, OPTION_ROUTER), Some(&[192, 168, 4, 1][..]));
+    }
+
+    #[test]
+    fn discover_request_ack() {
+        let mut server = Server::<4>::new(ServerConfig::soft_ap()).unwrap();
+        let offer = exchange(&mut server, 0, MessageType::Discover, PHONE, &[]);
+        let address = Ipv4Addr::new(192, 168, 4, 2);
+        assert_eq!(offer, Some((MessageType::Offer, address)));
+
+        let server_id = [192, 168, 4, 1];
+        let options: [(u8, &[u8]); 2] = [
+            (OPTION_REQUESTED_IP, &address.octets()),
+            (OPTION_SERVER_ID, &server_id),
+        ];
+        let ack = exchange(&mut server, 1, MessageType::Request, PHONE, &options);
+        assert_eq!(ack, Some((MessageType::Ack, address)));
+        let lease = server.leases().next().unwrap();
+        assert_eq!((lease.mac, lease.address), (PHONE, address));
+        assert_eq!(lease.expires_s, 1 + 2 * 60 * 60);
+
+        // A second client gets the next address, the first one keeps its own
+        let offer = exchange(&mut server, 2, MessageType::Discover, LAPTOP, &[]);
+        assert_eq!(
+            offer,
+            Some((MessageType::Offer, Ipv4Addr::new(192, 168, 4, 3)))
+        );
+        let offer = exchange(&mut server, 3, MessageType::Discover, PHONE, &[]);
+        assert_eq!(offer, Some((MessageType::Offer, address)));
+    }
+
+    #[test]
+    fn naks_addresses_it_cannot_give() {
+        let mut server = Server::<4>::new(ServerConfig::soft_ap()).unwrap();
+        let taken = Ipv4Addr::new(192, 168, 4, 2);
+        exchange(&mut server, 0, MessageType::Discover, PHONE, &[]);
+        let request: [(u8, &[u8]); 1] = [(OPTION_REQUESTED_IP, &taken.octets())];
+        exchange(&mut server, 0, MessageType::Request, PHONE, &request);
+
+        // Someone else's address, and one from another network
+        let nak = exchange(&mut server, 10, MessageType::Request, LAPTOP, &request);
+        assert_eq!(nak, Some((MessageType::Nak, Ipv4Addr::UNSPECIFIED)));
+        let elsewhere: [(u8, &[u8]); 1] = [(OPTION_REQUESTED_IP, &[10, 0, 0, 7])];
+        let nak = exchange(&mut server, 10, MessageType::Request, LAPTOP, &elsewhere);
+        assert_eq!(nak, Some((MessageType::Nak, Ipv4Addr::UNSPECIFIED)));
+
+        // Requests meant for another server are none of its business
+        let other: [(u8, &[u8]); 2] = [
+            (OPTION_REQUESTED_IP, &[192, 168, 4, 3]),
+            (OPTION_SERVER_ID, &[192, 168, 4, 254]),
+        ];
+        assert_eq!(
+            exchange(&mut server, 10, MessageType::Request, LAPTOP, &other),
+            None
+        );
+    }
+
+    #[test]
+    fn pool_reuses_expired_and_released_leases() {
+        let mut server = Server::<2>::new(ServerConfig::soft_ap()).unwrap();
+        let mut out = [0u8; MAX_REPLY_LEN];
+        for mac in [PHONE, LAPTOP] {
+            let discover = request(MessageType::Discover, mac, &[], [0; 4]);
+            assert!(server.handle(0, &discover, &mut out).is_some());
+        }
+        // Both offers are still held
+        let third = [0x02, 0, 0, 0, 0, 3];
+        let discover = request(MessageType::Discover, third, &[], [0; 4]);
+        assert_eq!(server.handle(10, &discover, &mut out), None);
+        // Once they run out, the oldest is reused
+        let len = server.handle(31, &discover, &mut out).unwrap();
+        assert_eq!(parse_reply(&out[..len]).1, Ipv4Addr::new(192, 168, 4, 2));
+
+        let release = request(MessageType::Release, LAPTOP, &[], [192, 168, 4, 3]);
+        assert_eq!(server.handle(32, &release, &mut out), None);
+        assert_eq!(server.leases().count(), 1);
+    }
+
+    #[test]
+    fn ignores_garbage() {
+        let mut server = Server::<4>::new(ServerConfig::soft_ap()).unwrap();
+        let mut out = [0u8; MAX_REPLY_LEN];
+        assert_eq!(server.handle(0, &[0u8; 100], &mut out), None);
+        let mut reply = request(MessageType::Discover, PHONE, &[], [0; 4]);
+        reply[0] = OP_REPLY;
+        assert_eq!(server.handle(0, &reply, &mut out), None);
+        let mut truncated = request(MessageType::Discover, PHONE, &[], [0; 4]);
+        truncated.truncate(OPTIONS + 1);
+        assert_eq!(server.handle(0, &truncated, &mut out), None);
+    }
+
+    #[test]
+    fn rejects_pools_outside_the_subnet() {
+        let soft_ap = ServerConfig::soft_ap();
+        assert_eq!(soft_ap.validate(253), Ok(()));
+        // .2 to .255 would hand out the broadcast address
+        assert_eq!(soft_ap.validate(254), Err(ConfigError::PoolOutsideSubnet));
+        assert!(Server::<254>::new(soft_ap).is_err());
+
+        let around_server = ServerConfig {
+            pool_start: Ipv4Addr::new(192, 168, 4, 0),
+            ..soft_ap
+        };
+        assert_eq!(
+            around_server.validate(8),
+            Err(ConfigError::PoolOutsideSubnet)
+        );
+        let around_server = ServerConfig {
+            address: Ipv4Addr::new(192, 168, 4, 5),
+            ..soft_ap
+        };
+        assert_eq!(
+            around_server.validate(8),
+            Err(ConfigError::PoolContainsServer)
+        );
+        let elsewhere = ServerConfig {
+            pool_start: Ipv4Addr::new(10, 0, 0, 2),
+            ..soft_ap
+        };
+        assert_eq!(elsewhere.validate(8), Err(ConfigError::PoolOutsideSubnet));
+
+        // Near the top of the address space the pool must not wrap
+        let top = ServerConfig {
+            address: Ipv4Addr::new(255, 255, 255, 1),
+            netmask: Ipv4Addr::new(255, 255, 255, 0),
+            pool_start: Ipv4Addr::new(255, 255, 255, 250),
+            lease_secs: 60,
+        };
+        assert_eq!(top.validate(4), Ok(()));
+        assert_eq!(top.validate(8), Err(ConfigError::PoolOutsideSubnet));
+
+        let holey = ServerConfig {
+            netmask: Ipv4Addr::new(255, 0, 255, 0),
+            ..soft_ap
+        };
+        assert_eq!(holey.validate(8), Err(ConfigError::InvalidNetmask));
+    }
+}
@@ -0,0 +1,286 @@
+//! Just enough DNS to answer queries without a resolver.
+//!
+//! [`captive_response`] answers every name with one address, which is how a
+//! captive portal makes phones and laptops open its page. Packets go in and
+//! out as byte slices, the UDP socket stays with the caller.
+
+use core::net::Ipv4Addr;
+
+pub const PORT: u16 = 53;
+
+pub const TYPE_A: u16 = 1;
+pub const TYPE_ANY: u16 = 255;
+pub const CLASS_IN: u16 = 1;
+
+const HEADER_LEN: usize = 12;
+// Longest name on the wire
+const MAX_NAME_LEN: usize = 255;
+
+/// Fixed part at the start of every message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+    pub id: u16,
+    pub flags: u16,
+    pub questions: u16,
+    pub answers: u16,
+    pub authorities: u16,
+    pub additionals: u16,
+}
+
+impl Header {
+    pub const FLAG_RESPONSE: u16 = 0x8000;
+    pub const FLAG_AUTHORITATIVE: u16 = 0x0400;
+    pub const FLAG_TRUNCATED: u16 = 0x0200;
+    pub const FLAG_RECURSION_DESIRED: u16 = 0x0100;
+    const OPCODE_MASK: u16 = 0x7800;
+
+    pub fn parse(packet: &[u8]) -> Option<Self> {
+        let word = |i: usize| u16::from_be_bytes([packet[i], packet[i + 1]]);
+        (packet.len() >= HEADER_LEN).then(|| Self {
+            id: word(0),
+            flags: word(2),
+            questions: word(4),
+            answers: word(6),
+            authorities: word(8),
+            additionals: word(10),
+        })
+    }
+
+    pub fn write(&self, out: &mut [u8]) {
+        let words = [
+            self.id,
+            self.flags,
+            self.questions,
+            self.answers,
+            self.authorities,
+            self.additionals,
+        ];
+        for (chunk, word) in out[..HEADER_LEN].chunks_exact_mut(2).zip(words) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+    }
+
+    pub fn is_response(&self) -> bool {
+        self.flags & Self::FLAG_RESPONSE != 0
+    }
+
+    /// A standard query, as opposed to e.g. an update.
+    pub fn is_standard_query(&self) -> bool {
+        !self.is_response() && self.flags & Self::OPCODE_MASK == 0
+    }
+}
+
+/// Offset just past the name starting at `offset`, following no compression pointers.
+pub fn skip_name(packet: &[u8], mut offset: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(offset)? as usize;
+        match len {
+            0 => return Some(offset + 1),
+            // A pointer ends the name
+            _ if len & 0xC0 == 0xC0 => return (offset + 2 <= packet.len()).then_some(offset + 2),
+            _ if len & 0xC0 != 0 => return None,
+            _ => offset += 1 + len,
+        }
+    }
+}
+
+/// Calls `label` for every label of the name at `offset`, following compression pointers.
+pub fn read_name(packet: &[u8], mut offset: usize, mut label: impl FnMut(&[u8])) -> Option<()> {
+    let mut total = 0;
+    // Every pointer has to go backwards, so a loop cannot last forever
+    let mut limit = offset;
+    loop {
+        let len = *packet.get(offset)? as usize;
+        match len {
+            0 => return Some(()),
+            _ if len & 0xC0 == 0xC0 => {
+                let target = (len & 0x3F) << 8 | *packet.get(offset + 1)? as usize;
+                if target >= limit {
+                    return None;
+                }
+                limit = target;
+                offset = target;
+            }
+            _ if len & 0xC0 != 0 => return None,
+            _ => {
+                total += 1 + len;
+                if total > MAX_NAME_LEN {
+                    return None;
+                }
+                label(packet.get(offset + 1..offset + 1 + len)?);
+                offset += 1 + len;
+            }
+        }
+    }
+}
+
+/// Writes `name` (e.g. `"esp32.local"`) as labels at `offset`, returns the offset past it.
+pub fn write_name(out: &mut [u8], offset: usize, name: &str) -> Option<usize> {
+    write_labels(out, offset, name.split('.'))
+}
+
+/// Writes a name made of `labels`, empty ones are skipped, returns the offset past it.
+pub fn write_labels<'l>(
+    out: &mut [u8],
+    mut offset: usize,
+    labels: impl IntoIterator<Item = &'l str>,
+) -> Option<usize> {
+    let start = offset;
+    for label in labels.into_iter().filter(|label| !label.is_empty()) {
+        if label.len() > 63 {
+            return None;
+        }
+        *out.get_mut(offset)? = label.len() as u8;
+        out.get_mut(offset + 1..offset + 1 + label.len())?
+            .copy_from_slice(label.as_bytes());
+        offset += 1 + label.len();
+    }
+    if offset + 1 - start > MAX_NAME_LEN {
+        return None;
+    }
+    *out.get_mut(offset)? = 0;
+    Some(offset + 1)
+}
+
+/// Whether the name at `offset` is made of `labels`, ignoring ASCII case as DNS does.
+pub fn name_eq<'l>(
+    packet: &[u8],
+    offset: usize,
+    labels: impl IntoIterator<Item = &'l str>,
+) -> bool {
+    let mut labels = labels.into_iter().filter(|label| !label.is_empty());
+    let mut equal = true;
+    let read = read_name(packet, offset, |label| {
+        equal &= labels
+            .next()
+            .is_some_and(|expected| expected.as_bytes().eq_ignore_ascii_case(label));
+    });
+    read.is_some() && equal && labels.next().is_none()
+}
+
+/// Answers a query for any name with `address`, returns the length written to `out`.
+///
+/// Queries for other record types, e.g. AAAA, get an empty answer so clients
+/// fall back to IPv4. `None` for anything that is not a single question query.
+pub fn captive_response(query: &[u8], address: Ipv4Addr, out: &mut [u8]) -> Option<usize> {
+    let header = Header::parse(query)?;
+    if !header.is_standard_query() || header.questions != 1 {
+        return None;
+    }
+    let name_end = skip_name(query, HEADER_LEN)?;
+    let question = query.get(HEADER_LEN..name_end + 4)?;
+    let record_type =
+        u16::from_be_bytes([question[question.len() - 4], question[question.len() - 3]]);
+    let class = u16::from_be_bytes([question[question.len() - 2], question[question.len() - 1]]);
+    let answer = matches!(record_type, TYPE_A | TYPE_ANY) && class & 0x7FFF == CLASS_IN;
+
+    let len = HEADER_LEN + question.len() + if answer { 16 } else { 0 };
+    let out = out.get_mut(..len)?;
+    Header {
+        id: header.id,
+        flags: Header::FLAG_RESPONSE
+            | Header::FLAG_AUTHORITATIVE
+            | header.flags & Header::FLAG_RECURSION_DESIRED,
+        questions: 1,
+        answers: answer as u16,
+        authorities: 0,
+        additionals: 0,
+    }
+    .write(out);
+    out[HEADER_LEN..HEADER_LEN + question.len()].copy_from_slice(question);
+    if answer {
+        let record = &mut out[HEADER_LEN + question.len()..];
+        // Pointer to the name in the question
+        record[..2].copy_from_slice(&(0xC000 | HEADER_LEN as u16).to_be_bytes());
+        record[2..4].copy_from_slice(&TYPE_A.to_be_bytes());
+        record[4..6].copy_from_slice(&CLASS_IN.to_be_bytes());
+        // Short TTL, the portal is only there until the device is set up
+        record[6..10].copy_from_slice(&60u32.to_be_bytes());
+        record[10..12].copy_from_slice(&4u16.to_be_bytes());
+        record[12..16].copy_from_slice(&address.octets());
+    }
+    Some(len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `dig @192.168.4.1 connectivitycheck.gstatic.com`, without the EDNS record
+    const QUERY: [u8; 47] = [
+        0x2b, 0x7e, 0x01, 0x20, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, b'c', b'o',
+        b'n', b'n', b'e', b'c', b't', b'i', b'v', b'i', b't', b'y', b'c', b'h', b'e', b'c', b'k',
+        0x07, b'g', b's', b't', b'a', b't', b'i', b'c', 0x03, b'c', b'o', b'm', 0x00, 0x00, 0x01,
+        0x00, 0x01,
+    ];
+    const PORTAL: Ipv4Addr = Ipv4Addr::new(192, 168, 4, 1);
+
+    #[test]
+    fn answers_any_name_with_the_portal() {
+        let mut out = [0u8; 512];
+        let len = captive_response(&QUERY, PORTAL, &mut out).unwrap();
+        assert_eq!(len, QUERY.len() + 16);
+        let header = Header::parse(&out).unwrap();
+        assert_eq!(header.id, 0x2b7e);
+        assert!(header.is_response());
+        assert_eq!(
+            header.flags & Header::FLAG_RECURSION_DESIRED,
+            Header::FLAG_RECURSION_DESIRED
+        );
+        assert_eq!((header.questions, header.answers), (1, 1));
+        assert_eq!(out[12..QUERY.len()], QUERY[12..]);
+        assert_eq!(
+            out[QUERY.len()..len],
+            [0xC0, 0x0C, 0, 1, 0, 1, 0, 0, 0, 60, 0, 4, 192, 168, 4, 1]
+        );
+
+        let mut labels = Vec::new();
+        read_name(&out, QUERY.len(), |label| labels.push(label.to_vec())).unwrap();
+        assert_eq!(labels, [&b"connectivitycheck"[..], b"gstatic", b"com"]);
+    }
+
+    #[test]
+    fn other_types_get_no_answer() {
+        let mut query = QUERY;
+        // AAAA
+        query[44] = 28;
+        let mut out = [0u8; 512];
+        let len = captive_response(&query, PORTAL, &mut out).unwrap();
+        assert_eq!(len, query.len());
+        assert_eq!(Header::parse(&out).unwrap().answers, 0);
+    }
+
+    #[test]
+    fn ignores_what_is_not_a_query() {
+        let mut out = [0u8; 512];
+        assert_eq!(captive_response(&QUERY[..20], PORTAL, &mut out), None);
+        let mut response = QUERY;
+        response[2] |= 0x80;
+        assert_eq!(captive_response(&response, PORTAL, &mut out), None);
+        // Too small an output buffer
+        assert_eq!(captive_response(&QUERY, PORTAL, &mut out[..50]), None);
+    }
+
+    #[test]
+    fn names() {
+        let mut out = [0u8; 64];
+        let end = write_name(&mut out, 0, "esp32.local").unwrap();
+        assert_eq!(out[..end], *b"\x05esp32\x05local\x00");
+        assert_eq!(skip_name(&out, 0), Some(end));
+        assert_eq!(write_name(&mut out, 0, &"a".repeat(64)), None);
+
+        let end = write_labels(&mut out, 0, ["My Device", "_http", "_tcp", "local"]).unwrap();
+        assert_eq!(out[..end], *b"\x09My Device\x05_http\x04_tcp\x05local\x00");
+        assert!(name_eq(&out, 0, ["my device", "_HTTP", "_tcp", "local"]));
+        assert!(!name_eq(&out, 0, ["My Device", "_http", "_tcp"]));
+        assert!(!name_eq(
+            &out,
+            0,
+            ["My Device", "_http", "_tcp", "local", "x"]
+        ));
+
+        // A pointer to itself is rejected instead of looping
+        let looped = [0xC0, 0x00];
+        assert_eq!(read_name(&looped, 0, |_| {}), None);
+    }
+}
@@ -0,0 +1,225 @@
+/// A stage of a per-channel filter chain.
+pub trait Filter {
+    fn update(&mut self, sample: f64) -> f64;
+
+    /// Feeds the output of this filter into `next`.
+    fn then<B: Filter>(self, next: B) -> Chain<Self, B>
+    where
+        Self: Sized,
+    {
+        Chain(self, next)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Chain<A, B>(A, B);
+
+impl<A: Filter, B: Filter> Filter for Chain<A, B> {
+    fn update(&mut self, sample: f64) -> f64 {
+        self.1.update(self.0.update(sample))
+    }
+}
+
+/// Averages `n` consecutive readings into one sample.
+pub fn oversample<T, E>(n: usize, mut read: impl FnMut() -> Result<T, E>) -> Result<f64, E>
+where
+    T: Into<f64>,
+{
+    let n = n.max(1);
+    let mut sum = 0.0;
+    for _ in 0..n {
+        sum += read()?.into();
+    }
+    Ok(sum / n as f64)
+}
+
+/// Median of the last `N` samples, rejects single spikes.
+#[derive(Debug, Clone, Copy)]
+pub struct Median<const N: usize> {
+    window: [f64; N],
+    len: usize,
+    next: usize,
+}
+
+impl<const N: usize> Median<N> {
+    pub const fn new() -> Self {
+        // An empty window would divide by zero on the first update
+        const { assert!(N > 0, "Median needs at least one sample") };
+        Self {
+            window: [0.0; N],
+            len: 0,
+            next: 0,
+        }
+    }
+}
+
+impl<const N: usize> Default for Median<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for Median<N> {
+    fn update(&mut self, sample: f64) -> f64 {
+        self.window[self.next] = sample;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+
+        let mut sorted = self.window;
+        let sorted = &mut sorted[..self.len];
+        sorted.sort_unstable_by(f64::total_cmp);
+        let mid = self.len / 2;
+        if self.len % 2 == 1 {
+            sorted[mid]
+        } else {
+            (sorted[mid - 1] + sorted[mid]) / 2.0
+        }
+    }
+}
+
+/// Exponential moving average, `alpha` in `0.0..=1.0` (1.0 = no smoothing).
+#[derive(Debug, Clone, Copy)]
+pub struct Ema {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ema {
+    pub const fn new(alpha: f64) -> Self {
+        Self { alpha, value: None }
+    }
+}
+
+impl Filter for Ema {
+    fn update(&mut self, sample: f64) -> f64 {
+        let value = match self.value {
+            Some(prev) => prev + self.alpha * (sample - prev),
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+}
+
+/// Holds its output until the input moves more than `width` away from it.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadband {
+    width: f64,
+    value: Option<f64>,
+}
+
+impl Deadband {
+    pub const fn new(width: f64) -> Self {
+        Self { width, value: None }
+    }
+}
+
+impl Filter for Deadband {
+    fn update(&mut self, sample: f64) -> f64 {
+        match self.value {
+            Some(prev) if (sample - prev).abs() <= self.width => prev,
+            _ => {
+                self.value = Some(sample);
+                sample
+            }
+        }
+    }
+}
+
+/// Threshold with separate switch-on and switch-off levels, so noise around a single
+/// threshold does not toggle the output.
+#[derive(Debug, Clone, Copy)]
+pub struct Hysteresis {
+    low: f64,
+    high: f64,
+    state: bool,
+}
+
+impl Hysteresis {
+    /// Turns on above `high` and off again below `low`.
+    pub const fn new(low: f64, high: f64) -> Self {
+        Self {
+            low,
+            high,
+            state: false,
+        }
+    }
+
+    pub fn update(&mut self, sample: f64) -> bool {
+        if self.state && sample < self.low {
+            self.state = false;
+        } else if !self.state && sample > self.high {
+            self.state = true;
+        }
+        self.state
+    }
+
+    pub fn state(&self) -> bool {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversample_averages_and_propagates_errors() {
+        let mut readings = [100u16, 102, 98, 100].into_iter();
+        let avg = oversample(4, || Ok::<_, ()>(readings.next().unwrap())).unwrap();
+        assert_eq!(avg, 100.0);
+
+        let mut calls = 0;
+        let res = oversample(4, || {
+            calls += 1;
+            if calls == 2 {
+                Err("adc busy")
+            } else {
+                Ok(1u16)
+            }
+        });
+        assert_eq!(res, Err("adc busy"));
+    }
+
+    #[test]
+    fn median_rejects_spikes() {
+        let mut median = Median::<3>::new();
+        let out: [f64; 5] = [10.0, 10.0, 4000.0, 10.0, 11.0].map(|s| median.update(s));
+        assert_eq!(out, [10.0, 10.0, 10.0, 10.0, 11.0]);
+    }
+
+    #[test]
+    fn ema_converges() {
+        let mut ema = Ema::new(0.5);
+        assert_eq!(ema.update(100.0), 100.0);
+        assert_eq!(ema.update(200.0), 150.0);
+        assert_eq!(ema.update(200.0), 175.0);
+    }
+
+    #[test]
+    fn deadband_ignores_jitter() {
+        let mut deadband = Deadband::new(5.0);
+        let out: [f64; 5] = [100.0, 103.0, 97.0, 110.0, 108.0].map(|s| deadband.update(s));
+        assert_eq!(out, [100.0, 100.0, 100.0, 110.0, 110.0]);
+    }
+
+    #[test]
+    fn hysteresis_does_not_flicker() {
+        let mut threshold = Hysteresis::new(1900.0, 2100.0);
+        let noisy = [
+            1950.0, 2050.0, 2150.0, 2050.0, 1950.0, 2050.0, 1850.0, 1950.0,
+        ];
+        let out = noisy.map(|s| threshold.update(s));
+        assert_eq!(out, [false, false, true, true, true, true, false, false]);
+    }
+
+    #[test]
+    fn chain_applies_in_order() {
+        let mut filter = Median::<3>::new().then(Ema::new(0.5));
+        assert_eq!(filter.update(100.0), 100.0);
+        assert_eq!(filter.update(100.0), 100.0);
+        // spike removed by the median before it reaches the average
+        assert_eq!(filter.update(4000.0), 100.0);
+        assert_eq!(filter.update(200.0), 150.0);
+    }
+}
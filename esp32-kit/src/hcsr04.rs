@@ -0,0 +1,232 @@
+// Datasheet range of the HC-SR04
+pub const MIN_DISTANCE_CM: f64 = 2.0;
+pub const MAX_DISTANCE_CM: f64 = 400.0;
+
+/// Speed of sound in dry air at the given temperature.
+pub fn speed_of_sound_m_per_s(celsius: f64) -> f64 {
+    331.3 + 0.606 * celsius
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Distance(f64);
+
+impl Distance {
+    pub const fn from_cm(cm: f64) -> Self {
+        Self(cm)
+    }
+
+    pub const fn cm(self) -> f64 {
+        self.0
+    }
+}
+
+/// The end of the sensor's range a reading fell off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeEnd {
+    /// Closer than [`MIN_DISTANCE_CM`], something is right in front of the sensor.
+    Near,
+    /// Farther than [`MAX_DISTANCE_CM`] or no echo back, nothing is in range.
+    Far,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hcsr04Error {
+    /// The echo pulse never started, e.g. the sensor is unplugged.
+    Timeout,
+    /// The echo is shorter or longer than the sensor's range.
+    OutOfRange(RangeEnd),
+}
+
+fn cm_per_us(celsius: f64) -> f64 {
+    speed_of_sound_m_per_s(celsius) / 10_000.0
+}
+
+/// Longest echo pulse that is still within range at the given temperature.
+pub fn max_pulse_width_us(celsius: f64) -> u64 {
+    pulse_from_distance(Distance(MAX_DISTANCE_CM), celsius) as u64
+}
+
+/// Echo pulse width in µs the sensor reports for an object at `distance`.
+pub fn pulse_from_distance(distance: Distance, celsius: f64) -> f64 {
+    2.0 * distance.cm() / cm_per_us(celsius)
+}
+
+/// Derive distance from the echo pulse width; the sound travels to the object and back.
+pub fn distance_from_pulse(pulse_width_us: u64, celsius: f64) -> Result<Distance, Hcsr04Error> {
+    let cm = (pulse_width_us as f64 * cm_per_us(celsius)) / 2.0;
+    if cm < MIN_DISTANCE_CM {
+        return Err(Hcsr04Error::OutOfRange(RangeEnd::Near));
+    }
+    if cm > MAX_DISTANCE_CM {
+        return Err(Hcsr04Error::OutOfRange(RangeEnd::Far));
+    }
+    Ok(Distance(cm))
+}
+
+#[cfg(feature = "embassy")]
+pub use driver::{Hcsr04, Hcsr04Group};
+
+#[cfg(feature = "embassy")]
+mod driver {
+    use core::convert::Infallible;
+
+    use embassy_time::{block_for, with_timeout, Duration, Instant, Timer};
+    use embedded_hal::digital::{InputPin, OutputPin};
+    use embedded_hal_async::digital::Wait;
+
+    use super::{distance_from_pulse, max_pulse_width_us, Distance, Hcsr04Error, RangeEnd};
+
+    // The echo line goes high ~0.5ms after the trigger, give it some slack
+    const ECHO_START_TIMEOUT: Duration = Duration::from_millis(10);
+    // Let stray echoes die out before the next sensor is triggered
+    const SETTLE_TIME: Duration = Duration::from_millis(60);
+
+    /// HC-SR04 driver measuring the echo pulse with GPIO edge interrupts.
+    pub struct Hcsr04<Trig, Echo> {
+        trig: Trig,
+        echo: Echo,
+        temperature: f64,
+    }
+
+    impl<Trig, Echo> Hcsr04<Trig, Echo>
+    where
+        Trig: OutputPin<Error = Infallible>,
+        Echo: InputPin<Error = Infallible> + Wait,
+    {
+        pub fn new(trig: Trig, echo: Echo) -> Self {
+            Self {
+                trig,
+                echo,
+                temperature: 20.0,
+            }
+        }
+
+        /// Ambient temperature in °C used for the speed of sound, 20°C by default.
+        pub fn set_temperature(&mut self, celsius: f64) {
+            self.temperature = celsius;
+        }
+
+        /// Triggers one ping and times its echo.
+        ///
+        /// Both echo edges are timestamped when their interrupt wakes this task,
+        /// so only a difference between the two wake delays ends up in the
+        /// distance, about 0.17 mm per µs. Under heavy load, e.g. next to WiFi,
+        /// run the sensor from a higher priority executor.
+        pub async fn measure(&mut self) -> Result<Distance, Hcsr04Error> {
+            // A previous echo still in flight would be mistaken for this one
+            if self.echo.is_high().unwrap_or(false) {
+                with_timeout(SETTLE_TIME, self.echo.wait_for_low())
+                    .await
+                    .map_err(|_| Hcsr04Error::Timeout)?
+                    .ok();
+            }
+
+            // Trigger ultrasonic waves
+            self.trig.set_low().ok();
+            block_for(Duration::from_micros(2));
+            self.trig.set_high().ok();
+            block_for(Duration::from_micros(10));
+            self.trig.set_low().ok();
+
+            with_timeout(ECHO_START_TIMEOUT, self.echo.wait_for_high())
+                .await
+                .map_err(|_| Hcsr04Error::Timeout)?
+                .ok();
+            let rise = Instant::now();
+
+            // No object in range keeps the echo high for ~38ms
+            let max_pulse = Duration::from_micros(max_pulse_width_us(self.temperature));
+            with_timeout(max_pulse, self.echo.wait_for_low())
+                .await
+                .map_err(|_| Hcsr04Error::OutOfRange(RangeEnd::Far))?
+                .ok();
+            let fall = Instant::now();
+
+            distance_from_pulse((fall - rise).as_micros(), self.temperature)
+        }
+    }
+
+    /// Several sensors triggered one after another so they do not hear each other's echoes.
+    pub struct Hcsr04Group<Trig, Echo, const N: usize> {
+        sensors: [Hcsr04<Trig, Echo>; N],
+    }
+
+    impl<Trig, Echo, const N: usize> Hcsr04Group<Trig, Echo, N>
+    where
+        Trig: OutputPin<Error = Infallible>,
+        Echo: InputPin<Error = Infallible> + Wait,
+    {
+        pub fn new(sensors: [Hcsr04<Trig, Echo>; N]) -> Self {
+            Self { sensors }
+        }
+
+        pub fn set_temperature(&mut self, celsius: f64) {
+            for sensor in &mut self.sensors {
+                sensor.set_temperature(celsius);
+            }
+        }
+
+        pub async fn measure_all(&mut self) -> [Result<Distance, Hcsr04Error>; N] {
+            let mut results = [Err(Hcsr04Error::Timeout); N];
+            for (sensor, result) in self.sensors.iter_mut().zip(&mut results) {
+                *result = sensor.measure().await;
+                Timer::after(SETTLE_TIME).await;
+            }
+            results
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn speed_of_sound_follows_temperature() {
+        assert!((speed_of_sound_m_per_s(0.0) - 331.3).abs() < 1e-9);
+        assert!((speed_of_sound_m_per_s(20.0) - 343.42).abs() < 1e-9);
+    }
+
+    #[test]
+    fn one_meter_round_trip() {
+        // ~5824 µs round trip for 100 cm at 20°C
+        let d = distance_from_pulse(5824, 20.0).unwrap();
+        assert!((d.cm() - 100.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn colder_air_means_shorter_distance() {
+        let winter = distance_from_pulse(5824, -10.0).unwrap();
+        let summer = distance_from_pulse(5824, 35.0).unwrap();
+        assert!(winter < summer);
+    }
+
+    #[test]
+    fn compensation_keeps_parking_distance_across_seasons() {
+        let car = Distance::from_cm(50.0);
+        for celsius in [-15.0, 0.0, 20.0, 40.0] {
+            let pulse = pulse_from_distance(car, celsius).round() as u64;
+            let compensated = distance_from_pulse(pulse, celsius).unwrap();
+            assert!((compensated.cm() - 50.0).abs() < 0.02);
+        }
+        // The old fixed 0.0343 cm/µs reads a winter car almost 3 cm too far away
+        let winter = pulse_from_distance(car, -15.0);
+        assert!(winter * 0.0343 / 2.0 - 50.0 > 2.5);
+    }
+
+    #[test]
+    fn out_of_range_pulses_are_rejected() {
+        // Closer than 2 cm is not the same as nothing there
+        assert_eq!(
+            distance_from_pulse(50, 20.0),
+            Err(Hcsr04Error::OutOfRange(RangeEnd::Near))
+        );
+        assert_eq!(
+            distance_from_pulse(38_000, 20.0),
+            Err(Hcsr04Error::OutOfRange(RangeEnd::Far))
+        );
+        let max = max_pulse_width_us(20.0);
+        assert!(distance_from_pulse(max, 20.0).is_ok());
+        assert!(distance_from_pulse(max + 2, 20.0).is_err());
+    }
+}
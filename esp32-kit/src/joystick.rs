@@ -0,0 +1,449 @@
+//! Analog thumb joystick: two potentiometers on ADC pins plus a push button.
+//!
+//! [`Joystick`] turns samples into normalized axes and menu style events.
+//! Like [`crate::pantilt`], readings and time are passed in so it can be
+//! driven from recorded samples on the host.
+
+use crate::button::{self, GestureConfig, Gestures};
+use crate::storage::Record;
+
+/// Readings of one axis at both ends and at rest, in corrected ADC LSB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AxisCalibration {
+    pub min: u16,
+    pub center: u16,
+    pub max: u16,
+}
+
+impl AxisCalibration {
+    /// Uncalibrated 12-bit axis resting in the middle.
+    pub const DEFAULT: Self = Self {
+        min: 0,
+        center: 2048,
+        max: 4095,
+    };
+
+    /// Maps a reading to -1..=1 with 0 at rest.
+    ///
+    /// Both halves are scaled separately, the rest position of cheap sticks is rarely in the middle.
+    pub fn normalize(&self, raw: f64) -> f32 {
+        let (min, center, max) = (self.min as f64, self.center as f64, self.max as f64);
+        let value = if raw >= center {
+            (raw - center) / (max - center).max(1.0)
+        } else {
+            (raw - center) / (center - min).max(1.0)
+        };
+        value.clamp(-1.0, 1.0) as f32
+    }
+}
+
+impl Default for AxisCalibration {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Calibration {
+    pub x: AxisCalibration,
+    pub y: AxisCalibration,
+}
+
+impl Record for Calibration {
+    const MAGIC: u32 = u32::from_le_bytes(*b"JOY1");
+    const LEN: usize = 12;
+
+    fn encode(&self, buf: &mut [u8]) {
+        let values = [
+            self.x.min,
+            self.x.center,
+            self.x.max,
+            self.y.min,
+            self.y.center,
+            self.y.max,
+        ];
+        for (chunk, value) in buf.chunks_exact_mut(2).zip(values) {
+            chunk.copy_from_slice(&value.to_le_bytes());
+        }
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        let mut values = buf
+            .chunks_exact(2)
+            .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]));
+        let mut axis = || {
+            let axis = AxisCalibration {
+                min: values.next()?,
+                center: values.next()?,
+                max: values.next()?,
+            };
+            (axis.min < axis.center && axis.center < axis.max).then_some(axis)
+        };
+        Some(Self {
+            x: axis()?,
+            y: axis()?,
+        })
+    }
+}
+
+// Anything narrower than this was not moved during calibration
+const MIN_HALF_RANGE: f64 = 200.0;
+
+/// Records a calibration: first the stick rests, then it is circled along its edge.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CalibrationCapture {
+    sum: [f64; 2],
+    samples: u32,
+    min: [f64; 2],
+    max: [f64; 2],
+}
+
+impl CalibrationCapture {
+    pub const fn new() -> Self {
+        Self {
+            sum: [0.0; 2],
+            samples: 0,
+            min: [f64::MAX; 2],
+            max: [f64::MIN; 2],
+        }
+    }
+
+    /// Reading `[x, y]` while the stick is left alone.
+    pub fn rest(&mut self, raw: [f64; 2]) {
+        self.sum[0] += raw[0];
+        self.sum[1] += raw[1];
+        self.samples += 1;
+    }
+
+    /// Reading `[x, y]` while the stick is moved to its ends.
+    pub fn sweep(&mut self, raw: [f64; 2]) {
+        for ((min, max), raw) in self.min.iter_mut().zip(&mut self.max).zip(raw) {
+            *min = min.min(raw);
+            *max = max.max(raw);
+        }
+    }
+
+    /// `None` unless both axes rested and were moved far enough both ways.
+    pub fn finish(&self) -> Option<Calibration> {
+        if self.samples == 0 {
+            return None;
+        }
+        let axis = |i: usize| {
+            let center = self.sum[i] / self.samples as f64;
+            let moved =
+                center - self.min[i] >= MIN_HALF_RANGE && self.max[i] - center >= MIN_HALF_RANGE;
+            moved.then(|| AxisCalibration {
+                min: libm::round(self.min[i]) as u16,
+                center: libm::round(center) as u16,
+                max: libm::round(self.max[i]) as u16,
+            })
+        };
+        Some(Calibration {
+            x: axis(0)?,
+            y: axis(1)?,
+        })
+    }
+}
+
+/// Zeroes `value` inside `deadzone` and rescales the rest so the output still starts at 0.
+pub fn deadzone(value: f32, deadzone: f32) -> f32 {
+    let magnitude = value.abs();
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+    value.signum() * ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0)
+}
+
+/// Blends in a cubic curve for finer control around the centre, `expo` from 0 (linear) to 1.
+pub fn expo(value: f32, expo: f32) -> f32 {
+    (1.0 - expo) * value + expo * value * value * value
+}
+
+/// Zeroes both axes inside a circle of radius `deadzone` and rescales the rest.
+///
+/// Unlike a deadzone per axis this keeps diagonals smooth and the output inside the unit circle.
+pub fn radial_deadzone(axes: [f32; 2], deadzone: f32) -> [f32; 2] {
+    let magnitude = libm::sqrtf(axes[0] * axes[0] + axes[1] * axes[1]);
+    if magnitude <= deadzone {
+        return [0.0; 2];
+    }
+    let scaled = ((magnitude - deadzone) / (1.0 - deadzone)).min(1.0);
+    axes.map(|value| value / magnitude * scaled)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Up,
+    Down,
+    Left,
+    Right,
+    Click,
+    DoubleClick,
+    LongPress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JoystickConfig {
+    /// Radius of the [`radial_deadzone`].
+    pub deadzone: f32,
+    /// Deflection that sends a direction event.
+    pub press_threshold: f32,
+    /// The stick has to come back below this before the next direction event.
+    pub release_threshold: f32,
+    /// Stick modules usually read highest when pushed down.
+    pub invert_y: bool,
+    pub button: GestureConfig,
+}
+
+impl Default for JoystickConfig {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.1,
+            press_threshold: 0.6,
+            release_threshold: 0.4,
+            invert_y: false,
+            button: GestureConfig::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Joystick {
+    config: JoystickConfig,
+    calibration: Calibration,
+    axes: [f32; 2],
+    direction: Option<Event>,
+    button: Gestures,
+}
+
+impl Joystick {
+    pub fn new(config: JoystickConfig, calibration: Calibration) -> Self {
+        Self {
+            config,
+            calibration,
+            axes: [0.0; 2],
+            direction: None,
+            button: Gestures::new(config.button),
+        }
+    }
+
+    pub fn calibration(&self) -> &Calibration {
+        &self.calibration
+    }
+
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// `[x, y]` in -1..=1 after the deadzone, right and up are positive.
+    pub fn axes(&self) -> [f32; 2] {
+        self.axes
+    }
+
+    /// Debounced button level.
+    pub fn is_pressed(&self) -> bool {
+        self.button.is_pressed()
+    }
+
+    /// Feeds a stick reading `[x, y]` in corrected ADC LSB and the raw button level.
+    ///
+    /// Sample every 10 to 50 ms, slower sampling delays the button gestures.
+    pub fn update(
+        &mut self,
+        now_ms: u64,
+        raw: [f64; 2],
+        pressed: bool,
+    ) -> impl Iterator<Item = Event> {
+        let x = self.calibration.x.normalize(raw[0]);
+        let mut y = self.calibration.y.normalize(raw[1]);
+        if self.config.invert_y {
+            y = -y;
+        }
+        self.axes = radial_deadzone([x, y], self.config.deadzone);
+        let direction = self.direction();
+
+        self.button.input(now_ms, pressed);
+        // Press, release and repeats are of no use for menus
+        let gesture =
+            core::iter::from_fn(|| self.button.poll(now_ms)).find_map(|event| match event {
+                button::Event::Click => Some(Event::Click),
+                button::Event::DoubleClick => Some(Event::DoubleClick),
+                button::Event::LongPress(_) => Some(Event::LongPress),
+                _ => None,
+            });
+        [direction, gesture].into_iter().flatten()
+    }
+
+    // A new direction event once the dominant axis passes the press threshold
+    fn direction(&mut self) -> Option<Event> {
+        let [x, y] = self.axes;
+        let magnitude = libm::sqrtf(x * x + y * y);
+        if magnitude < self.config.release_threshold {
+            self.direction = None;
+            return None;
+        }
+        if magnitude < self.config.press_threshold {
+            return None;
+        }
+        let direction = if x.abs() > y.abs() {
+            if x > 0.0 {
+                Event::Right
+            } else {
+                Event::Left
+            }
+        } else if y > 0.0 {
+            Event::Up
+        } else {
+            Event::Down
+        };
+        if self.direction == Some(direction) {
+            return None;
+        }
+        self.direction = Some(direction);
+        Some(direction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{load, save, RamStorage};
+
+    #[test]
+    fn normalize_scales_each_half() {
+        let axis = AxisCalibration {
+            min: 100,
+            center: 1900,
+            max: 4000,
+        };
+        assert_eq!(axis.normalize(1900.0), 0.0);
+        assert_eq!(axis.normalize(100.0), -1.0);
+        assert_eq!(axis.normalize(4000.0), 1.0);
+        assert_eq!(axis.normalize(1000.0), -0.5);
+        assert_eq!(axis.normalize(2950.0), 0.5);
+        assert_eq!(axis.normalize(4095.0), 1.0);
+    }
+
+    #[test]
+    fn deadzone_and_expo() {
+        assert_eq!(deadzone(0.05, 0.1), 0.0);
+        assert_eq!(deadzone(-0.1, 0.1), 0.0);
+        assert!((deadzone(0.55, 0.1) - 0.5).abs() < 1e-6);
+        assert_eq!(deadzone(-1.0, 0.1), -1.0);
+
+        assert_eq!(expo(0.5, 0.0), 0.5);
+        assert_eq!(expo(0.5, 1.0), 0.125);
+        assert_eq!(expo(-1.0, 0.7), -1.0);
+    }
+
+    #[test]
+    fn capture_and_persist() {
+        let mut capture = CalibrationCapture::new();
+        assert_eq!(capture.finish(), None);
+        for raw in [[1890.0, 2010.0], [1910.0, 1990.0]] {
+            capture.rest(raw);
+        }
+        // Only x was moved so far
+        capture.sweep([80.0, 2000.0]);
+        capture.sweep([4010.0, 2000.0]);
+        assert_eq!(capture.finish(), None);
+        capture.sweep([2000.0, 30.0]);
+        capture.sweep([2000.0, 4095.0]);
+        let calibration = capture.finish().unwrap();
+        assert_eq!(
+            calibration.x,
+            AxisCalibration {
+                min: 80,
+                center: 1900,
+                max: 4010
+            }
+        );
+        assert_eq!(calibration.y.center, 2000);
+
+        let mut flash = RamStorage::new();
+        save(&mut flash, 0, &calibration).unwrap();
+        assert_eq!(load(&mut flash, 0), Ok(Some(calibration)));
+    }
+
+    #[test]
+    fn radial_deadzone_keeps_diagonals() {
+        assert_eq!(radial_deadzone([0.05, -0.05], 0.1), [0.0, 0.0]);
+        let [x, y] = radial_deadzone([0.5, 0.5], 0.1);
+        assert!((x - y).abs() < 1e-6);
+        let magnitude = libm::sqrtf(x * x + y * y);
+        assert!((magnitude - (core::f32::consts::FRAC_1_SQRT_2 - 0.1) / 0.9).abs() < 1e-3);
+        // Corners of the square never leave the unit circle
+        let [x, y] = radial_deadzone([1.0, -1.0], 0.1);
+        assert!((x * x + y * y - 1.0).abs() < 1e-5);
+    }
+
+    // Samples every 20 ms, `stick` gives the raw reading and button level at a time
+    fn replay(stick: impl Fn(u64) -> ([f64; 2], bool), until_ms: u64) -> Vec<(u64, Event)> {
+        let mut joystick = Joystick::new(JoystickConfig::default(), Calibration::default());
+        let mut events = Vec::new();
+        for now in (0..=until_ms).step_by(20) {
+            let (raw, pressed) = stick(now);
+            events.extend(joystick.update(now, raw, pressed).map(|e| (now, e)));
+        }
+        events
+    }
+
+    #[test]
+    fn direction_events_need_a_return_to_centre() {
+        let events = replay(
+            |now| {
+                let raw = match now {
+                    // Up, hovering around the press threshold without repeating
+                    100..=300 => [2048.0, 3500.0],
+                    320..=400 => [2048.0, 3200.0],
+                    420..=500 => [2048.0, 3500.0],
+                    // Back through the centre and up again
+                    600..=700 => [2048.0, 2048.0],
+                    720..=800 => [2048.0, 4095.0],
+                    // Rolled straight over to the left
+                    820..=900 => [0.0, 2048.0],
+                    1000..=1100 => [2048.0, 100.0],
+                    1200..=1300 => [4000.0, 2300.0],
+                    _ => [2048.0, 2048.0],
+                };
+                (raw, false)
+            },
+            1500,
+        );
+        let expected = [
+            (100, Event::Up),
+            (720, Event::Up),
+            (820, Event::Left),
+            (1000, Event::Down),
+            (1200, Event::Right),
+        ];
+        assert_eq!(events, expected);
+    }
+
+    #[test]
+    fn button_gestures_are_debounced() {
+        let events = replay(
+            |now| {
+                let pressed = match now {
+                    // Click, with a one sample bounce while held
+                    100..=160 | 200..=240 => true,
+                    // Double click
+                    1000..=1060 | 1200..=1260 => true,
+                    // Long press
+                    2000..=3500 => true,
+                    _ => false,
+                };
+                ([2048.0, 2048.0], pressed)
+            },
+            4000,
+        );
+        assert_eq!(
+            events,
+            [
+                (560, Event::Click),
+                (1320, Event::DoubleClick),
+                (2800, Event::LongPress)
+            ]
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! Brightness patterns for an LED on a PWM channel.
+//!
+//! An [`Effect`] is a pure function of the time since it started, so the
+//! patterns can be checked on the host. With the `embassy` feature,
+//! [`LedEffect`] plays them on an LEDC channel and switches effects on request
+//! from any task.
+
+use core::f32::consts::PI;
+
+/// Refresh interval of the fading effects, fast enough to look smooth.
+pub const FRAME_MS: u64 = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Effect {
+    Off,
+    /// Constant brightness from 0 to 1.
+    Solid(f32),
+    /// On for `duty` (0 to 1) of every `period_ms`.
+    Blink {
+        period_ms: u64,
+        duty: f32,
+    },
+    /// Repeats `text` in Morse code, a dot lasts `unit_ms`. Unknown characters are skipped.
+    Morse {
+        text: &'static str,
+        unit_ms: u64,
+    },
+    /// Double beat, like a pulse, every `period_ms`.
+    Heartbeat {
+        period_ms: u64,
+    },
+    /// Gamma corrected sine fade in and out over `period_ms`.
+    Breathe {
+        period_ms: u64,
+    },
+    /// Full brightness once for `duration_ms`, then the previous effect carries on.
+    Flash {
+        duration_ms: u64,
+    },
+}
+
+/// Brightness to show now and how long it stays, `None` for as long as the effect runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub brightness: f32,
+    pub hold_ms: Option<u64>,
+}
+
+impl Sample {
+    const fn new(brightness: f32, hold_ms: u64) -> Self {
+        Self {
+            brightness,
+            hold_ms: Some(hold_ms),
+        }
+    }
+}
+
+/// Perceived brightness to PWM duty, the eye is far more sensitive at the dark end.
+pub fn gamma(brightness: f32) -> f32 {
+    libm::powf(brightness.clamp(0.0, 1.0), 2.2)
+}
+
+/// PWM duty for `brightness` on a channel with full scale `max_duty`.
+pub fn duty(brightness: f32, max_duty: u16) -> u16 {
+    libm::roundf(brightness.clamp(0.0, 1.0) * max_duty as f32) as u16
+}
+
+impl Effect {
+    /// International distress signal
+    pub const SOS: Effect = Effect::Morse {
+        text: "SOS",
+        unit_ms: 150,
+    };
+
+    /// What to show `elapsed_ms` after the effect started, `None` once a one-shot effect is over.
+    pub fn sample(&self, elapsed_ms: u64) -> Option<Sample> {
+        let sample = match *self {
+            Effect::Off => Sample {
+                brightness: 0.0,
+                hold_ms: None,
+            },
+            Effect::Solid(brightness) => Sample {
+                brightness,
+                hold_ms: None,
+            },
+            Effect::Blink { period_ms, duty } => {
+                let period_ms = period_ms.max(1);
+                let on_ms = libm::roundf(duty.clamp(0.0, 1.0) * period_ms as f32) as u64;
+                let t = elapsed_ms % period_ms;
+                if t < on_ms {
+                    Sample::new(1.0, on_ms - t)
+                } else {
+                    Sample::new(0.0, period_ms - t)
+                }
+            }
+            Effect::Morse { text, unit_ms } => morse(text, unit_ms.max(1), elapsed_ms),
+            Effect::Heartbeat { period_ms } => heartbeat(period_ms.max(1), elapsed_ms),
+            Effect::Breathe { period_ms } => {
+                let phase = (elapsed_ms % period_ms.max(1)) as f32 / period_ms.max(1) as f32;
+                Sample::new(gamma((1.0 - libm::cosf(2.0 * PI * phase)) / 2.0), FRAME_MS)
+            }
+            Effect::Flash { duration_ms } => {
+                if elapsed_ms >= duration_ms {
+                    return None;
+                }
+                Sample::new(1.0, duration_ms - elapsed_ms)
+            }
+        };
+        Some(sample)
+    }
+}
+
+// Dots and dashes of letters and digits
+fn morse_code(c: char) -> Option<&'static str> {
+    const LETTERS: [&str; 26] = [
+        ".-", "-...", "-.-.", "-..", ".", "..-.", "--.", "....", "..", ".---", "-.-", ".-..", "--",
+        "-.", "---", ".--.", "--.-", ".-.", "...", "-", "..-", "...-", ".--", "-..-", "-.--",
+        "--..",
+    ];
+    const DIGITS: [&str; 10] = [
+        "-----", ".----", "..---", "...--", "....-", ".....", "-....", "--...", "---..", "----.",
+    ];
+    match c.to_ascii_uppercase() {
+        c @ 'A'..='Z' => Some(LETTERS[c as usize - 'A' as usize]),
+        c @ '0'..='9' => Some(DIGITS[c as usize - '0' as usize]),
+        _ => None,
+    }
+}
+
+// (on, length in units) of the whole text followed by a word gap
+fn morse_elements(text: &str) -> impl Iterator<Item = (bool, u64)> + '_ {
+    text.split(' ')
+        .filter_map(|word| {
+            let mut letters = word.chars().filter_map(morse_code).peekable();
+            letters.peek()?;
+            Some(letters)
+        })
+        .flat_map(|letters| {
+            letters
+                .flat_map(|code| {
+                    let units = |symbol| if symbol == '-' { 3 } else { 1 };
+                    code.chars()
+                        .flat_map(move |symbol| [(true, units(symbol)), (false, 1)])
+                        // 3 units between letters, 1 of them already follows the last symbol
+                        .chain([(false, 2)])
+                })
+                // and 7 between words
+                .chain([(false, 4)])
+        })
+}
+
+fn morse(text: &'static str, unit_ms: u64, elapsed_ms: u64) -> Sample {
+    let total: u64 = morse_elements(text).map(|(_, units)| units).sum();
+    if total == 0 {
+        return Sample {
+            brightness: 0.0,
+            hold_ms: None,
+        };
+    }
+    let t = elapsed_ms % (total * unit_ms);
+    let mut start = 0;
+    for (on, units) in morse_elements(text) {
+        let end = start + units * unit_ms;
+        if t < end {
+            return Sample::new(if on { 1.0 } else { 0.0 }, end - t);
+        }
+        start = end;
+    }
+    unreachable!()
+}
+
+fn heartbeat(period_ms: u64, elapsed_ms: u64) -> Sample {
+    // Strong beat, short pause, weaker beat, then rest, as fractions of the period
+    const STEPS: [(f32, f32); 4] = [(1.0, 0.1), (0.0, 0.12), (0.5, 0.1), (0.0, 0.68)];
+    let t = elapsed_ms % period_ms;
+    let mut end = 0;
+    for (i, (brightness, fraction)) in STEPS.into_iter().enumerate() {
+        end = if i == STEPS.len() - 1 {
+            period_ms
+        } else {
+            end + libm::roundf(fraction * period_ms as f32) as u64
+        };
+        if t < end {
+            return Sample::new(gamma(brightness), end - t);
+        }
+    }
+    unreachable!()
+}
+
+#[cfg(feature = "embassy")]
+pub use driver::LedEffect;
+
+#[cfg(feature = "embassy")]
+mod driver {
+    use core::future::pending;
+
+    use embassy_futures::select::{select, Either};
+    use embassy_sync::blocking_mutex::raw::RawMutex;
+    use embassy_sync::channel::Channel;
+    use embassy_time::{Duration, Instant, Timer};
+    use embedded_hal::pwm::SetDutyCycle;
+
+    use super::{duty, Effect};
+
+    /// Plays [`Effect`]s on a PWM channel, switched at runtime from any task.
+    ///
+    /// ```ignore
+    /// static STATUS_LED: LedEffect<CriticalSectionRawMutex> = LedEffect::new();
+    ///
+    /// #[embassy_executor::task]
+    /// async fn led_task(mut channel: channel::Channel<'static, LowSpeed>) {
+    ///     STATUS_LED.run(&mut channel).await
+    /// }
+    /// ```
+    pub struct LedEffect<M: RawMutex> {
+        effects: Channel<M, Effect, 4>,
+    }
+
+    impl<M: RawMutex> LedEffect<M> {
+        pub const fn new() -> Self {
+            Self {
+                effects: Channel::new(),
+            }
+        }
+
+        /// Switches to `effect`, waiting while earlier requests are still queued.
+        pub async fn set(&self, effect: Effect) {
+            self.effects.send(effect).await;
+        }
+
+        /// Switches to `effect` unless the queue is full, usable outside async code.
+        pub fn try_set(&self, effect: Effect) -> bool {
+            self.effects.try_send(effect).is_ok()
+        }
+
+        /// Shows the requested effects on `led` forever, starting dark.
+        pub async fn run<P: SetDutyCycle>(&self, led: &mut P) -> ! {
+            let mut current = Effect::Off;
+            // Where to go back to after a flash
+            let mut resume = Effect::Off;
+            let mut start = Instant::now();
+            loop {
+                let Some(sample) = current.sample(start.elapsed().as_millis()) else {
+                    current = resume;
+                    start = Instant::now();
+                    continue;
+                };
+                // LEDC duty updates cannot fail
+                let _ = led.set_duty_cycle(duty(sample.brightness, led.max_duty_cycle()));
+
+                let hold = async {
+                    match sample.hold_ms {
+                        Some(ms) => Timer::after(Duration::from_millis(ms)).await,
+                        None => pending().await,
+                    }
+                };
+                if let Either::Second(effect) = select(hold, self.effects.receive()).await {
+                    match effect {
+                        Effect::Flash { .. } => {
+                            if !matches!(current, Effect::Flash { .. }) {
+                                resume = current;
+                            }
+                        }
+                        _ => resume = effect,
+                    }
+                    current = effect;
+                    start = Instant::now();
+                }
+            }
+        }
+    }
+
+    impl<M: RawMutex> Default for LedEffect<M> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (brightness, length) runs over one cycle, following the holds
+    fn runs(effect: Effect, until_ms: u64) -> Vec<(f32, u64)> {
+        let mut t = 0;
+        let mut out = Vec::new();
+        while t < until_ms {
+            let sample = effect.sample(t).unwrap();
+            let hold = sample.hold_ms.unwrap();
+            out.push((sample.brightness, hold));
+            t += hold;
+        }
+        out
+    }
+
+    #[test]
+    fn blink_duty_cycle() {
+        let blink = Effect::Blink {
+            period_ms: 1000,
+            duty: 0.1,
+        };
+        assert_eq!(
+            runs(blink, 2000),
+            [(1.0, 100), (0.0, 900), (1.0, 100), (0.0, 900)]
+        );
+        // Sampled mid-phase, the hold runs to the next toggle
+        assert_eq!(blink.sample(1050), Some(Sample::new(1.0, 50)));
+    }
+
+    #[test]
+    fn morse_timing() {
+        // S = ..., O = ---, unit 100 ms
+        let sos = Effect::Morse {
+            text: "sos",
+            unit_ms: 100,
+        };
+        let s = [
+            (1.0, 100),
+            (0.0, 100),
+            (1.0, 100),
+            (0.0, 100),
+            (1.0, 100),
+            (0.0, 100),
+            (0.0, 200),
+        ];
+        let o = [
+            (1.0, 300),
+            (0.0, 100),
+            (1.0, 300),
+            (0.0, 100),
+            (1.0, 300),
+            (0.0, 100),
+            (0.0, 200),
+        ];
+        let mut expected: Vec<_> = s.iter().chain(&o).chain(&s).copied().collect();
+        expected.push((0.0, 400));
+        // 5 + 3 + 11 + 3 + 5 + 7 units
+        let cycle = 3400;
+        assert_eq!(runs(sos, cycle), expected);
+        assert_eq!(sos.sample(cycle), sos.sample(0));
+
+        // Unknown characters and extra spaces are skipped
+        let e = Effect::Morse {
+            text: " e? ",
+            unit_ms: 10,
+        };
+        assert_eq!(runs(e, 80), [(1.0, 10), (0.0, 10), (0.0, 20), (0.0, 40)]);
+        let nothing = Effect::Morse {
+            text: "?!",
+            unit_ms: 10,
+        };
+        assert_eq!(nothing.sample(5).unwrap().hold_ms, None);
+    }
+
+    #[test]
+    fn breathing_is_gamma_corrected() {
+        let breathe = Effect::Breathe { period_ms: 2000 };
+        assert_eq!(breathe.sample(0).unwrap().brightness, 0.0);
+        assert!((breathe.sample(1000).unwrap().brightness - 1.0).abs() < 1e-6);
+        // Half way up in perceived brightness is much less than half the duty
+        let quarter = breathe.sample(500).unwrap();
+        assert!((quarter.brightness - gamma(0.5)).abs() < 1e-3);
+        assert!(quarter.brightness < 0.25);
+        assert_eq!(quarter.hold_ms, Some(FRAME_MS));
+        assert_eq!(breathe.sample(2500), breathe.sample(500));
+    }
+
+    #[test]
+    fn heartbeat_and_flash() {
+        let beat = Effect::Heartbeat { period_ms: 1000 };
+        assert_eq!(
+            runs(beat, 1000),
+            [(1.0, 100), (0.0, 120), (gamma(0.5), 100), (0.0, 680)]
+        );
+
+        let flash = Effect::Flash { duration_ms: 80 };
+        assert_eq!(flash.sample(0), Some(Sample::new(1.0, 80)));
+        assert_eq!(flash.sample(80), None);
+    }
+
+    #[test]
+    fn duty_scaling() {
+        assert_eq!(duty(0.0, 1023), 0);
+        assert_eq!(duty(1.0, 1023), 1023);
+        assert_eq!(duty(0.5, 1024), 512);
+        assert_eq!(duty(1.5, 255), 255);
+    }
+}
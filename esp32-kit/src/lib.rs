@@ -0,0 +1,78 @@
+//! Hardware-independent building blocks shared by the ESP32 examples.
+//!
+//! Everything in here is plain `no_std` logic so it can be unit-tested on the
+//! host with `cargo test`. Pieces that need `esp-hal` are provided as macros
+//! which expand inside the example crate.
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+
+pub mod adc;
+#[cfg(any(test, feature = "std"))]
+pub mod build;
+pub mod button;
+pub mod clock;
+pub mod dhcp;
+pub mod dns;
+pub mod filter;
+pub mod hcsr04;
+pub mod joystick;
+pub mod led;
+pub mod mdns;
+pub mod motion;
+pub mod music;
+pub mod pantilt;
+pub mod proximity;
+pub mod servo;
+pub mod sntp;
+pub mod status;
+pub mod storage;
+pub mod thermistor;
+pub mod wifi;
+
+#[doc(hidden)]
+pub use static_cell;
+
+#[cfg(all(feature = "panic-handler", not(any(test, feature = "std"))))]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
+
+// When you are okay with using a nightly compiler it's better to use https://docs.rs/static_cell/2.1.0/static_cell/macro.make_static.html
+#[macro_export]
+macro_rules! mk_static {
+    ($t:ty,$val:expr) => {{
+        static STATIC_CELL: $crate::static_cell::StaticCell<$t> =
+            $crate::static_cell::StaticCell::new();
+        #[deny(unused_attributes)]
+        let x = STATIC_CELL.uninit().write(($val));
+        x
+    }};
+}
+
+/// Busy-waits for the given `esp_hal::time::Duration`.
+#[macro_export]
+macro_rules! blocking_delay {
+    ($duration:expr) => {{
+        let delay_start = ::esp_hal::time::Instant::now();
+        while delay_start.elapsed() < $duration {}
+    }};
+}
+
+/// Initializes `esp-hal` with the CPU clock at its maximum and returns the peripherals.
+#[macro_export]
+macro_rules! init {
+    () => {
+        ::esp_hal::init(
+            ::esp_hal::Config::default().with_cpu_clock(::esp_hal::clock::CpuClock::max()),
+        )
+    };
+}
+
+/// Starts the embassy time driver on timer0 of the given timer group.
+#[macro_export]
+macro_rules! init_embassy {
+    ($timg:expr) => {{
+        let timer0 = ::esp_hal::timer::timg::TimerGroup::new($timg);
+        ::esp_hal_embassy::init(timer0.timer0);
+    }};
+}
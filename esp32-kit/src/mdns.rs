@@ -0,0 +1,553 @@
+//! mDNS responder with DNS-SD, so the board can be found as e.g. `esp32.local`.
+//!
+//! [`Responder`] answers queries for its host name and advertises services
+//! such as a web server under `_http._tcp`. Like [`crate::dns`] it only turns
+//! packets into packets, the caller owns the UDP socket on [`PORT`] and the
+//! membership of [`GROUP`].
+
+use core::iter;
+use core::net::Ipv4Addr;
+
+use crate::dns::{self, Header, CLASS_IN, TYPE_A, TYPE_ANY};
+
+pub const PORT: u16 = 5353;
+/// Where queries and announcements are sent.
+pub const GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 0, 251);
+
+pub const TYPE_PTR: u16 = 12;
+pub const TYPE_TXT: u16 = 16;
+pub const TYPE_SRV: u16 = 33;
+/// Services past this many are never advertised.
+pub const MAX_SERVICES: usize = 8;
+
+const HEADER_LEN: usize = 12;
+// Records of names only one host may answer for
+const CACHE_FLUSH: u16 = 0x8000;
+// Questions asking for a unicast reply
+const UNICAST_RESPONSE: u16 = 0x8000;
+// Recommended by RFC 6762 for records with and without the host name
+const HOST_TTL: u32 = 120;
+const OTHER_TTL: u32 = 4500;
+// Most a one-shot resolver may cache an answer
+const LEGACY_TTL: u32 = 10;
+const CLASS_ANY: u16 = 255;
+
+// The name DNS-SD browsers query for all service types
+const SERVICE_TYPES: [&str; 4] = ["_services", "_dns-sd", "_udp", "local"];
+
+/// A service advertised with DNS-SD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Service<'a> {
+    /// Name shown by service browsers, e.g. `"ESP32 web"`, without dots.
+    pub instance: &'a str,
+    /// e.g. `"_http._tcp"`
+    pub service_type: &'a str,
+    pub port: u16,
+    /// `key=value` pairs of the TXT record, e.g. the firmware version.
+    pub txt: &'a [(&'a str, &'a str)],
+}
+
+impl<'a> Service<'a> {
+    fn type_name(&self) -> impl Iterator<Item = &'a str> + Clone {
+        self.service_type.split('.').chain(iter::once("local"))
+    }
+
+    fn instance_name(&self) -> impl Iterator<Item = &'a str> + Clone {
+        iter::once(self.instance).chain(self.type_name())
+    }
+}
+
+// One bit per service
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct Records {
+    host: bool,
+    service_types: u8,
+    pointers: u8,
+    srv: u8,
+    txt: u8,
+}
+
+impl Records {
+    fn all(services: usize) -> Self {
+        let mask = ((1u16 << services.min(MAX_SERVICES)) - 1) as u8;
+        Self {
+            host: true,
+            service_types: mask,
+            pointers: mask,
+            srv: mask,
+            txt: mask,
+        }
+    }
+
+    fn without(self, other: Self) -> Self {
+        Self {
+            host: self.host && !other.host,
+            service_types: self.service_types & !other.service_types,
+            pointers: self.pointers & !other.pointers,
+            srv: self.srv & !other.srv,
+            txt: self.txt & !other.txt,
+        }
+    }
+
+    fn count(&self) -> u16 {
+        self.host as u16
+            + [self.service_types, self.pointers, self.srv, self.txt]
+                .iter()
+                .map(|mask| mask.count_ones() as u16)
+                .sum::<u16>()
+    }
+}
+
+// Appends records to a message
+struct Writer<'o> {
+    out: &'o mut [u8],
+    len: usize,
+    // Legacy replies cap the TTL and leave out the cache flush bit
+    legacy: bool,
+}
+
+impl Writer<'_> {
+    fn bytes(&mut self, bytes: &[u8]) -> Option<()> {
+        self.out
+            .get_mut(self.len..self.len + bytes.len())?
+            .copy_from_slice(bytes);
+        self.len += bytes.len();
+        Some(())
+    }
+
+    fn name<'l>(&mut self, labels: impl IntoIterator<Item = &'l str>) -> Option<()> {
+        self.len = dns::write_labels(self.out, self.len, labels)?;
+        Some(())
+    }
+
+    fn record<'l>(
+        &mut self,
+        name: impl IntoIterator<Item = &'l str>,
+        record_type: u16,
+        unique: bool,
+        ttl: u32,
+        data: impl FnOnce(&mut Self) -> Option<()>,
+    ) -> Option<()> {
+        self.name(name)?;
+        let class = if unique && !self.legacy {
+            CLASS_IN | CACHE_FLUSH
+        } else {
+            CLASS_IN
+        };
+        let ttl = if self.legacy {
+            ttl.min(LEGACY_TTL)
+        } else {
+            ttl
+        };
+        self.bytes(&record_type.to_be_bytes())?;
+        self.bytes(&class.to_be_bytes())?;
+        self.bytes(&ttl.to_be_bytes())?;
+        // Data length, filled in below
+        let length_at = self.len;
+        self.bytes(&[0, 0])?;
+        data(self)?;
+        let length = (self.len - length_at - 2) as u16;
+        self.out[length_at..length_at + 2].copy_from_slice(&length.to_be_bytes());
+        Some(())
+    }
+}
+
+/// Answers for one host name and its services.
+#[derive(Debug, Clone, Copy)]
+pub struct Responder<'a> {
+    hostname: &'a str,
+    services: &'a [Service<'a>],
+}
+
+impl<'a> Responder<'a> {
+    /// `hostname` may be given with or without `.local`.
+    pub fn new(hostname: &'a str, services: &'a [Service<'a>]) -> Self {
+        let hostname = hostname.strip_suffix(".local").unwrap_or(hostname);
+        let services = &services[..services.len().min(MAX_SERVICES)];
+        Self { hostname, services }
+    }
+
+    /// The host name without `.local`.
+    pub fn hostname(&self) -> &'a str {
+        self.hostname
+    }
+
+    fn host_name(&self) -> impl Iterator<Item = &'a str> + Clone {
+        self.hostname.split('.').chain(iter::once("local"))
+    }
+
+    /// Answers `query`, received from `source_port`, returns the length of the reply in `out`.
+    ///
+    /// Queries from [`PORT`] are answered to [`GROUP`], others come from one-shot
+    /// resolvers such as `dig -p 5353 @224.0.0.251` and are answered to the sender.
+    /// `None` when there is nothing to answer.
+    pub fn respond(
+        &self,
+        query: &[u8],
+        source_port: u16,
+        address: Ipv4Addr,
+        out: &mut [u8],
+    ) -> Option<usize> {
+        let header = Header::parse(query)?;
+        if !header.is_standard_query() {
+            return None;
+        }
+        let mut answers = Records::default();
+        let mut additionals = Records::default();
+        let mut offset = HEADER_LEN;
+        for _ in 0..header.questions {
+            let name = offset;
+            offset = dns::skip_name(query, offset)?;
+            let fixed = query.get(offset..offset + 4)?;
+            offset += 4;
+            let question_type = u16::from_be_bytes([fixed[0], fixed[1]]);
+            let class = u16::from_be_bytes([fixed[2], fixed[3]]) & !UNICAST_RESPONSE;
+            if class != CLASS_IN && class != CLASS_ANY {
+                continue;
+            }
+            let wants =
+                |record_type: u16| question_type == record_type || question_type == TYPE_ANY;
+
+            if wants(TYPE_A) && dns::name_eq(query, name, self.host_name()) {
+                answers.host = true;
+            }
+            if wants(TYPE_PTR) && dns::name_eq(query, name, SERVICE_TYPES) {
+                answers.service_types = Records::all(self.services.len()).service_types;
+            }
+            for (i, service) in self.services.iter().enumerate() {
+                let bit = 1 << i;
+                if wants(TYPE_PTR) && dns::name_eq(query, name, service.type_name()) {
+                    // Saves browsers asking for the rest one by one
+                    answers.pointers |= bit;
+                    additionals.srv |= bit;
+                    additionals.txt |= bit;
+                    additionals.host = true;
+                }
+                if dns::name_eq(query, name, service.instance_name()) {
+                    if wants(TYPE_SRV) {
+                        answers.srv |= bit;
+                        additionals.host = true;
+                    }
+                    if wants(TYPE_TXT) {
+                        answers.txt |= bit;
+                    }
+                }
+            }
+        }
+        if answers.count() == 0 {
+            return None;
+        }
+
+        let legacy = source_port != PORT;
+        let mut writer = Writer {
+            out,
+            len: HEADER_LEN,
+            legacy,
+        };
+        let questions = if legacy {
+            // One-shot resolvers match the reply to their query
+            writer.bytes(&query[HEADER_LEN..offset])?;
+            header.questions
+        } else {
+            0
+        };
+        self.write_records(&mut writer, answers, address)?;
+        let additionals = additionals.without(answers);
+        self.write_records(&mut writer, additionals, address)?;
+        Header {
+            id: if legacy { header.id } else { 0 },
+            flags: Header::FLAG_RESPONSE | Header::FLAG_AUTHORITATIVE,
+            questions,
+            answers: answers.count(),
+            authorities: 0,
+            additionals: additionals.count(),
+        }
+        .write(writer.out);
+        Some(writer.len)
+    }
+
+    /// Every record, sent unasked to [`GROUP`] once the board has `address`.
+    ///
+    /// RFC 6762 asks for at least two announcements, one second apart.
+    pub fn announce(&self, address: Ipv4Addr, out: &mut [u8]) -> Option<usize> {
+        let mut writer = Writer {
+            out,
+            len: HEADER_LEN,
+            legacy: false,
+        };
+        let records = Records::all(self.services.len());
+        self.write_records(&mut writer, records, address)?;
+        Header {
+            id: 0,
+            flags: Header::FLAG_RESPONSE | Header::FLAG_AUTHORITATIVE,
+            questions: 0,
+            answers: records.count(),
+            authorities: 0,
+            additionals: 0,
+        }
+        .write(writer.out);
+        Some(writer.len)
+    }
+
+    fn write_records(
+        &self,
+        writer: &mut Writer<'_>,
+        records: Records,
+        address: Ipv4Addr,
+    ) -> Option<()> {
+        if records.host {
+            writer.record(self.host_name(), TYPE_A, true, HOST_TTL, |w| {
+                w.bytes(&address.octets())
+            })?;
+        }
+        for (i, service) in self.services.iter().enumerate() {
+            let bit = 1 << i;
+            if records.service_types & bit != 0 {
+                writer.record(SERVICE_TYPES, TYPE_PTR, false, OTHER_TTL, |w| {
+                    w.name(service.type_name())
+                })?;
+            }
+            if records.pointers & bit != 0 {
+                writer.record(service.type_name(), TYPE_PTR, false, OTHER_TTL, |w| {
+                    w.name(service.instance_name())
+                })?;
+            }
+            if records.srv & bit != 0 {
+                writer.record(service.instance_name(), TYPE_SRV, true, HOST_TTL, |w| {
+                    // Priority and weight
+                    w.bytes(&[0, 0, 0, 0])?;
+                    w.bytes(&service.port.to_be_bytes())?;
+                    w.name(self.host_name())
+                })?;
+            }
+            if records.txt & bit != 0 {
+                writer.record(service.instance_name(), TYPE_TXT, true, OTHER_TTL, |w| {
+                    for (key, value) in service.txt {
+                        let len = key.len() + 1 + value.len();
+                        w.bytes(&[u8::try_from(len).ok()?])?;
+                        w.bytes(key.as_bytes())?;
+                        w.bytes(b"=")?;
+                        w.bytes(value.as_bytes())?;
+                    }
+                    // A TXT record is never empty
+                    if service.txt.is_empty() {
+                        w.bytes(&[0])?;
+                    }
+                    Some(())
+                })?;
+            }
+        }
+        Some(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TXT: [(&str, &str); 2] = [("version", "0.1.0"), ("id", "a0b1c2d3e4f5")];
+    const SERVICES: [Service; 1] = [Service {
+        instance: "ESP32 web",
+        service_type: "_http._tcp",
+        port: 80,
+        txt: &TXT,
+    }];
+    const ADDRESS: Ipv4Addr = Ipv4Addr::new(192, 168, 1, 42);
+
+    fn query(name: &str, record_type: u16) -> Vec<u8> {
+        let mut packet = vec![0u8; 512];
+        Header {
+            id: 0,
+            flags: 0,
+            questions: 1,
+            answers: 0,
+            authorities: 0,
+            additionals: 0,
+        }
+        .write(&mut packet);
+        let end = dns::write_name(&mut packet, HEADER_LEN, name).unwrap();
+        packet.truncate(end);
+        packet.extend(record_type.to_be_bytes());
+        packet.extend(CLASS_IN.to_be_bytes());
+        packet
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Parsed {
+        name: String,
+        record_type: u16,
+        class: u16,
+        ttl: u32,
+        data: Vec<u8>,
+    }
+
+    // Every record after the questions of `packet`
+    fn parse(packet: &[u8]) -> (Header, Vec<Parsed>) {
+        let header = Header::parse(packet).unwrap();
+        let mut offset = HEADER_LEN;
+        for _ in 0..header.questions {
+            offset = dns::skip_name(packet, offset).unwrap() + 4;
+        }
+        let mut records = Vec::new();
+        for _ in 0..header.answers + header.additionals {
+            let mut labels = Vec::new();
+            dns::read_name(packet, offset, |label| {
+                labels.push(String::from_utf8(label.to_vec()).unwrap())
+            })
+            .unwrap();
+            offset = dns::skip_name(packet, offset).unwrap();
+            let word = |i: usize| u16::from_be_bytes([packet[i], packet[i + 1]]);
+            let len = word(offset + 8) as usize;
+            records.push(Parsed {
+                name: labels.join("."),
+                record_type: word(offset),
+                class: word(offset + 2),
+                ttl: u32::from_be_bytes(packet[offset + 4..offset + 8].try_into().unwrap()),
+                data: packet[offset + 10..offset + 10 + len].to_vec(),
+            });
+            offset += 10 + len;
+        }
+        assert_eq!(offset, packet.len());
+        (header, records)
+    }
+
+    #[test]
+    fn answers_the_host_name() {
+        let responder = Responder::new("esp32.local", &SERVICES);
+        assert_eq!(responder.hostname(), "esp32");
+        let mut out = [0u8; 512];
+        let len = responder
+            .respond(&query("ESP32.local", TYPE_A), PORT, ADDRESS, &mut out)
+            .unwrap();
+        let (header, records) = parse(&out[..len]);
+        assert_eq!(header.id, 0);
+        assert!(header.is_response());
+        assert_eq!(
+            (header.questions, header.answers, header.additionals),
+            (0, 1, 0)
+        );
+        assert_eq!(
+            records,
+            [Parsed {
+                name: "esp32.local".into(),
+                record_type: TYPE_A,
+                class: CLASS_IN | CACHE_FLUSH,
+                ttl: HOST_TTL,
+                data: vec![192, 168, 1, 42],
+            }]
+        );
+
+        // Other hosts and types are none of its business
+        assert_eq!(
+            responder.respond(&query("other.local", TYPE_A), PORT, ADDRESS, &mut out),
+            None
+        );
+        assert_eq!(
+            responder.respond(&query("esp32.local", 28), PORT, ADDRESS, &mut out),
+            None
+        );
+    }
+
+    #[test]
+    fn browsing_for_http_services() {
+        let responder = Responder::new("esp32", &SERVICES);
+        let mut out = [0u8; 512];
+        let len = responder
+            .respond(
+                &query("_services._dns-sd._udp.local", TYPE_PTR),
+                PORT,
+                ADDRESS,
+                &mut out,
+            )
+            .unwrap();
+        let (_, records) = parse(&out[..len]);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].name, "_services._dns-sd._udp.local");
+        assert_eq!(records[0].data, b"\x05_http\x04_tcp\x05local\x00");
+
+        // The pointer comes with everything needed to connect
+        let len = responder
+            .respond(
+                &query("_http._tcp.local", TYPE_PTR),
+                PORT,
+                ADDRESS,
+                &mut out,
+            )
+            .unwrap();
+        let (header, records) = parse(&out[..len]);
+        assert_eq!((header.answers, header.additionals), (1, 3));
+        let kinds: Vec<_> = records
+            .iter()
+            .map(|r| (r.name.as_str(), r.record_type))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("_http._tcp.local", TYPE_PTR),
+                ("esp32.local", TYPE_A),
+                ("ESP32 web._http._tcp.local", TYPE_SRV),
+                ("ESP32 web._http._tcp.local", TYPE_TXT),
+            ]
+        );
+        assert_eq!(records[0].class, CLASS_IN);
+        assert_eq!(
+            records[0].data,
+            b"\x09ESP32 web\x05_http\x04_tcp\x05local\x00"
+        );
+        assert_eq!(
+            records[2].data,
+            b"\x00\x00\x00\x00\x00\x50\x05esp32\x05local\x00"
+        );
+        assert_eq!(records[3].data, b"\x0dversion=0.1.0\x0fid=a0b1c2d3e4f5");
+    }
+
+    #[test]
+    fn one_shot_queries_get_a_unicast_style_reply() {
+        let responder = Responder::new("esp32", &SERVICES);
+        let mut question = query("esp32.local", TYPE_A);
+        question[..2].copy_from_slice(&0x4242u16.to_be_bytes());
+        let mut out = [0u8; 512];
+        let len = responder
+            .respond(&question, 40000, ADDRESS, &mut out)
+            .unwrap();
+        let (header, records) = parse(&out[..len]);
+        assert_eq!((header.id, header.questions), (0x4242, 1));
+        assert_eq!(out[HEADER_LEN..question.len()], question[HEADER_LEN..]);
+        assert_eq!((records[0].class, records[0].ttl), (CLASS_IN, LEGACY_TTL));
+    }
+
+    #[test]
+    fn announces_everything() {
+        let responder = Responder::new("esp32", &SERVICES);
+        let mut out = [0u8; 512];
+        let len = responder.announce(ADDRESS, &mut out).unwrap();
+        let (header, records) = parse(&out[..len]);
+        assert_eq!(header.answers, 5);
+        assert_eq!(records.len(), 5);
+        assert!(responder.announce(ADDRESS, &mut out[..100]).is_none());
+
+        // Without TXT pairs the record holds one empty string
+        let bare = [Service {
+            txt: &[],
+            ..SERVICES[0]
+        }];
+        let len = Responder::new("esp32", &bare)
+            .announce(ADDRESS, &mut out)
+            .unwrap();
+        let (_, records) = parse(&out[..len]);
+        assert_eq!(records.last().unwrap().data, [0]);
+    }
+
+    #[test]
+    fn ignores_responses_and_garbage() {
+        let responder = Responder::new("esp32", &SERVICES);
+        let mut out = [0u8; 512];
+        let mut response = query("esp32.local", TYPE_A);
+        response[2] |= 0x80;
+        assert_eq!(responder.respond(&response, PORT, ADDRESS, &mut out), None);
+        let truncated = query("esp32.local", TYPE_A);
+        let truncated = &truncated[..truncated.len() - 2];
+        assert_eq!(responder.respond(truncated, PORT, ADDRESS, &mut out), None);
+        assert_eq!(responder.respond(&[0; 5], PORT, ADDRESS, &mut out), None);
+    }
+}
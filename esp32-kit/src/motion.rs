@@ -0,0 +1,364 @@
+//! PIR motion alarm with warm-up, pulse filtering, cooldown and an event log.
+//!
+//! Like [`crate::proximity`], time is passed in as milliseconds so the state
+//! machine can be driven with simulated edge timestamps on the host.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    /// The PIR needs time after power-on before its output can be trusted.
+    WarmingUp,
+    Disarmed,
+    Armed,
+    Triggered,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventKind {
+    /// Warm-up finished.
+    Ready,
+    Armed,
+    Disarmed,
+    Triggered,
+    /// Cooldown after an alarm is over, motion is watched again.
+    Rearmed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Event {
+    pub at_ms: u64,
+    pub kind: EventKind,
+}
+
+/// Keeps the last `N` events, overwriting the oldest.
+#[derive(Debug, Clone, Copy)]
+pub struct EventLog<const N: usize> {
+    events: [Option<Event>; N],
+    next: usize,
+}
+
+impl<const N: usize> EventLog<N> {
+    pub const fn new() -> Self {
+        // Nowhere to put the first event otherwise
+        const { assert!(N > 0, "EventLog needs room for at least one event") };
+        Self {
+            events: [None; N],
+            next: 0,
+        }
+    }
+
+    pub fn push(&mut self, event: Event) {
+        self.events[self.next] = Some(event);
+        self.next = (self.next + 1) % N;
+    }
+
+    pub fn len(&self) -> usize {
+        self.events.iter().filter(|e| e.is_some()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.events[0].is_none()
+    }
+
+    /// Oldest event first.
+    pub fn iter(&self) -> impl Iterator<Item = Event> + '_ {
+        let (newer, older) = self.events.split_at(self.next);
+        older.iter().chain(newer).filter_map(|e| *e)
+    }
+}
+
+impl<const N: usize> Default for EventLog<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MotionConfig {
+    /// Time after power-on before the sensor output is used (HC-SR501 needs up to a minute).
+    pub warm_up_ms: u64,
+    /// The sensor has to stay high this long to count as motion.
+    pub min_pulse_ms: u64,
+    /// How long the alarm sounds once triggered.
+    pub alarm_ms: u64,
+    /// Quiet time after the alarm before motion can trigger it again.
+    pub cooldown_ms: u64,
+}
+
+impl Default for MotionConfig {
+    fn default() -> Self {
+        Self {
+            warm_up_ms: 60_000,
+            min_pulse_ms: 300,
+            alarm_ms: 5_000,
+            cooldown_ms: 10_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MotionAlarm<const N: usize> {
+    config: MotionConfig,
+    ready_at: u64,
+    warmed_up: bool,
+    armed: bool,
+    // Rising edge of the current sensor pulse
+    high_since: Option<u64>,
+    // Pulses only count from here, so motion during warm-up or cooldown is not held over
+    watch_from: u64,
+    triggered_until: Option<u64>,
+    cooldown_until: Option<u64>,
+    log: EventLog<N>,
+}
+
+impl<const N: usize> MotionAlarm<N> {
+    /// `now_ms` is the power-on time of the sensor.
+    pub fn new(config: MotionConfig, now_ms: u64) -> Self {
+        let ready_at = now_ms + config.warm_up_ms;
+        Self {
+            config,
+            ready_at,
+            warmed_up: false,
+            armed: false,
+            high_since: None,
+            watch_from: ready_at,
+            triggered_until: None,
+            cooldown_until: None,
+            log: EventLog::new(),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        if !self.warmed_up {
+            State::WarmingUp
+        } else if !self.armed {
+            State::Disarmed
+        } else if self.triggered_until.is_some() {
+            State::Triggered
+        } else {
+            State::Armed
+        }
+    }
+
+    pub fn log(&self) -> &EventLog<N> {
+        &self.log
+    }
+
+    /// Arming during warm-up takes effect once the sensor is ready.
+    pub fn set_armed(&mut self, now_ms: u64, armed: bool) -> State {
+        self.poll(now_ms);
+        if armed != self.armed {
+            self.armed = armed;
+            self.triggered_until = None;
+            self.cooldown_until = None;
+            self.watch_from = now_ms.max(self.ready_at);
+            self.record(
+                now_ms,
+                if armed {
+                    EventKind::Armed
+                } else {
+                    EventKind::Disarmed
+                },
+            );
+        }
+        self.poll(now_ms)
+    }
+
+    pub fn toggle_armed(&mut self, now_ms: u64) -> State {
+        self.set_armed(now_ms, !self.armed)
+    }
+
+    /// Feeds a level change of the PIR output.
+    pub fn sensor(&mut self, now_ms: u64, high: bool) -> State {
+        self.poll(now_ms);
+        self.high_since = match (high, self.high_since) {
+            (true, None) => Some(now_ms),
+            (true, since) => since,
+            (false, _) => None,
+        };
+        self.poll(now_ms)
+    }
+
+    /// Advances timers; call at [`Self::next_deadline`] or whenever convenient.
+    pub fn poll(&mut self, now_ms: u64) -> State {
+        if !self.warmed_up && now_ms >= self.ready_at {
+            self.warmed_up = true;
+            self.record(self.ready_at, EventKind::Ready);
+        }
+        if let Some(until) = self.triggered_until.filter(|&until| now_ms >= until) {
+            self.triggered_until = None;
+            self.cooldown_until = Some(until + self.config.cooldown_ms);
+        }
+        if let Some(until) = self.cooldown_until.filter(|&until| now_ms >= until) {
+            self.cooldown_until = None;
+            self.watch_from = until;
+            self.record(until, EventKind::Rearmed);
+        }
+        if let Some(at) = self.trigger_at() {
+            if now_ms >= at {
+                self.triggered_until = Some(at + self.config.alarm_ms);
+                self.record(at, EventKind::Triggered);
+                // The alarm may already be over if polled late
+                return self.poll(now_ms);
+            }
+        }
+        self.state()
+    }
+
+    /// When the state changes next without any input, if ever.
+    pub fn next_deadline(&self) -> Option<u64> {
+        if !self.warmed_up {
+            return Some(self.ready_at);
+        }
+        self.triggered_until
+            .or(self.cooldown_until)
+            .or_else(|| self.trigger_at())
+    }
+
+    // When the current pulse becomes long enough to count as motion
+    fn trigger_at(&self) -> Option<u64> {
+        if !self.warmed_up
+            || !self.armed
+            || self.triggered_until.is_some()
+            || self.cooldown_until.is_some()
+        {
+            return None;
+        }
+        let since = self.high_since?.max(self.watch_from);
+        Some(since + self.config.min_pulse_ms)
+    }
+
+    fn record(&mut self, at_ms: u64, kind: EventKind) {
+        self.log.push(Event { at_ms, kind });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: MotionConfig = MotionConfig {
+        warm_up_ms: 1_000,
+        min_pulse_ms: 300,
+        alarm_ms: 2_000,
+        cooldown_ms: 5_000,
+    };
+
+    fn kinds<const N: usize>(alarm: &MotionAlarm<N>) -> Vec<EventKind> {
+        alarm.log().iter().map(|e| e.kind).collect()
+    }
+
+    #[test]
+    fn ignores_motion_during_warm_up() {
+        let mut alarm = MotionAlarm::<8>::new(CONFIG, 0);
+        alarm.set_armed(0, true);
+        assert_eq!(alarm.sensor(100, true), State::WarmingUp);
+        assert_eq!(alarm.next_deadline(), Some(1_000));
+        // Still high at the end of warm-up, only counts from there
+        assert_eq!(alarm.poll(1_000), State::Armed);
+        assert_eq!(alarm.next_deadline(), Some(1_300));
+        assert_eq!(alarm.poll(1_300), State::Triggered);
+        assert_eq!(
+            kinds(&alarm),
+            [EventKind::Armed, EventKind::Ready, EventKind::Triggered]
+        );
+    }
+
+    #[test]
+    fn short_pulses_are_filtered() {
+        let mut alarm = MotionAlarm::<8>::new(CONFIG, 0);
+        alarm.set_armed(2_000, true);
+        alarm.sensor(3_000, true);
+        assert_eq!(alarm.sensor(3_200, false), State::Armed);
+        assert_eq!(alarm.next_deadline(), None);
+        alarm.sensor(4_000, true);
+        assert_eq!(alarm.poll(4_299), State::Armed);
+        assert_eq!(alarm.poll(4_300), State::Triggered);
+    }
+
+    #[test]
+    fn cooldown_before_rearming() {
+        let mut alarm = MotionAlarm::<8>::new(CONFIG, 0);
+        alarm.set_armed(2_000, true);
+        alarm.sensor(3_000, true);
+        assert_eq!(alarm.poll(3_300), State::Triggered);
+        assert_eq!(alarm.poll(5_299), State::Triggered);
+        // Alarm over, still moving but cooling down
+        assert_eq!(alarm.poll(5_300), State::Armed);
+        assert_eq!(alarm.next_deadline(), Some(10_300));
+        assert_eq!(alarm.poll(8_000), State::Armed);
+        // Pulse held through the cooldown counts from the re-arm
+        assert_eq!(alarm.poll(10_300), State::Armed);
+        assert_eq!(alarm.poll(10_600), State::Triggered);
+
+        let log: Vec<_> = alarm.log().iter().collect();
+        assert_eq!(
+            log[1..],
+            [
+                Event {
+                    at_ms: 2_000,
+                    kind: EventKind::Armed
+                },
+                Event {
+                    at_ms: 3_300,
+                    kind: EventKind::Triggered
+                },
+                Event {
+                    at_ms: 10_300,
+                    kind: EventKind::Rearmed
+                },
+                Event {
+                    at_ms: 10_600,
+                    kind: EventKind::Triggered
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn late_poll_catches_up() {
+        let mut alarm = MotionAlarm::<8>::new(CONFIG, 0);
+        alarm.set_armed(2_000, true);
+        alarm.sensor(3_000, true);
+        alarm.sensor(3_500, false);
+        assert_eq!(alarm.poll(20_000), State::Armed);
+        assert_eq!(
+            kinds(&alarm),
+            [
+                EventKind::Ready,
+                EventKind::Armed,
+                EventKind::Triggered,
+                EventKind::Rearmed
+            ]
+        );
+    }
+
+    #[test]
+    fn disarming_silences_alarm() {
+        let mut alarm = MotionAlarm::<8>::new(CONFIG, 0);
+        alarm.toggle_armed(2_000);
+        alarm.sensor(3_000, true);
+        assert_eq!(alarm.poll(3_300), State::Triggered);
+        assert_eq!(alarm.toggle_armed(3_500), State::Disarmed);
+        assert_eq!(alarm.poll(4_000), State::Disarmed);
+        assert_eq!(alarm.next_deadline(), None);
+        // Re-arming while the sensor is still high needs a full pulse again
+        assert_eq!(alarm.toggle_armed(4_000), State::Armed);
+        assert_eq!(alarm.poll(4_299), State::Armed);
+        assert_eq!(alarm.poll(4_300), State::Triggered);
+    }
+
+    #[test]
+    fn event_log_keeps_newest() {
+        let mut log = EventLog::<3>::new();
+        assert!(log.is_empty());
+        for at_ms in 0..5 {
+            log.push(Event {
+                at_ms,
+                kind: EventKind::Armed,
+            });
+        }
+        assert_eq!(log.len(), 3);
+        let times: Vec<_> = log.iter().map(|e| e.at_ms).collect();
+        assert_eq!(times, [2, 3, 4]);
+    }
+}
@@ -1,3 +1,8 @@
+#[cfg(feature = "embassy")]
+pub mod player;
+pub mod rtttl;
+pub mod sequencer;
+
 #[allow(unused)]
 // Note frequencies in Hertz as f64
 pub const NOTE_B0: f64 = 31.0;
@@ -91,6 +96,14 @@ pub const NOTE_D8: f64 = 4699.0;
 pub const NOTE_DS8: f64 = 4978.0;
 pub const REST: f64 = 0.0; // No sound, for pauses
 
+/// A tune as `(frequency, divider)` notes played at `tempo`, see [`Song`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Melody<'a> {
+    pub name: &'a str,
+    pub tempo: u16,
+    pub notes: &'a [(f64, i16)],
+}
+
 pub struct Song {
     whole_note: u32,
 }
@@ -109,4 +122,24 @@ impl Song {
             (duration as f64 * 1.5) as u32
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn note_durations_follow_tempo() {
+        let song = Song::new(120);
+        assert_eq!(song.calc_note_duration(1), 2000);
+        assert_eq!(song.calc_note_duration(4), 500);
+        assert_eq!(song.calc_note_duration(8), 250);
+    }
+
+    #[test]
+    fn negative_divider_is_dotted() {
+        let song = Song::new(120);
+        assert_eq!(song.calc_note_duration(-4), 750);
+        assert_eq!(song.calc_note_duration(-8), 375);
+    }
+}
@@ -0,0 +1,182 @@
+//! Plays a queue of melodies in the background while the rest of the firmware keeps running.
+//!
+//! ```ignore
+//! static PLAYER: Player<CriticalSectionRawMutex, 4> = Player::new();
+//!
+//! #[embassy_executor::task]
+//! async fn player_task(mut buzzer: LedcTone) {
+//!     PLAYER.run(&mut buzzer).await
+//! }
+//! ```
+
+use embassy_futures::select::{select, Either};
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Instant, Timer};
+
+use super::rtttl::{Notes, Rtttl};
+use super::{Melody, Song, REST};
+
+/// A square wave output, e.g. an LEDC channel driving a passive buzzer.
+pub trait Tone {
+    fn tone(&mut self, freq_hz: u32);
+    fn no_tone(&mut self);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Tune {
+    Melody(Melody<'static>),
+    Rtttl(Rtttl<'static>),
+}
+
+impl Tune {
+    fn tempo(&self) -> u16 {
+        match self {
+            Tune::Melody(melody) => melody.tempo,
+            Tune::Rtttl(rtttl) => rtttl.tempo(),
+        }
+    }
+
+    fn notes(&self) -> TuneNotes {
+        match self {
+            Tune::Melody(melody) => TuneNotes::Melody(melody.notes.iter()),
+            Tune::Rtttl(rtttl) => TuneNotes::Rtttl(rtttl.notes()),
+        }
+    }
+}
+
+enum TuneNotes {
+    Melody(core::slice::Iter<'static, (f64, i16)>),
+    Rtttl(Notes<'static>),
+}
+
+impl Iterator for TuneNotes {
+    type Item = (f64, i16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            TuneNotes::Melody(notes) => notes.next().copied(),
+            TuneNotes::Rtttl(notes) => notes.next(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Pause,
+    Resume,
+    /// Ends the current tune and moves on to the next in the queue.
+    Skip,
+    /// Ends the current tune and drops everything queued.
+    Stop,
+}
+
+pub struct Player<M: RawMutex, const Q: usize> {
+    queue: Channel<M, Tune, Q>,
+    control: Channel<M, Control, 4>,
+}
+
+impl<M: RawMutex, const Q: usize> Player<M, Q> {
+    pub const fn new() -> Self {
+        Self {
+            queue: Channel::new(),
+            control: Channel::new(),
+        }
+    }
+
+    /// Queues a tune, waiting while the queue is full.
+    pub async fn play(&self, tune: Tune) {
+        self.queue.send(tune).await;
+    }
+
+    /// Queues a tune, or hands it back if the queue is full.
+    pub fn try_play(&self, tune: Tune) -> Result<(), Tune> {
+        self.queue.try_send(tune).map_err(|e| match e {
+            embassy_sync::channel::TrySendError::Full(tune) => tune,
+        })
+    }
+
+    pub async fn control(&self, control: Control) {
+        self.control.send(control).await;
+    }
+
+    /// Plays queued tunes on `output` forever. Pause and resume only apply while a tune is playing.
+    pub async fn run(&self, output: &mut impl Tone) -> ! {
+        loop {
+            match select(self.queue.receive(), self.control.receive()).await {
+                Either::First(tune) => {
+                    if let Some(Control::Stop) = self.play_tune(output, tune).await {
+                        self.queue.clear();
+                    }
+                }
+                Either::Second(Control::Stop) => self.queue.clear(),
+                Either::Second(_) => {}
+            }
+        }
+    }
+
+    // Returns the control that ended the tune early, if any
+    async fn play_tune(&self, output: &mut impl Tone, tune: Tune) -> Option<Control> {
+        let song = Song::new(tune.tempo());
+        // Start each note on the schedule rather than after the previous one, so delays do not add up
+        let mut next_start = Instant::now();
+        for (freq, divider) in tune.notes() {
+            let duration = song.calc_note_duration(divider) as u64;
+            let pause = duration / 10; // 10% of note_duration
+            for (sounding, ms) in [(freq != REST, duration - pause), (false, pause)] {
+                let mut end = next_start + Duration::from_millis(ms);
+                if let Some(control) = self
+                    .sound_until(output, sounding.then_some(freq), &mut end)
+                    .await
+                {
+                    output.no_tone();
+                    return Some(control);
+                }
+                next_start = end;
+            }
+        }
+        output.no_tone();
+        None
+    }
+
+    async fn sound_until(
+        &self,
+        output: &mut impl Tone,
+        freq: Option<f64>,
+        end: &mut Instant,
+    ) -> Option<Control> {
+        loop {
+            match freq {
+                Some(freq) => output.tone(freq as u32),
+                None => output.no_tone(),
+            }
+            let control = match select(Timer::at(*end), self.control.receive()).await {
+                Either::First(()) => return None,
+                Either::Second(control) => control,
+            };
+            match control {
+                Control::Pause => {
+                    output.no_tone();
+                    let paused_at = Instant::now();
+                    loop {
+                        match self.control.receive().await {
+                            Control::Resume => break,
+                            Control::Pause => {}
+                            other => return Some(other),
+                        }
+                    }
+                    // Finish the rest of the note after resuming, later notes shift along
+                    *end += paused_at.elapsed();
+                }
+                Control::Resume => {}
+                other => return Some(other),
+            }
+        }
+    }
+}
+
+impl<M: RawMutex, const Q: usize> Default for Player<M, Q> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,395 @@
+//! RTTTL (Nokia ringtone) parser, e.g. `"Beep:d=8,o=5,b=120:c6,p,c6"`.
+//!
+//! Everything is `const fn`, so the same code parses strings at runtime and
+//! inside [`rtttl!`](crate::rtttl) at compile time. Notes come out in the same
+//! `(frequency, divider)` form as the hand-written `MELODY` tables.
+
+use super::{Melody, REST};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RtttlError {
+    /// Not of the form `name:settings:notes`.
+    MissingSection,
+    /// Unknown key or out-of-range value in the `d=..,o=..,b=..` section.
+    InvalidSetting,
+    /// Byte offset in the string where a note could not be parsed.
+    InvalidNote(usize),
+}
+
+// Equal temperament, octave 4
+const OCTAVE_4: [f64; 12] = [
+    261.6256, 277.1826, 293.6648, 311.1270, 329.6276, 349.2282, 369.9944, 391.9954, 415.3047,
+    440.0, 466.1638, 493.8833,
+];
+
+/// Frequency rounded to whole Hz like the `NOTE_*` constants.
+const fn frequency(octave: u16, semitone: u16) -> f64 {
+    let n = octave * 12 + semitone;
+    let mut freq = OCTAVE_4[(n % 12) as usize];
+    let mut octave = n / 12;
+    while octave > 4 {
+        freq *= 2.0;
+        octave -= 1;
+    }
+    while octave < 4 {
+        freq /= 2.0;
+        octave += 1;
+    }
+    ((freq + 0.5) as u32) as f64
+}
+
+const fn is_valid_duration(d: u16) -> bool {
+    matches!(d, 1 | 2 | 4 | 8 | 16 | 32)
+}
+
+const fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+const fn parse_number(bytes: &[u8], mut i: usize) -> (Option<u16>, usize) {
+    let mut value: Option<u16> = None;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        let digit = (bytes[i] - b'0') as u16;
+        value = match value {
+            Some(v) if v >= 1000 => Some(v),
+            Some(v) => Some(v * 10 + digit),
+            None => Some(digit),
+        };
+        i += 1;
+    }
+    (value, i)
+}
+
+const fn find(bytes: &[u8], from: usize, byte: u8) -> Option<usize> {
+    let mut i = from;
+    while i < bytes.len() {
+        if bytes[i] == byte {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// A validated RTTTL string, notes are decoded lazily.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rtttl<'a> {
+    source: &'a str,
+    name_end: usize,
+    notes_start: usize,
+    duration: u16,
+    octave: u16,
+    tempo: u16,
+}
+
+impl<'a> Rtttl<'a> {
+    pub const fn parse(source: &'a str) -> Result<Self, RtttlError> {
+        let bytes = source.as_bytes();
+        let name_end = match find(bytes, 0, b':') {
+            Some(i) => i,
+            None => return Err(RtttlError::MissingSection),
+        };
+        let settings_end = match find(bytes, name_end + 1, b':') {
+            Some(i) => i,
+            None => return Err(RtttlError::MissingSection),
+        };
+
+        // Defaults from the RTTTL spec
+        let (mut duration, mut octave, mut tempo) = (4, 6, 63);
+        let mut i = skip_whitespace(bytes, name_end + 1);
+        while i < settings_end {
+            let key = bytes[i];
+            i = skip_whitespace(bytes, i + 1);
+            if i >= settings_end || bytes[i] != b'=' {
+                return Err(RtttlError::InvalidSetting);
+            }
+            let (value, next) = parse_number(bytes, skip_whitespace(bytes, i + 1));
+            let value = match value {
+                Some(v) => v,
+                None => return Err(RtttlError::InvalidSetting),
+            };
+            match key {
+                b'd' | b'D' if is_valid_duration(value) => duration = value,
+                b'o' | b'O' if value <= 8 => octave = value,
+                b'b' | b'B' if value > 0 => tempo = value,
+                _ => return Err(RtttlError::InvalidSetting),
+            }
+            i = skip_whitespace(bytes, next);
+            if i < settings_end {
+                if bytes[i] != b',' {
+                    return Err(RtttlError::InvalidSetting);
+                }
+                i = skip_whitespace(bytes, i + 1);
+            }
+        }
+
+        let rtttl = Self {
+            source,
+            name_end,
+            notes_start: settings_end + 1,
+            duration,
+            octave,
+            tempo,
+        };
+        // Check every note up front so iterating can not fail
+        let mut pos = rtttl.first_note();
+        while pos < bytes.len() {
+            match rtttl.note_at(pos) {
+                Ok((_, next)) => pos = next,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(rtttl)
+    }
+
+    /// Like [`Self::parse`], but fails the build when used in a `const`.
+    pub const fn parse_const(source: &'a str) -> Self {
+        match Self::parse(source) {
+            Ok(rtttl) => rtttl,
+            Err(_) => panic!("invalid RTTTL string"),
+        }
+    }
+
+    pub const fn name(&self) -> &'a str {
+        // A char boundary, the split is right before an ASCII ':'
+        self.source.split_at(self.name_end).0
+    }
+
+    /// Beats (quarter notes) per minute, as taken by [`super::Song::new`].
+    pub const fn tempo(&self) -> u16 {
+        self.tempo
+    }
+
+    pub const fn len(&self) -> usize {
+        let mut count = 0;
+        let mut pos = self.first_note();
+        while pos < self.source.len() {
+            pos = match self.note_at(pos) {
+                Ok((_, next)) => next,
+                Err(_) => unreachable!(),
+            };
+            count += 1;
+        }
+        count
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn notes(&self) -> Notes<'a> {
+        Notes {
+            rtttl: *self,
+            pos: self.first_note(),
+        }
+    }
+
+    /// All notes as an array, `N` has to be [`Self::len`].
+    pub const fn to_array<const N: usize>(&self) -> [(f64, i16); N] {
+        let mut notes = [(REST, 0); N];
+        let mut pos = self.first_note();
+        let mut i = 0;
+        while i < N {
+            let (note, next) = match self.note_at(pos) {
+                Ok(note) => note,
+                Err(_) => panic!("RTTTL has fewer notes than the array"),
+            };
+            notes[i] = note;
+            pos = next;
+            i += 1;
+        }
+        notes
+    }
+
+    /// Plays the tune through [`super::Song`] and the player like a `MELODY` table.
+    pub const fn as_melody(&self, notes: &'a [(f64, i16)]) -> Melody<'a> {
+        Melody {
+            name: self.name(),
+            tempo: self.tempo,
+            notes,
+        }
+    }
+
+    const fn first_note(&self) -> usize {
+        skip_whitespace(self.source.as_bytes(), self.notes_start)
+    }
+
+    // Decodes `[duration]note[#][.][octave][.]` at `i`, returns the note and where the next starts
+    const fn note_at(&self, start: usize) -> Result<((f64, i16), usize), RtttlError> {
+        let bytes = self.source.as_bytes();
+        let (duration, mut i) = parse_number(bytes, start);
+        let duration = match duration {
+            None => self.duration,
+            Some(d) if is_valid_duration(d) => d,
+            Some(_) => return Err(RtttlError::InvalidNote(start)),
+        };
+        if i >= bytes.len() {
+            return Err(RtttlError::InvalidNote(start));
+        }
+        let semitone = match bytes[i].to_ascii_lowercase() {
+            b'c' => Some(0),
+            b'd' => Some(2),
+            b'e' => Some(4),
+            b'f' => Some(5),
+            b'g' => Some(7),
+            b'a' => Some(9),
+            b'b' | b'h' => Some(11),
+            b'p' => None,
+            _ => return Err(RtttlError::InvalidNote(start)),
+        };
+        i += 1;
+        let mut sharp = 0;
+        if i < bytes.len() && bytes[i] == b'#' {
+            sharp = 1;
+            i += 1;
+        }
+        // The dot may come before or after the octave
+        let mut dotted = false;
+        if i < bytes.len() && bytes[i] == b'.' {
+            dotted = true;
+            i += 1;
+        }
+        let (octave, next) = parse_number(bytes, i);
+        i = next;
+        let octave = match octave {
+            None => self.octave,
+            Some(o) if o <= 8 => o,
+            Some(_) => return Err(RtttlError::InvalidNote(start)),
+        };
+        if i < bytes.len() && bytes[i] == b'.' {
+            dotted = true;
+            i += 1;
+        }
+
+        i = skip_whitespace(bytes, i);
+        if i < bytes.len() {
+            if bytes[i] != b',' {
+                return Err(RtttlError::InvalidNote(start));
+            }
+            i = skip_whitespace(bytes, i + 1);
+            if i >= bytes.len() {
+                // Trailing comma
+                return Err(RtttlError::InvalidNote(start));
+            }
+        }
+
+        let freq = match semitone {
+            Some(s) => frequency(octave, s + sharp),
+            None => REST,
+        };
+        let divider = if dotted {
+            -(duration as i16)
+        } else {
+            duration as i16
+        };
+        Ok(((freq, divider), i))
+    }
+}
+
+/// Iterator over the `(frequency, divider)` notes of an [`Rtttl`].
+#[derive(Debug, Clone)]
+pub struct Notes<'a> {
+    rtttl: Rtttl<'a>,
+    pos: usize,
+}
+
+impl Iterator for Notes<'_> {
+    type Item = (f64, i16);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.rtttl.source.len() {
+            return None;
+        }
+        let (note, next) = self.rtttl.note_at(self.pos).ok()?;
+        self.pos = next;
+        Some(note)
+    }
+}
+
+/// Parses an RTTTL string at compile time into a [`Melody`](crate::music::Melody).
+///
+/// ```
+/// const BEEP: esp32_kit::music::Melody = esp32_kit::rtttl!("Beep:d=8,o=6,b=120:c,p,c");
+/// assert_eq!(BEEP.notes.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! rtttl {
+    ($rtttl:expr) => {{
+        const RTTTL: $crate::music::rtttl::Rtttl<'static> =
+            $crate::music::rtttl::Rtttl::parse_const($rtttl);
+        const NOTES: [(f64, i16); RTTTL.len()] = RTTTL.to_array();
+        RTTTL.as_melody(&NOTES)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::music::*;
+
+    const THE_SIMPSONS: &str = "The Simpsons:d=4,o=5,b=160:c.6,e6,f#6,8a6,g.6,e6,c6,8a,8f#,8f#,8f#,2g,8p,8p,8f#,8f#,8f#,8g,a#.,8c6,8c6,8c6,c6";
+
+    #[test]
+    fn frequencies_match_note_constants() {
+        assert_eq!(frequency(4, 9), NOTE_A4);
+        assert_eq!(frequency(1, 1), NOTE_CS1);
+        assert_eq!(frequency(3, 5), NOTE_F3);
+        assert_eq!(frequency(5, 8), NOTE_GS5);
+        assert_eq!(frequency(6, 4), NOTE_E6);
+        assert_eq!(frequency(7, 11), NOTE_B7);
+        assert_eq!(frequency(7, 12), NOTE_C8);
+        assert_eq!(frequency(8, 3), NOTE_DS8);
+    }
+
+    #[test]
+    fn parses_header_and_notes() {
+        let rtttl = Rtttl::parse(THE_SIMPSONS).unwrap();
+        assert_eq!(rtttl.name(), "The Simpsons");
+        assert_eq!(rtttl.tempo(), 160);
+        assert_eq!(rtttl.len(), 23);
+
+        let notes: Vec<_> = rtttl.notes().collect();
+        assert_eq!(notes.len(), 23);
+        assert_eq!(notes[0], (NOTE_C6, -4));
+        assert_eq!(notes[2], (NOTE_FS6, 4));
+        assert_eq!(notes[3], (NOTE_A6, 8));
+        assert_eq!(notes[7], (NOTE_A5, 8));
+        assert_eq!(notes[11], (NOTE_G5, 2));
+        assert_eq!(notes[12], (REST, 8));
+        assert_eq!(notes[18], (NOTE_AS5, -4));
+    }
+
+    #[test]
+    fn defaults_whitespace_and_dot_positions() {
+        let rtttl = Rtttl::parse("x: : c, 8e.5 ,16g5., h").unwrap();
+        assert_eq!(rtttl.tempo(), 63);
+        let notes: Vec<_> = rtttl.notes().collect();
+        assert_eq!(
+            notes,
+            [(NOTE_C6, 4), (NOTE_E5, -8), (NOTE_G5, -16), (NOTE_B6, 4)]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_strings() {
+        assert_eq!(Rtttl::parse("no sections"), Err(RtttlError::MissingSection));
+        assert_eq!(Rtttl::parse("x:d=3:c"), Err(RtttlError::InvalidSetting));
+        assert_eq!(Rtttl::parse("x:q=3:c"), Err(RtttlError::InvalidSetting));
+        assert_eq!(Rtttl::parse("x::c,x,e"), Err(RtttlError::InvalidNote(5)));
+        assert_eq!(Rtttl::parse("x::c,3e"), Err(RtttlError::InvalidNote(5)));
+        assert_eq!(Rtttl::parse("x::c,e9"), Err(RtttlError::InvalidNote(5)));
+        assert_eq!(Rtttl::parse("x::c,"), Err(RtttlError::InvalidNote(3)));
+    }
+
+    #[test]
+    fn compile_time_matches_runtime() {
+        const SIMPSONS: Melody = crate::rtttl!(THE_SIMPSONS);
+        assert_eq!(SIMPSONS.name, "The Simpsons");
+        assert_eq!(SIMPSONS.tempo, 160);
+        let runtime: Vec<_> = Rtttl::parse(THE_SIMPSONS).unwrap().notes().collect();
+        assert_eq!(SIMPSONS.notes, &runtime[..]);
+    }
+}
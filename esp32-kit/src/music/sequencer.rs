@@ -0,0 +1,245 @@
+//! Plays several `MELODY`-style voices together against one tempo clock.
+//!
+//! Every note start is computed from its absolute position in the piece, not
+//! by adding up rounded note lengths, so the voices never drift apart.
+
+use super::REST;
+
+// Divisible by every power of two up to 64 and by 3, 5 and 7, so dotted notes and tuplets land on whole ticks
+const TICKS_PER_WHOLE: u64 = 40_320;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Play(f64),
+    Silence,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Event {
+    /// Time since the start of the piece.
+    pub at_us: u64,
+    pub voice: usize,
+    pub action: Action,
+}
+
+/// Length of a `Song` note value in sequencer ticks, negative dividers are dotted.
+fn ticks(divider: i16) -> u64 {
+    let ticks = TICKS_PER_WHOLE / divider.unsigned_abs().max(1) as u64;
+    if divider < 0 {
+        ticks * 3 / 2
+    } else {
+        ticks
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Voice<'a> {
+    notes: &'a [(f64, i16)],
+    next: usize,
+    // Start of `notes[next]`
+    tick: u64,
+    silence_at_us: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Sequencer<'a, const V: usize> {
+    voices: [Voice<'a>; V],
+    tempo: u16,
+}
+
+impl<'a, const V: usize> Sequencer<'a, V> {
+    /// `tempo` in quarter notes per minute, as for [`super::Song::new`].
+    pub fn new(tempo: u16, voices: [&'a [(f64, i16)]; V]) -> Self {
+        Self {
+            voices: voices.map(|notes| Voice {
+                notes,
+                next: 0,
+                tick: 0,
+                silence_at_us: None,
+            }),
+            tempo: tempo.max(1),
+        }
+    }
+
+    fn tick_to_us(&self, tick: u64) -> u64 {
+        // A whole note is 4 beats of 60s / tempo
+        tick * 240_000_000 / (self.tempo as u64 * TICKS_PER_WHOLE)
+    }
+
+    /// Length of the longest voice.
+    pub fn duration_us(&self) -> u64 {
+        let longest = self
+            .voices
+            .iter()
+            .map(|voice| voice.notes.iter().map(|&(_, d)| ticks(d)).sum::<u64>())
+            .max()
+            .unwrap_or(0);
+        self.tick_to_us(longest)
+    }
+
+    /// Rewinds to the start of the piece.
+    pub fn reset(&mut self) {
+        for voice in &mut self.voices {
+            voice.next = 0;
+            voice.tick = 0;
+            voice.silence_at_us = None;
+        }
+    }
+
+    // Next event of one voice without consuming it
+    fn peek(&self, index: usize) -> Option<Event> {
+        let voice = &self.voices[index];
+        if let Some(at_us) = voice.silence_at_us {
+            return Some(Event {
+                at_us,
+                voice: index,
+                action: Action::Silence,
+            });
+        }
+        let mut tick = voice.tick;
+        for &(freq, divider) in &voice.notes[voice.next..] {
+            if freq != REST {
+                return Some(Event {
+                    at_us: self.tick_to_us(tick),
+                    voice: index,
+                    action: Action::Play(freq),
+                });
+            }
+            tick += ticks(divider);
+        }
+        None
+    }
+
+    fn advance(&mut self, event: &Event) {
+        let index = event.voice;
+        if let Action::Silence = event.action {
+            self.voices[index].silence_at_us = None;
+            return;
+        }
+        // Skip the rests in front of the note that was just started
+        while self.voices[index].notes[self.voices[index].next].0 == REST {
+            let voice = &mut self.voices[index];
+            voice.tick += ticks(voice.notes[voice.next].1);
+            voice.next += 1;
+        }
+        let voice = self.voices[index];
+        let end_tick = voice.tick + ticks(voice.notes[voice.next].1);
+        let (start_us, end_us) = (self.tick_to_us(voice.tick), self.tick_to_us(end_tick));
+        let voice = &mut self.voices[index];
+        // Sound for 90% of the note so repeated notes stay distinct
+        voice.silence_at_us = Some(end_us - (end_us - start_us) / 10);
+        voice.tick = end_tick;
+        voice.next += 1;
+    }
+}
+
+impl<const V: usize> Iterator for Sequencer<'_, V> {
+    type Item = Event;
+
+    /// Events of all voices in time order, ties go to the lower voice.
+    fn next(&mut self) -> Option<Event> {
+        let event = (0..V)
+            .filter_map(|voice| self.peek(voice))
+            .min_by_key(|event| (event.at_us, event.voice))?;
+        self.advance(&event);
+        Some(event)
+    }
+}
+
+#[cfg(feature = "embassy")]
+mod play {
+    use embassy_time::{Duration, Instant, Timer};
+
+    use super::{Action, Sequencer};
+    use crate::music::player::Tone;
+
+    impl<const V: usize> Sequencer<'_, V> {
+        /// Plays the piece from the start, voice `i` on `outputs[i]`.
+        pub async fn play<T: Tone>(&mut self, outputs: &mut [T; V]) {
+            self.reset();
+            let start = Instant::now();
+            for event in self.by_ref() {
+                // Waiting for absolute times keeps late wake-ups from adding up
+                Timer::at(start + Duration::from_micros(event.at_us)).await;
+                match event.action {
+                    Action::Play(freq) => outputs[event.voice].tone(freq as u32),
+                    Action::Silence => outputs[event.voice].no_tone(),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::music::*;
+
+    #[test]
+    fn note_values_are_exact_ticks() {
+        assert_eq!(ticks(1), TICKS_PER_WHOLE);
+        assert_eq!(ticks(-4), TICKS_PER_WHOLE * 3 / 8);
+        assert_eq!(ticks(-64) * 64, TICKS_PER_WHOLE * 3 / 2);
+        // Triplets
+        assert_eq!(ticks(12) * 3, ticks(4));
+    }
+
+    #[test]
+    fn events_are_merged_in_time_order() {
+        let melody = [(NOTE_E5, 4), (NOTE_D5, 4)];
+        let bass = [(NOTE_C3, 2)];
+        // 120 bpm: quarter note = 500 ms
+        let events: Vec<_> = Sequencer::new(120, [&melody[..], &bass[..]]).collect();
+        let expected = [
+            (0, 0, Action::Play(NOTE_E5)),
+            (0, 1, Action::Play(NOTE_C3)),
+            (450_000, 0, Action::Silence),
+            (500_000, 0, Action::Play(NOTE_D5)),
+            (900_000, 1, Action::Silence),
+            (950_000, 0, Action::Silence),
+        ];
+        let actual: Vec<_> = events
+            .iter()
+            .map(|e| (e.at_us, e.voice, e.action))
+            .collect();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rests_delay_the_voice() {
+        let voice = [(REST, 8), (REST, -4), (NOTE_A4, 8)];
+        let events: Vec<_> = Sequencer::new(60, [&voice[..]]).collect();
+        // Eighth + dotted quarter = 2 beats at 60 bpm
+        assert_eq!(events[0].at_us, 2_000_000);
+        assert_eq!(events[0].action, Action::Play(NOTE_A4));
+        assert_eq!(events.len(), 2);
+    }
+
+    #[test]
+    fn voices_do_not_drift() {
+        // At 140 bpm a sixteenth is 107.14 ms, summing rounded lengths would lose 0.14 ms per note
+        let sixteenths = [(NOTE_C5, 16); 256];
+        let wholes = [(NOTE_C3, 1); 16];
+        let mut sequencer = Sequencer::new(140, [&sixteenths[..], &wholes[..]]);
+        let plays: Vec<_> = sequencer
+            .by_ref()
+            .filter(|e| matches!(e.action, Action::Play(_)))
+            .collect();
+        for whole in plays.iter().filter(|e| e.voice == 1) {
+            assert!(plays.iter().any(|e| e.voice == 0 && e.at_us == whole.at_us));
+        }
+        let last = plays.iter().rfind(|e| e.voice == 0).unwrap();
+        assert_eq!(last.at_us, 255 * 240_000_000 / (140 * 16));
+        assert_eq!(sequencer.duration_us(), 16 * 240_000_000 / 140);
+    }
+
+    #[test]
+    fn reset_starts_over() {
+        let voice = [(NOTE_A4, 4)];
+        let mut sequencer = Sequencer::new(120, [&voice[..]]);
+        assert_eq!(sequencer.by_ref().count(), 2);
+        assert_eq!(sequencer.next(), None);
+        sequencer.reset();
+        assert_eq!(sequencer.count(), 2);
+    }
+}
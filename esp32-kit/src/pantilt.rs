@@ -0,0 +1,297 @@
+//! Pan/tilt head steered by a joystick.
+//!
+//! In rate mode the stick sets how fast the head turns and it stays put when
+//! the stick is let go. In absolute mode the stick position is the head
+//! position. A short button press recenters, a long press switches modes.
+//! Time is passed in as milliseconds, like [`crate::motion`].
+
+use crate::joystick::{self, Calibration};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisConfig {
+    /// Fraction of the stick travel around the centre that is ignored.
+    pub deadzone: f32,
+    /// 0 is linear, 1 fully cubic, see [`joystick::expo`].
+    pub expo: f32,
+    pub inverted: bool,
+    pub min_angle: f32,
+    pub max_angle: f32,
+    /// Angle at rest and after recentering.
+    pub home: f32,
+}
+
+impl Default for AxisConfig {
+    fn default() -> Self {
+        Self {
+            deadzone: 0.08,
+            expo: 0.4,
+            inverted: false,
+            min_angle: 0.0,
+            max_angle: 180.0,
+            home: 90.0,
+        }
+    }
+}
+
+impl AxisConfig {
+    // Stick reading shaped into -1..=1
+    fn shape(&self, value: f32) -> f32 {
+        let value = joystick::expo(joystick::deadzone(value, self.deadzone), self.expo);
+        if self.inverted {
+            -value
+        } else {
+            value
+        }
+    }
+
+    // Absolute mode: full deflection reaches the end of travel on either side of home
+    fn angle_for(&self, value: f32) -> f32 {
+        if value >= 0.0 {
+            self.home + value * (self.max_angle - self.home)
+        } else {
+            self.home + value * (self.home - self.min_angle)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PanTiltConfig {
+    pub pan: AxisConfig,
+    pub tilt: AxisConfig,
+    /// °/s at full deflection in rate mode, and the slew limit in absolute mode.
+    pub max_rate: f32,
+    /// Holding the button at least this long switches modes instead of recentering.
+    pub long_press_ms: u64,
+}
+
+impl Default for PanTiltConfig {
+    fn default() -> Self {
+        Self {
+            pan: AxisConfig::default(),
+            tilt: AxisConfig::default(),
+            max_rate: 120.0,
+            long_press_ms: 800,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Rate,
+    Absolute,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Recentered,
+    ModeChanged(Mode),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PanTilt {
+    config: PanTiltConfig,
+    calibration: Calibration,
+    mode: Mode,
+    angles: [f32; 2],
+    last_ms: Option<u64>,
+    pressed_at: Option<u64>,
+    // The long press already switched modes, the release must not recenter
+    long_press_done: bool,
+}
+
+impl PanTilt {
+    pub fn new(config: PanTiltConfig, calibration: Calibration) -> Self {
+        Self {
+            config,
+            calibration,
+            mode: Mode::Rate,
+            angles: [config.pan.home, config.tilt.home],
+            last_ms: None,
+            pressed_at: None,
+            long_press_done: false,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// `[pan, tilt]` in degrees.
+    pub fn angles(&self) -> [f32; 2] {
+        self.angles
+    }
+
+    pub fn set_calibration(&mut self, calibration: Calibration) {
+        self.calibration = calibration;
+    }
+
+    /// Steps the head with the stick reading `[x, y]` in corrected ADC LSB, returns the new angles.
+    pub fn update(&mut self, now_ms: u64, raw: [f64; 2]) -> [f32; 2] {
+        let dt = self
+            .last_ms
+            .map_or(0.0, |last| now_ms.saturating_sub(last) as f32 / 1000.0);
+        self.last_ms = Some(now_ms);
+        let max_step = self.config.max_rate * dt;
+
+        let stick = [
+            self.calibration.x.normalize(raw[0]),
+            self.calibration.y.normalize(raw[1]),
+        ];
+        let axes = [self.config.pan, self.config.tilt];
+        for ((angle, axis), value) in self.angles.iter_mut().zip(axes).zip(stick) {
+            let value = axis.shape(value);
+            let target = match self.mode {
+                Mode::Rate => *angle + value * max_step,
+                Mode::Absolute => {
+                    let target = axis.angle_for(value);
+                    *angle + (target - *angle).clamp(-max_step, max_step)
+                }
+            };
+            *angle = target.clamp(axis.min_angle, axis.max_angle);
+        }
+        self.angles
+    }
+
+    /// Feeds the (debounced) button level.
+    pub fn button(&mut self, now_ms: u64, pressed: bool) -> Option<Action> {
+        match (self.pressed_at, pressed) {
+            (None, true) => {
+                self.pressed_at = Some(now_ms);
+                self.long_press_done = false;
+                None
+            }
+            (Some(since), true) => {
+                let long = now_ms.saturating_sub(since) >= self.config.long_press_ms;
+                if !long || self.long_press_done {
+                    return None;
+                }
+                self.long_press_done = true;
+                Some(self.toggle_mode())
+            }
+            (Some(_), false) => {
+                self.pressed_at = None;
+                if self.long_press_done {
+                    return None;
+                }
+                self.recenter();
+                Some(Action::Recentered)
+            }
+            (None, false) => None,
+        }
+    }
+
+    /// Rate mode turns back to home. In absolute mode the head follows the stick anyway.
+    pub fn recenter(&mut self) {
+        if self.mode == Mode::Rate {
+            self.angles = [self.config.pan.home, self.config.tilt.home];
+        }
+    }
+
+    pub fn toggle_mode(&mut self) -> Action {
+        self.mode = match self.mode {
+            Mode::Rate => Mode::Absolute,
+            Mode::Absolute => Mode::Rate,
+        };
+        Action::ModeChanged(self.mode)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CENTER: [f64; 2] = [2048.0, 2048.0];
+
+    fn linear() -> PanTiltConfig {
+        let axis = AxisConfig {
+            deadzone: 0.0,
+            expo: 0.0,
+            ..AxisConfig::default()
+        };
+        PanTiltConfig {
+            pan: axis,
+            tilt: axis,
+            ..PanTiltConfig::default()
+        }
+    }
+
+    #[test]
+    fn rate_mode_integrates_and_holds() {
+        let mut head = PanTilt::new(linear(), Calibration::default());
+        assert_eq!(head.update(0, CENTER), [90.0, 90.0]);
+        // Full right for half a second at 120°/s
+        for ms in (20..=500).step_by(20) {
+            head.update(ms, [4095.0, 2048.0]);
+        }
+        assert!((head.angles()[0] - 150.0).abs() < 0.01);
+        assert_eq!(head.angles()[1], 90.0);
+        // Letting go keeps the position
+        assert_eq!(head.update(520, CENTER)[0], head.angles()[0]);
+        // and the end of travel is never passed
+        for ms in (540..=3000).step_by(20) {
+            head.update(ms, [4095.0, 0.0]);
+        }
+        assert_eq!(head.angles(), [180.0, 0.0]);
+    }
+
+    #[test]
+    fn absolute_mode_follows_the_stick() {
+        let config = PanTiltConfig {
+            pan: AxisConfig {
+                inverted: true,
+                ..linear().pan
+            },
+            ..linear()
+        };
+        let mut head = PanTilt::new(config, Calibration::default());
+        head.toggle_mode();
+        head.update(0, CENTER);
+        // Slewing towards 0° (pan is inverted), at most 2.4° per 20 ms
+        assert!((head.update(20, [4095.0, 3071.5])[0] - 87.6).abs() < 0.01);
+        for ms in (40..=2000).step_by(20) {
+            head.update(ms, [4095.0, 3071.5]);
+        }
+        let [pan, tilt] = head.angles();
+        assert_eq!(pan, 0.0);
+        assert!((tilt - 135.0).abs() < 0.1);
+        // Back to the centre of the stick is back home
+        for ms in (2020..=4000).step_by(20) {
+            head.update(ms, CENTER);
+        }
+        assert_eq!(head.angles(), [90.0, 90.0]);
+    }
+
+    #[test]
+    fn deadzone_and_expo_soften_the_centre() {
+        let mut head = PanTilt::new(PanTiltConfig::default(), Calibration::default());
+        head.update(0, CENTER);
+        // A little off centre is inside the deadzone
+        head.update(1000, [2150.0, 1950.0]);
+        assert_eq!(head.angles(), [90.0, 90.0]);
+        // Half deflection for a second turns less than half of 120° with expo
+        head.update(2000, [3071.5, 2048.0]);
+        let turned = head.angles()[0] - 90.0;
+        assert!(turned > 30.0 && turned < 40.0);
+    }
+
+    #[test]
+    fn button_recenters_or_switches_mode() {
+        let mut head = PanTilt::new(linear(), Calibration::default());
+        head.update(0, CENTER);
+        head.update(500, [4095.0, 4095.0]);
+        assert_eq!(head.button(1000, true), None);
+        assert_eq!(head.button(1200, false), Some(Action::Recentered));
+        assert_eq!(head.angles(), [90.0, 90.0]);
+
+        assert_eq!(head.button(2000, true), None);
+        assert_eq!(head.button(2500, true), None);
+        assert_eq!(
+            head.button(2800, true),
+            Some(Action::ModeChanged(Mode::Absolute))
+        );
+        // Held on, nothing more happens, and the release does not recenter
+        assert_eq!(head.button(4000, true), None);
+        assert_eq!(head.button(4100, false), None);
+        assert_eq!(head.mode(), Mode::Absolute);
+    }
+}
@@ -0,0 +1,253 @@
+//! Parking-sensor style alarm: the closer the object, the faster the beeps.
+//!
+//! Timestamps are plain milliseconds from any monotonic clock, so recorded
+//! distance traces can be replayed on the host.
+
+use crate::hcsr04::Distance;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Zone {
+    Far,
+    Near,
+    Close,
+    Critical,
+}
+
+/// Buzzer on/off times for one zone, `off_ms == 0` means a continuous tone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cadence {
+    pub on_ms: u64,
+    pub off_ms: u64,
+}
+
+impl Cadence {
+    pub const SILENT: Self = Self {
+        on_ms: 0,
+        off_ms: 1,
+    };
+    pub const CONTINUOUS: Self = Self {
+        on_ms: 1,
+        off_ms: 0,
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProximityConfig {
+    /// Upper bounds of the near, close and critical zones in cm.
+    pub near_cm: f64,
+    pub close_cm: f64,
+    pub critical_cm: f64,
+    /// How far past a boundary an object has to move back before the alarm calms down.
+    pub hysteresis_cm: f64,
+    /// Cadence for near, close and critical; far is always silent.
+    pub near: Cadence,
+    pub close: Cadence,
+    pub critical: Cadence,
+}
+
+impl Default for ProximityConfig {
+    fn default() -> Self {
+        Self {
+            near_cm: 100.0,
+            close_cm: 50.0,
+            critical_cm: 20.0,
+            hysteresis_cm: 3.0,
+            near: Cadence {
+                on_ms: 100,
+                off_ms: 700,
+            },
+            close: Cadence {
+                on_ms: 100,
+                off_ms: 200,
+            },
+            critical: Cadence::CONTINUOUS,
+        }
+    }
+}
+
+impl ProximityConfig {
+    fn zone_for(&self, cm: f64) -> Zone {
+        if cm < self.critical_cm {
+            Zone::Critical
+        } else if cm < self.close_cm {
+            Zone::Close
+        } else if cm < self.near_cm {
+            Zone::Near
+        } else {
+            Zone::Far
+        }
+    }
+
+    fn cadence(&self, zone: Zone) -> Cadence {
+        match zone {
+            Zone::Far => Cadence::SILENT,
+            Zone::Near => self.near,
+            Zone::Close => self.close,
+            Zone::Critical => self.critical,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProximityAlarm {
+    config: ProximityConfig,
+    zone: Zone,
+    // Start of the current on/off cycle
+    cycle_start_ms: u64,
+}
+
+impl ProximityAlarm {
+    pub fn new(config: ProximityConfig) -> Self {
+        Self {
+            config,
+            zone: Zone::Far,
+            cycle_start_ms: 0,
+        }
+    }
+
+    pub fn zone(&self) -> Zone {
+        self.zone
+    }
+
+    /// Feeds a new measurement; `None` (no echo) counts as nothing in range.
+    pub fn update(&mut self, now_ms: u64, distance: Option<Distance>) -> Zone {
+        let zone = match distance {
+            Some(d) => {
+                let closer = self.config.zone_for(d.cm());
+                if closer >= self.zone {
+                    closer
+                } else {
+                    // Only back off once clear of the boundary by the hysteresis
+                    self.config
+                        .zone_for(d.cm() - self.config.hysteresis_cm)
+                        .max(closer)
+                        .min(self.zone)
+                }
+            }
+            None => Zone::Far,
+        };
+        if zone != self.zone {
+            self.zone = zone;
+            // Start the new cadence with a beep right away
+            self.cycle_start_ms = now_ms;
+        }
+        self.zone
+    }
+
+    /// Whether the buzzer should sound at `now_ms`.
+    pub fn buzzer(&self, now_ms: u64) -> bool {
+        let Cadence { on_ms, off_ms } = self.config.cadence(self.zone);
+        if off_ms == 0 {
+            return true;
+        }
+        let period = on_ms + off_ms;
+        now_ms.saturating_sub(self.cycle_start_ms) % period < on_ms
+    }
+
+    /// Time until the buzzer state next changes, `None` while it stays constant.
+    pub fn next_change_in(&self, now_ms: u64) -> Option<u64> {
+        let Cadence { on_ms, off_ms } = self.config.cadence(self.zone);
+        if on_ms == 0 || off_ms == 0 {
+            return None;
+        }
+        let phase = now_ms.saturating_sub(self.cycle_start_ms) % (on_ms + off_ms);
+        Some(if phase < on_ms {
+            on_ms - phase
+        } else {
+            on_ms + off_ms - phase
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Car reversing towards a wall, sampled every 60 ms
+    fn replay(alarm: &mut ProximityAlarm, trace: &[(u64, Option<f64>)]) -> Vec<Zone> {
+        trace
+            .iter()
+            .map(|&(t, d)| alarm.update(t, d.map(Distance::from_cm)))
+            .collect()
+    }
+
+    #[test]
+    fn zones_follow_distance() {
+        let mut alarm = ProximityAlarm::new(ProximityConfig::default());
+        let trace = [
+            (0, Some(150.0)),
+            (60, Some(90.0)),
+            (120, Some(40.0)),
+            (180, Some(10.0)),
+            (240, None),
+        ];
+        assert_eq!(
+            replay(&mut alarm, &trace),
+            [
+                Zone::Far,
+                Zone::Near,
+                Zone::Close,
+                Zone::Critical,
+                Zone::Far
+            ]
+        );
+    }
+
+    #[test]
+    fn hysteresis_stops_chatter_at_boundary() {
+        let mut alarm = ProximityAlarm::new(ProximityConfig::default());
+        // Noisy readings parked right at the 50 cm boundary
+        let trace: Vec<_> = [49.0, 51.0, 49.5, 52.0, 50.5, 49.8, 52.9]
+            .iter()
+            .enumerate()
+            .map(|(i, &d)| (i as u64 * 60, Some(d)))
+            .collect();
+        let zones = replay(&mut alarm, &trace);
+        assert!(zones.iter().all(|&z| z == Zone::Close));
+
+        // Clearly backing away releases it
+        assert_eq!(alarm.update(500, Some(Distance::from_cm(53.5))), Zone::Near);
+        // A big jump skips straight past several zones
+        assert_eq!(alarm.update(560, Some(Distance::from_cm(200.0))), Zone::Far);
+    }
+
+    #[test]
+    fn cadence_gets_faster_when_closer() {
+        let config = ProximityConfig::default();
+        let mut alarm = ProximityAlarm::new(config);
+
+        let beeps_per_second = |alarm: &ProximityAlarm, start: u64| {
+            let mut beeps = 0;
+            let mut prev = false;
+            for t in (start..start + 1000).step_by(10) {
+                let on = alarm.buzzer(t);
+                if on && !prev {
+                    beeps += 1;
+                }
+                prev = on;
+            }
+            beeps
+        };
+
+        alarm.update(0, Some(Distance::from_cm(200.0)));
+        assert_eq!(beeps_per_second(&alarm, 0), 0);
+        alarm.update(1000, Some(Distance::from_cm(80.0)));
+        assert_eq!(beeps_per_second(&alarm, 1000), 2);
+        alarm.update(2000, Some(Distance::from_cm(30.0)));
+        assert_eq!(beeps_per_second(&alarm, 2000), 4);
+        alarm.update(3000, Some(Distance::from_cm(10.0)));
+        assert!((3000..4000).all(|t| alarm.buzzer(t)));
+    }
+
+    #[test]
+    fn next_change_matches_schedule() {
+        let mut alarm = ProximityAlarm::new(ProximityConfig::default());
+        assert_eq!(alarm.next_change_in(0), None);
+        alarm.update(1000, Some(Distance::from_cm(30.0)));
+        assert!(alarm.buzzer(1000));
+        assert_eq!(alarm.next_change_in(1000), Some(100));
+        assert!(!alarm.buzzer(1100));
+        assert_eq!(alarm.next_change_in(1150), Some(150));
+        assert!(alarm.buzzer(1300));
+    }
+}
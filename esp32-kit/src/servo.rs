@@ -0,0 +1,260 @@
+//! Hobby servos driven by a 50 Hz PWM signal, from LEDC or MCPWM.
+
+use embedded_hal::pwm::SetDutyCycle;
+
+#[cfg(feature = "embassy")]
+pub mod planner;
+pub mod trajectory;
+
+/// Length of one 50 Hz servo frame.
+pub const PERIOD_US: u32 = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServoError {
+    /// The angle is NaN or infinite.
+    InvalidAngle,
+    /// Empty pulse or angle range, or pulses longer than the PWM period.
+    InvalidCalibration,
+    /// Speed or acceleration limits that are not positive and finite.
+    InvalidLimits,
+    /// The PWM backend refused the duty cycle.
+    Pwm,
+}
+
+/// Per-servo calibration, taken from the datasheet or measured on the horn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Calibration {
+    /// Pulse widths at the ends of travel.
+    pub min_pulse_us: u16,
+    pub max_pulse_us: u16,
+    /// Angles reached at `min_pulse_us` and `max_pulse_us`.
+    pub min_angle: f32,
+    pub max_angle: f32,
+    /// Swaps the direction of travel, for servos mounted mirrored.
+    pub inverted: bool,
+    /// Added to every pulse to center the horn.
+    pub trim_us: i16,
+}
+
+impl Default for Calibration {
+    /// SG90 style servo: 0.5 ms to 2.5 ms for 0° to 180°.
+    fn default() -> Self {
+        Self {
+            min_pulse_us: 500,
+            max_pulse_us: 2500,
+            min_angle: 0.0,
+            max_angle: 180.0,
+            inverted: false,
+            trim_us: 0,
+        }
+    }
+}
+
+impl Calibration {
+    pub fn validate(&self) -> Result<(), ServoError> {
+        let angles_ok = self.min_angle.is_finite()
+            && self.max_angle.is_finite()
+            && self.min_angle < self.max_angle;
+        if !angles_ok
+            || self.min_pulse_us >= self.max_pulse_us
+            || self.max_pulse_us as u32 >= PERIOD_US
+        {
+            return Err(ServoError::InvalidCalibration);
+        }
+        Ok(())
+    }
+
+    /// `deg` clamped to the calibrated range.
+    pub fn clamp_angle(&self, deg: f32) -> Result<f32, ServoError> {
+        if !deg.is_finite() {
+            return Err(ServoError::InvalidAngle);
+        }
+        Ok(deg.clamp(self.min_angle, self.max_angle))
+    }
+
+    /// Pulse width for `deg`, angles outside the range are clamped to its ends.
+    pub fn pulse_from_angle(&self, deg: f32) -> Result<u16, ServoError> {
+        let deg = self.clamp_angle(deg)?;
+        let mut fraction = (deg - self.min_angle) / (self.max_angle - self.min_angle);
+        if self.inverted {
+            fraction = 1.0 - fraction;
+        }
+        let span = (self.max_pulse_us - self.min_pulse_us) as f32;
+        let pulse = self.min_pulse_us as f32 + fraction * span + self.trim_us as f32;
+        Ok(self.clamp_pulse(libm::roundf(pulse) as i32))
+    }
+
+    /// Angle the servo moves to for `pulse_us`, the inverse of [`Self::pulse_from_angle`].
+    pub fn angle_from_pulse(&self, pulse_us: u16) -> f32 {
+        let untrimmed = self.clamp_pulse(pulse_us as i32) as f32 - self.trim_us as f32;
+        let span = (self.max_pulse_us - self.min_pulse_us) as f32;
+        let mut fraction = ((untrimmed - self.min_pulse_us as f32) / span).clamp(0.0, 1.0);
+        if self.inverted {
+            fraction = 1.0 - fraction;
+        }
+        self.min_angle + fraction * (self.max_angle - self.min_angle)
+    }
+
+    /// Keeps pulses inside the calibrated range so the servo never drives into its end stops.
+    pub fn clamp_pulse(&self, pulse_us: i32) -> u16 {
+        pulse_us.clamp(self.min_pulse_us as i32, self.max_pulse_us as i32) as u16
+    }
+}
+
+pub trait Servo {
+    fn calibration(&self) -> &Calibration;
+
+    /// Sets the raw pulse width, clamped to the calibrated range. Returns the pulse actually sent.
+    fn set_pulse_us(&mut self, pulse_us: u16) -> Result<u16, ServoError>;
+
+    fn set_angle(&mut self, deg: f32) -> Result<(), ServoError> {
+        let pulse_us = self.calibration().pulse_from_angle(deg)?;
+        self.set_pulse_us(pulse_us).map(|_| ())
+    }
+}
+
+/// Servo on any 50 Hz PWM output implementing `SetDutyCycle`.
+///
+/// Works with an LEDC channel (m10_servo_motor, 4.9 µs steps at 12 bit) as
+/// well as an MCPWM pin (m11_servo_motor_mcpwm, 1 µs steps with a period of 20_000).
+pub struct PwmServo<P> {
+    pwm: P,
+    calibration: Calibration,
+}
+
+impl<P: SetDutyCycle> PwmServo<P> {
+    pub fn new(pwm: P, calibration: Calibration) -> Result<Self, ServoError> {
+        calibration.validate()?;
+        Ok(Self { pwm, calibration })
+    }
+
+    pub fn release(self) -> P {
+        self.pwm
+    }
+}
+
+/// Duty cycle for `pulse_us` on a channel whose full scale is `max_duty` per 20 ms.
+pub fn duty_from_pulse(pulse_us: u16, max_duty: u16) -> u16 {
+    ((pulse_us as u32 * max_duty as u32 + PERIOD_US / 2) / PERIOD_US) as u16
+}
+
+impl<P: SetDutyCycle> Servo for PwmServo<P> {
+    fn calibration(&self) -> &Calibration {
+        &self.calibration
+    }
+
+    fn set_pulse_us(&mut self, pulse_us: u16) -> Result<u16, ServoError> {
+        let pulse_us = self.calibration.clamp_pulse(pulse_us as i32);
+        let duty = duty_from_pulse(pulse_us, self.pwm.max_duty_cycle());
+        self.pwm.set_duty_cycle(duty).map_err(|_| ServoError::Pwm)?;
+        Ok(pulse_us)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::convert::Infallible;
+
+    struct FakePwm {
+        max: u16,
+        duty: u16,
+    }
+
+    impl embedded_hal::pwm::ErrorType for FakePwm {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for FakePwm {
+        fn max_duty_cycle(&self) -> u16 {
+            self.max
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Infallible> {
+            self.duty = duty;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn angle_endpoints() {
+        let cal = Calibration::default();
+        assert_eq!(cal.pulse_from_angle(0.0), Ok(500));
+        assert_eq!(cal.pulse_from_angle(90.0), Ok(1500));
+        assert_eq!(cal.pulse_from_angle(180.0), Ok(2500));
+        assert_eq!(cal.pulse_from_angle(45.5), Ok(1006));
+    }
+
+    #[test]
+    fn clamps_and_validates() {
+        let cal = Calibration::default();
+        assert_eq!(cal.pulse_from_angle(-20.0), Ok(500));
+        assert_eq!(cal.pulse_from_angle(999.0), Ok(2500));
+        assert_eq!(
+            cal.pulse_from_angle(f32::NAN),
+            Err(ServoError::InvalidAngle)
+        );
+        assert_eq!(
+            cal.pulse_from_angle(f32::INFINITY),
+            Err(ServoError::InvalidAngle)
+        );
+
+        let empty = Calibration {
+            max_pulse_us: 500,
+            ..cal
+        };
+        assert_eq!(empty.validate(), Err(ServoError::InvalidCalibration));
+        let backwards = Calibration {
+            min_angle: 90.0,
+            max_angle: -90.0,
+            ..cal
+        };
+        assert_eq!(backwards.validate(), Err(ServoError::InvalidCalibration));
+        assert!(cal.validate().is_ok());
+    }
+
+    #[test]
+    fn inversion_trim_and_custom_range() {
+        // 270° servo centred at 0°, mounted mirrored and slightly off
+        let cal = Calibration {
+            min_pulse_us: 600,
+            max_pulse_us: 2400,
+            min_angle: -135.0,
+            max_angle: 135.0,
+            inverted: true,
+            trim_us: 20,
+        };
+        assert_eq!(cal.pulse_from_angle(0.0), Ok(1520));
+        assert_eq!(cal.pulse_from_angle(-135.0), Ok(2400));
+        assert_eq!(cal.pulse_from_angle(135.0), Ok(620));
+        // Trim never pushes past the end stops
+        assert_eq!(cal.pulse_from_angle(-134.0), Ok(2400));
+
+        for deg in [-100.0, -10.0, 0.0, 42.0, 120.0] {
+            let pulse = cal.pulse_from_angle(deg).unwrap();
+            assert!((cal.angle_from_pulse(pulse) - deg).abs() < 0.2);
+        }
+    }
+
+    #[test]
+    fn pwm_backends() {
+        // LEDC at 12 bit, 2.5% to 12.5% duty
+        let ledc = FakePwm { max: 4096, duty: 0 };
+        let mut servo = PwmServo::new(ledc, Calibration::default()).unwrap();
+        servo.set_angle(0.0).unwrap();
+        assert_eq!(servo.pwm.duty, 102);
+        servo.set_angle(180.0).unwrap();
+        assert_eq!(servo.pwm.duty, 512);
+
+        // MCPWM with timestamps 0..=19_999
+        let mcpwm = FakePwm {
+            max: 19_999,
+            duty: 0,
+        };
+        let mut servo = PwmServo::new(mcpwm, Calibration::default()).unwrap();
+        servo.set_angle(90.0).unwrap();
+        assert_eq!(servo.pwm.duty, 1500);
+        assert_eq!(servo.set_pulse_us(3000), Ok(2500));
+        assert_eq!(servo.pwm.duty, 2500);
+    }
+}
@@ -0,0 +1,218 @@
+//! Runs servo moves in the background, one command at a time.
+//!
+//! ```ignore
+//! static PLANNER: Planner<CriticalSectionRawMutex, 2> = Planner::new();
+//!
+//! #[embassy_executor::task]
+//! async fn planner_task(mut servos: [LedcServo; 2]) {
+//!     PLANNER.run(&mut servos, [Limits::default(); 2], [90.0; 2]).await
+//! }
+//! ```
+
+use embassy_futures::select::{select, Either};
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Instant, Timer};
+
+use super::trajectory::{stopping_point, Limits, Move, Profile};
+use super::{Servo, ServoError};
+
+/// One update per 50 Hz servo frame, faster updates never reach the horn.
+const FRAME: Duration = Duration::from_millis(20);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command<const N: usize> {
+    /// Moves the servos that have a target, the others hold their angle.
+    ///
+    /// Targets are clamped to each servo's calibrated range, a command with a
+    /// NaN or infinite target is ignored.
+    MoveTo {
+        targets: [Option<f32>; N],
+        profile: Profile,
+        /// Stretch the shorter moves so every servo arrives at the same time.
+        synchronized: bool,
+    },
+    /// Stops every servo where it is.
+    Stop,
+}
+
+pub struct Planner<M: RawMutex, const N: usize> {
+    commands: Channel<M, Command<N>, 4>,
+}
+
+impl<M: RawMutex, const N: usize> Planner<M, N> {
+    pub const fn new() -> Self {
+        Self {
+            commands: Channel::new(),
+        }
+    }
+
+    pub async fn send(&self, command: Command<N>) {
+        self.commands.send(command).await;
+    }
+
+    /// Moves `servos[i]` within `limits[i]` forever, starting with a jump to `home`.
+    ///
+    /// A command that arrives mid-move replaces it, starting from wherever the
+    /// servos are and as fast as they are turning. A `home` outside the
+    /// calibrated range is clamped, a NaN one is the middle of the range.
+    pub async fn run<S: Servo>(
+        &self,
+        servos: &mut [S; N],
+        limits: [Limits; N],
+        home: [f32; N],
+    ) -> ! {
+        let mut positions: [f32; N] = core::array::from_fn(|i| {
+            let calibration = servos[i].calibration();
+            calibration
+                .clamp_angle(home[i])
+                .unwrap_or((calibration.min_angle + calibration.max_angle) / 2.0)
+        });
+        let mut velocities = [0.0; N];
+        write(servos, &positions);
+        let mut command = self.commands.receive().await;
+        loop {
+            let plan = match command {
+                Command::MoveTo {
+                    targets,
+                    profile,
+                    synchronized,
+                } => {
+                    let plan = plan_move(
+                        servos,
+                        positions,
+                        velocities,
+                        targets,
+                        limits,
+                        profile,
+                        synchronized,
+                    );
+                    match plan {
+                        Ok(plan) => plan,
+                        Err(_) => {
+                            command = self.commands.receive().await;
+                            continue;
+                        }
+                    }
+                }
+                Command::Stop => {
+                    velocities = [0.0; N];
+                    command = self.commands.receive().await;
+                    continue;
+                }
+            };
+            let start = Instant::now();
+            let mut frame = 0;
+            command = loop {
+                // Sample on absolute frame times so a slow frame does not stretch the move
+                let elapsed = (FRAME * frame).as_millis();
+                positions = plan.positions(elapsed);
+                velocities = plan.velocities(elapsed);
+                write(servos, &positions);
+                if plan.is_done(elapsed) {
+                    break self.commands.receive().await;
+                }
+                frame += 1;
+                match select(Timer::at(start + FRAME * frame), self.commands.receive()).await {
+                    Either::First(()) => {}
+                    Either::Second(command) => break command,
+                }
+            };
+        }
+    }
+}
+
+// Plans toward `targets` clamped to the calibrated ranges, servos without a
+// target brake to a stop inside their range
+fn plan_move<S: Servo, const N: usize>(
+    servos: &[S; N],
+    positions: [f32; N],
+    velocities: [f32; N],
+    targets: [Option<f32>; N],
+    limits: [Limits; N],
+    profile: Profile,
+    synchronized: bool,
+) -> Result<Move<N>, ServoError> {
+    let mut clamped = [None; N];
+    for i in 0..N {
+        let target = targets[i]
+            .unwrap_or_else(|| stopping_point(positions[i], velocities[i], limits[i], profile));
+        clamped[i] = Some(servos[i].calibration().clamp_angle(target)?);
+    }
+    Ok(Move::with_velocities(
+        positions,
+        velocities,
+        clamped,
+        limits,
+        profile,
+        synchronized,
+    ))
+}
+
+fn write<S: Servo, const N: usize>(servos: &mut [S; N], positions: &[f32; N]) {
+    for (servo, &deg) in servos.iter_mut().zip(positions) {
+        // Positions are finite and braking past a range end is clamped by the
+        // servo, so only a PWM fault can fail here and the next frame retries
+        let _ = servo.set_angle(deg);
+    }
+}
+
+impl<M: RawMutex, const N: usize> Default for Planner<M, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::servo::Calibration;
+
+    struct FakeServo(Calibration);
+
+    impl Servo for FakeServo {
+        fn calibration(&self) -> &Calibration {
+            &self.0
+        }
+
+        fn set_pulse_us(&mut self, pulse_us: u16) -> Result<u16, ServoError> {
+            Ok(self.0.clamp_pulse(pulse_us as i32))
+        }
+    }
+
+    fn plan(
+        targets: [Option<f32>; 2],
+        positions: [f32; 2],
+        velocities: [f32; 2],
+    ) -> Result<Move<2>, ServoError> {
+        let servos = [
+            FakeServo(Calibration::default()),
+            FakeServo(Calibration::default()),
+        ];
+        plan_move(
+            &servos,
+            positions,
+            velocities,
+            targets,
+            [Limits::default(); 2],
+            Profile::Trapezoidal,
+            false,
+        )
+    }
+
+    #[test]
+    fn rejects_non_finite_targets() {
+        let nan = plan([Some(f32::NAN), Some(90.0)], [90.0; 2], [0.0; 2]);
+        assert_eq!(nan.err(), Some(ServoError::InvalidAngle));
+        let infinite = plan([Some(f32::INFINITY), None], [90.0; 2], [0.0; 2]);
+        assert_eq!(infinite.err(), Some(ServoError::InvalidAngle));
+    }
+
+    #[test]
+    fn clamps_targets_and_stops_to_the_calibrated_range() {
+        // Turning toward 180° at full speed would brake to a stop past it
+        let plan = plan([Some(300.0), None], [90.0, 170.0], [0.0, 180.0]).unwrap();
+        let end = plan.positions(plan.duration_ms());
+        assert_eq!(end, [180.0, 180.0]);
+    }
+}
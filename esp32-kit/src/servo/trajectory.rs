@@ -0,0 +1,538 @@
+//! Velocity and acceleration limited moves between two angles.
+//!
+//! A move ramps up, cruises and ramps down. The trapezoidal profile ramps
+//! with constant acceleration. The S-curve ramps with a cosine velocity
+//! curve, so the acceleration itself rises and falls smoothly and the servo
+//! does not jerk at the corners. A servo that is already turning starts the
+//! move at its current speed, braking first if it heads the wrong way.
+
+use core::f32::consts::PI;
+
+use super::ServoError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    #[default]
+    Trapezoidal,
+    SCurve,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Limits {
+    // °/s
+    max_velocity: f32,
+    // °/s²
+    max_acceleration: f32,
+}
+
+impl Limits {
+    /// `max_velocity` in °/s and `max_acceleration` in °/s², both above zero.
+    pub fn new(max_velocity: f32, max_acceleration: f32) -> Result<Self, ServoError> {
+        let valid = |limit: f32| limit.is_finite() && limit > 0.0;
+        if !valid(max_velocity) || !valid(max_acceleration) {
+            return Err(ServoError::InvalidLimits);
+        }
+        Ok(Self {
+            max_velocity,
+            max_acceleration,
+        })
+    }
+
+    pub fn max_velocity(&self) -> f32 {
+        self.max_velocity
+    }
+
+    pub fn max_acceleration(&self) -> f32 {
+        self.max_acceleration
+    }
+}
+
+impl Default for Limits {
+    /// Comfortably inside what an SG90 manages (about 600 °/s unloaded).
+    fn default() -> Self {
+        Self {
+            max_velocity: 180.0,
+            max_acceleration: 720.0,
+        }
+    }
+}
+
+// One phase of a move, with signed speeds at either end
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+struct Segment {
+    duration_s: f32,
+    from: f32,
+    to: f32,
+}
+
+impl Segment {
+    fn ramp(from: f32, to: f32, accel: f32) -> Self {
+        Self {
+            duration_s: (to - from).abs() / accel,
+            from,
+            to,
+        }
+    }
+
+    // Both ramp shapes are symmetric, so they cover the same distance
+    fn distance(&self) -> f32 {
+        (self.from + self.to) / 2.0 * self.duration_s
+    }
+
+    // Signed distance and speed `t` seconds in
+    fn sample(&self, profile: Profile, t: f32) -> (f32, f32) {
+        let (v0, dv, span) = (self.from, self.to - self.from, self.duration_s);
+        match profile {
+            Profile::Trapezoidal => (v0 * t + dv * t * t / (2.0 * span), v0 + dv * t / span),
+            Profile::SCurve => {
+                let phase = PI * t / span;
+                (
+                    v0 * t + dv / 2.0 * (t - span / PI * libm::sinf(phase)),
+                    v0 + dv / 2.0 * (1.0 - libm::cosf(phase)),
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Trajectory {
+    start: f32,
+    target: f32,
+    profile: Profile,
+    start_velocity: f32,
+    // Brake, ramp up, cruise and ramp down, unused ones take no time
+    segments: [Segment; 4],
+}
+
+impl Trajectory {
+    /// Fastest move from `start` to `target` within `limits`.
+    pub fn new(start: f32, target: f32, limits: Limits, profile: Profile) -> Self {
+        Self::with_start_velocity(start, 0.0, target, limits, profile)
+    }
+
+    /// Fastest move for a servo that is turning at `velocity` °/s as it starts.
+    ///
+    /// If it heads away from `target`, or too fast to stop there, it brakes
+    /// first and comes back.
+    pub fn with_start_velocity(
+        start: f32,
+        velocity: f32,
+        target: f32,
+        limits: Limits,
+        profile: Profile,
+    ) -> Self {
+        Self::plan(start, velocity, target, limits, profile, None)
+    }
+
+    /// Same move stretched to take `duration_s`, used to let several servos finish together.
+    ///
+    /// Keeps the acceleration and lowers the cruise speed. Durations shorter
+    /// than the fastest possible move give the fastest move.
+    pub fn with_duration(
+        start: f32,
+        target: f32,
+        limits: Limits,
+        profile: Profile,
+        duration_s: f32,
+    ) -> Self {
+        Self::new(start, target, limits, profile).stretched(limits, duration_s)
+    }
+
+    /// This move, from the same speed, stretched to take `duration_s` like [`with_duration`](Self::with_duration).
+    pub fn stretched(&self, limits: Limits, duration_s: f32) -> Self {
+        if duration_s <= self.duration_s() {
+            return *self;
+        }
+        Self::plan(
+            self.start,
+            self.start_velocity,
+            self.target,
+            limits,
+            self.profile,
+            Some(duration_s),
+        )
+    }
+
+    fn plan(
+        start: f32,
+        velocity: f32,
+        target: f32,
+        limits: Limits,
+        profile: Profile,
+        duration_s: Option<f32>,
+    ) -> Self {
+        let accel = effective_acceleration(limits, profile);
+        let mut segments = [Segment::default(); 4];
+        // Signed distance it takes to stop
+        let stop = velocity * velocity.abs() / (2.0 * accel);
+        let ahead = target - start;
+        let mut from = start;
+        let mut speed = velocity.abs();
+        if velocity != 0.0 && (velocity * ahead < 0.0 || stop.abs() > ahead.abs()) {
+            segments[0] = Segment::ramp(velocity, 0.0, accel);
+            from += stop;
+            speed = 0.0;
+        }
+
+        let distance = (target - from).abs();
+        let sign = if target < from { -1.0 } else { 1.0 };
+        let peak = match duration_s {
+            None => fastest_peak(distance, speed, limits.max_velocity, accel),
+            Some(total_s) => {
+                stretched_peak(distance, speed, accel, total_s - segments[0].duration_s)
+            }
+        };
+        if distance > 0.0 || speed > 0.0 {
+            segments[1] = Segment::ramp(sign * speed, sign * peak, accel);
+            segments[3] = Segment::ramp(sign * peak, 0.0, accel);
+            let ramps = (segments[1].distance() + segments[3].distance()).abs();
+            if peak > 0.0 {
+                segments[2] = Segment {
+                    duration_s: ((distance - ramps) / peak).max(0.0),
+                    from: sign * peak,
+                    to: sign * peak,
+                };
+            }
+        }
+        Self {
+            start,
+            target,
+            profile,
+            start_velocity: velocity,
+            segments,
+        }
+    }
+
+    pub fn start(&self) -> f32 {
+        self.start
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    pub fn duration_s(&self) -> f32 {
+        self.segments.iter().map(|s| s.duration_s).sum()
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        libm::ceilf(self.duration_s() * 1000.0) as u64
+    }
+
+    // Angle and signed speed at `t` seconds
+    fn sample(&self, t: f32) -> (f32, f32) {
+        if t <= 0.0 {
+            return (self.start, self.start_velocity);
+        }
+        let (mut position, mut t) = (self.start, t);
+        for segment in &self.segments {
+            if t < segment.duration_s {
+                let (covered, speed) = segment.sample(self.profile, t);
+                return (position + covered, speed);
+            }
+            position += segment.distance();
+            t -= segment.duration_s;
+        }
+        (self.target, 0.0)
+    }
+
+    /// Angle `elapsed_ms` after the start of the move.
+    pub fn position(&self, elapsed_ms: u64) -> f32 {
+        self.sample(elapsed_ms as f32 / 1000.0).0
+    }
+
+    /// Signed speed in °/s `elapsed_ms` after the start of the move.
+    pub fn velocity(&self, elapsed_ms: u64) -> f32 {
+        self.sample(elapsed_ms as f32 / 1000.0).1
+    }
+}
+
+// The cosine ramp peaks at pi/2 times its mean acceleration
+/// Where a servo at `position` turning at `velocity` °/s comes to rest when braking now.
+pub fn stopping_point(position: f32, velocity: f32, limits: Limits, profile: Profile) -> f32 {
+    let accel = effective_acceleration(limits, profile);
+    position + velocity * velocity.abs() / (2.0 * accel)
+}
+
+fn effective_acceleration(limits: Limits, profile: Profile) -> f32 {
+    match profile {
+        Profile::Trapezoidal => limits.max_acceleration,
+        Profile::SCurve => limits.max_acceleration * 2.0 / PI,
+    }
+}
+
+// Top speed of the fastest move over `distance` that starts at `speed` toward the target
+fn fastest_peak(distance: f32, speed: f32, max_velocity: f32, accel: f32) -> f32 {
+    let ramps = ((max_velocity * max_velocity - speed * speed).abs() + max_velocity * max_velocity)
+        / (2.0 * accel);
+    if distance >= ramps {
+        max_velocity
+    } else {
+        // Too short to reach full speed: ramp up and straight back down
+        libm::sqrtf(accel * distance + speed * speed / 2.0)
+    }
+}
+
+// Cruise speed of the move that takes `duration_s`
+fn stretched_peak(distance: f32, speed: f32, accel: f32, duration_s: f32) -> f32 {
+    // Speeding up: duration = (v - speed) / a + (distance + speed² / 2a) / v,
+    // take the smaller root
+    let b = speed + accel * duration_s;
+    let discriminant = b * b - 4.0 * accel * distance - 2.0 * speed * speed;
+    let peak = (b - libm::sqrtf(discriminant.max(0.0))) / 2.0;
+    if peak >= speed {
+        return peak;
+    }
+    // Slowing down: duration = speed / a + (distance - speed² / 2a) / v
+    let cruise = distance - speed * speed / (2.0 * accel);
+    (cruise / (duration_s - speed / accel)).max(0.0)
+}
+
+/// Moves several servos at once, optionally stretched so they all arrive together.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Move<const N: usize> {
+    axes: [Trajectory; N],
+}
+
+impl<const N: usize> Move<N> {
+    /// Servos without a target hold `from`.
+    pub fn new(
+        from: [f32; N],
+        to: [Option<f32>; N],
+        limits: [Limits; N],
+        profile: Profile,
+        synchronized: bool,
+    ) -> Self {
+        Self::with_velocities(from, [0.0; N], to, limits, profile, synchronized)
+    }
+
+    /// Like [`new`](Self::new) for servos turning at `velocities` °/s.
+    ///
+    /// Servos without a target brake to a stop.
+    pub fn with_velocities(
+        from: [f32; N],
+        velocities: [f32; N],
+        to: [Option<f32>; N],
+        limits: [Limits; N],
+        profile: Profile,
+        synchronized: bool,
+    ) -> Self {
+        let mut axes: [Trajectory; N] = core::array::from_fn(|i| {
+            let stop = stopping_point(from[i], velocities[i], limits[i], profile);
+            Trajectory::with_start_velocity(
+                from[i],
+                velocities[i],
+                to[i].unwrap_or(stop),
+                limits[i],
+                profile,
+            )
+        });
+        if synchronized {
+            let duration_s = axes.iter().map(|a| a.duration_s()).fold(0.0, f32::max);
+            for (axis, limits) in axes.iter_mut().zip(limits) {
+                *axis = axis.stretched(limits, duration_s);
+            }
+        }
+        Self { axes }
+    }
+
+    pub fn axes(&self) -> &[Trajectory; N] {
+        &self.axes
+    }
+
+    pub fn duration_ms(&self) -> u64 {
+        self.axes.iter().map(|a| a.duration_ms()).max().unwrap_or(0)
+    }
+
+    pub fn positions(&self, elapsed_ms: u64) -> [f32; N] {
+        self.axes.map(|axis| axis.position(elapsed_ms))
+    }
+
+    pub fn velocities(&self, elapsed_ms: u64) -> [f32; N] {
+        self.axes.map(|axis| axis.velocity(elapsed_ms))
+    }
+
+    pub fn is_done(&self, elapsed_ms: u64) -> bool {
+        elapsed_ms >= self.duration_ms()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        max_velocity: 100.0,
+        max_acceleration: 200.0,
+    };
+
+    fn assert_close(a: f32, b: f32) {
+        assert!((a - b).abs() < 0.05, "{a} != {b}");
+    }
+
+    // Checks the limits by differentiating the sampled positions
+    fn assert_within_limits(trajectory: &Trajectory, limits: Limits) {
+        let mut last_velocity = trajectory.velocity(0);
+        for ms in 0..=trajectory.duration_ms() {
+            let velocity = trajectory.velocity(ms);
+            assert!(velocity.abs() <= limits.max_velocity + 0.01);
+            assert!((velocity - last_velocity).abs() * 1000.0 <= limits.max_acceleration + 1.0);
+            last_velocity = velocity;
+        }
+    }
+
+    #[test]
+    fn trapezoid_reaches_cruise_speed() {
+        // 0.5 s ramps covering 25° each, 0.5 s cruise for the 50° in between
+        let t = Trajectory::new(0.0, 100.0, LIMITS, Profile::Trapezoidal);
+        assert_close(t.duration_s(), 1.5);
+        assert_close(t.position(0), 0.0);
+        assert_close(t.position(500), 25.0);
+        assert_close(t.position(750), 50.0);
+        assert_close(t.position(1000), 75.0);
+        assert_close(t.position(1500), 100.0);
+        assert_close(t.position(9999), 100.0);
+        assert_close(t.velocity(750), 100.0);
+        assert_within_limits(&t, LIMITS);
+    }
+
+    #[test]
+    fn short_moves_are_triangular() {
+        // Never reaches 100°/s: peak sqrt(20 * 200) ~= 63°/s
+        let t = Trajectory::new(50.0, 30.0, LIMITS, Profile::Trapezoidal);
+        assert_close(t.velocity(t.duration_ms() / 2), -63.2);
+        assert_close(t.position(t.duration_ms() / 2), 40.0);
+        assert_close(t.position(t.duration_ms()), 30.0);
+        assert_within_limits(&t, LIMITS);
+
+        let still = Trajectory::new(42.0, 42.0, LIMITS, Profile::SCurve);
+        assert_eq!(still.duration_ms(), 0);
+        assert_eq!(still.position(100), 42.0);
+    }
+
+    #[test]
+    fn s_curve_is_smooth_and_within_limits() {
+        let s = Trajectory::new(180.0, 0.0, LIMITS, Profile::SCurve);
+        let trapezoid = Trajectory::new(180.0, 0.0, LIMITS, Profile::Trapezoidal);
+        // Gentler ramps take a little longer
+        assert!(s.duration_s() > trapezoid.duration_s());
+        assert_close(s.position(s.duration_ms()), 0.0);
+        assert_close(s.position(s.duration_ms() / 2), 90.0);
+        // Starts with zero acceleration instead of jumping straight to the limit
+        assert!(s.velocity(10).abs() < trapezoid.velocity(10).abs() / 10.0);
+        assert_within_limits(&s, LIMITS);
+    }
+
+    #[test]
+    fn synchronized_moves_finish_together() {
+        let from = [0.0, 90.0, 45.0];
+        let to = [Some(180.0), Some(80.0), None];
+        let limits = [LIMITS; 3];
+        let synced = Move::new(from, to, limits, Profile::SCurve, true);
+        let lead = Trajectory::new(0.0, 180.0, LIMITS, Profile::SCurve);
+        assert_eq!(synced.duration_ms(), lead.duration_ms());
+        for axis in &synced.axes()[..2] {
+            assert!(axis.duration_ms().abs_diff(lead.duration_ms()) <= 1);
+            assert_within_limits(axis, LIMITS);
+        }
+        // Halfway in time is halfway in angle thanks to the symmetric profile
+        let [a, b, c] = synced.positions(synced.duration_ms() / 2);
+        assert_close(a, 90.0);
+        assert_close(b, 85.0);
+        assert_eq!(c, 45.0);
+        for (at_end, target) in synced
+            .positions(synced.duration_ms())
+            .iter()
+            .zip([180.0, 80.0, 45.0])
+        {
+            assert_close(*at_end, target);
+        }
+
+        // Unsynchronized the short move is over long before the long one
+        let free = Move::new(from, to, limits, Profile::SCurve, false);
+        assert!(free.axes()[1].duration_ms() < free.duration_ms() / 2);
+        assert!(!free.is_done(free.duration_ms() - 1));
+        assert!(free.is_done(free.duration_ms()));
+    }
+
+    #[test]
+    fn starts_at_the_current_speed() {
+        // Already at full speed toward the target: no ramp up, cruise, ramp down
+        let t = Trajectory::with_start_velocity(0.0, 100.0, 100.0, LIMITS, Profile::Trapezoidal);
+        assert_eq!(t.velocity(0), 100.0);
+        assert_close(t.velocity(100), 100.0);
+        assert_close(t.duration_s(), 1.25);
+        assert_close(t.position(t.duration_ms()), 100.0);
+        assert_within_limits(&t, LIMITS);
+        // Stretched, it slows down to 50°/s to take 2 s
+        let slow = t.stretched(LIMITS, 2.0);
+        assert_close(slow.duration_s(), 2.0);
+        assert_eq!(slow.velocity(0), 100.0);
+        assert_close(slow.velocity(1000), 50.0);
+        assert_close(slow.position(slow.duration_ms()), 100.0);
+        assert_within_limits(&slow, LIMITS);
+
+        // Replacing a move halfway keeps the servo from jerking to a halt
+        let first = Trajectory::new(0.0, 100.0, LIMITS, Profile::SCurve);
+        let (at, speed) = (first.position(700), first.velocity(700));
+        let second = Trajectory::with_start_velocity(at, speed, 150.0, LIMITS, Profile::SCurve);
+        assert_eq!(second.position(0), at);
+        assert_eq!(second.velocity(0), speed);
+        assert_close(second.position(second.duration_ms()), 150.0);
+        assert_within_limits(&second, LIMITS);
+    }
+
+    #[test]
+    fn brakes_before_turning_back() {
+        // Heading up at full speed, the new target is behind: 25° to stop, then back
+        let t = Trajectory::with_start_velocity(90.0, 100.0, 60.0, LIMITS, Profile::Trapezoidal);
+        assert_close(t.position(500), 115.0);
+        assert_close(t.velocity(500), 0.0);
+        assert!(t.velocity(600) < 0.0);
+        assert_close(t.position(t.duration_ms()), 60.0);
+        assert_within_limits(&t, LIMITS);
+
+        // Too fast to stop at a target just ahead: overshoot and come back
+        let t = Trajectory::with_start_velocity(0.0, 100.0, 10.0, LIMITS, Profile::Trapezoidal);
+        assert_close(t.position(500), 25.0);
+        assert_close(t.position(t.duration_ms()), 10.0);
+        assert_within_limits(&t, LIMITS);
+    }
+
+    #[test]
+    fn untargeted_servos_coast_to_a_stop() {
+        let moving = Move::with_velocities(
+            [0.0, 90.0],
+            [0.0, -100.0],
+            [Some(180.0), None],
+            [LIMITS; 2],
+            Profile::Trapezoidal,
+            true,
+        );
+        let [lead, coasting] = moving.axes();
+        assert_eq!(coasting.target(), 65.0);
+        assert_eq!(moving.velocities(0), [0.0, -100.0]);
+        // Braking as hard as allowed can not be stretched to arrive with the other one
+        assert_eq!(coasting.duration_ms(), 500);
+        assert!(lead.duration_ms() > 2000);
+        assert_within_limits(coasting, LIMITS);
+        assert_close(moving.positions(moving.duration_ms())[1], 65.0);
+    }
+
+    #[test]
+    fn limits_must_be_positive() {
+        assert_eq!(Limits::new(100.0, 200.0), Ok(LIMITS));
+        for (velocity, acceleration) in [
+            (0.0, 200.0),
+            (100.0, -1.0),
+            (f32::NAN, 200.0),
+            (100.0, f32::INFINITY),
+        ] {
+            assert_eq!(
+                Limits::new(velocity, acceleration),
+                Err(ServoError::InvalidLimits)
+            );
+        }
+    }
+}
@@ -0,0 +1,233 @@
+//! SNTP client packets (RFC 4330).
+//!
+//! [`request`] builds the query and [`parse_reply`] turns the answer into the
+//! offset of the local clock, the UDP socket stays with the caller. Times are
+//! microseconds since 1970, the way `Rtc::current_time_us` counts them.
+
+use core::fmt;
+
+pub const PORT: u16 = 123;
+pub const PACKET_LEN: usize = 48;
+
+// Seconds from 1900, where NTP counts from, to 1970
+const NTP_TO_UNIX_SECS: u64 = 2_208_988_800;
+// NTP seconds wrap in 2036, smaller values are taken to be past that
+const ERA_PIVOT: u32 = 0x8000_0000;
+const VERSION: u8 = 4;
+const MODE_CLIENT: u8 = 3;
+const MODE_SERVER: u8 = 4;
+const MODE_BROADCAST: u8 = 5;
+const LEAP_UNSYNCHRONIZED: u8 = 3;
+// Stratums above this are reserved
+const MAX_STRATUM: u8 = 15;
+
+const STRATUM: usize = 1;
+const REFERENCE_ID: usize = 12;
+const ORIGINATE: usize = 24;
+const RECEIVE: usize = 32;
+const TRANSMIT: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SntpError {
+    TooShort,
+    /// Not a server or broadcast packet.
+    NotAServerReply,
+    /// Answers some other request, e.g. one that timed out earlier.
+    UnexpectedReply,
+    /// The server wants us to go away or slow down, its code is e.g. `RATE` or `DENY`.
+    KissOfDeath([u8; 4]),
+    /// The server does not know the time itself.
+    Unsynchronized,
+    /// A timestamp from before 1970, which the microsecond clock cannot hold.
+    BeforeEpoch,
+}
+
+impl fmt::Display for SntpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SntpError::TooShort => f.write_str("reply too short"),
+            SntpError::NotAServerReply => f.write_str("not a server reply"),
+            SntpError::UnexpectedReply => f.write_str("reply to another request"),
+            SntpError::KissOfDeath(code) => {
+                write!(f, "kiss of death ")?;
+                code.iter().try_for_each(|&c| write!(f, "{}", c as char))
+            }
+            SntpError::Unsynchronized => f.write_str("server not synchronized"),
+            SntpError::BeforeEpoch => f.write_str("time before 1970"),
+        }
+    }
+}
+
+/// One measurement of the local clock against a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    /// What to add to the local clock to get the server's time.
+    pub offset_us: i64,
+    /// Round trip time, without the time the server took to answer.
+    pub delay_us: u64,
+    pub stratum: u8,
+}
+
+/// NTP timestamp of a Unix time in microseconds.
+pub fn to_ntp(unix_us: u64) -> u64 {
+    let secs = (unix_us / 1_000_000 + NTP_TO_UNIX_SECS) as u32;
+    let fraction = ((unix_us % 1_000_000) << 32) / 1_000_000;
+    (secs as u64) << 32 | fraction
+}
+
+/// Unix time in microseconds of an NTP timestamp, from 1970 to 2104.
+///
+/// Timestamps from 1968 and 1969 are `None`.
+pub fn from_ntp(timestamp: u64) -> Option<u64> {
+    let secs = (timestamp >> 32) as u32;
+    let era = if secs < ERA_PIVOT { 1u64 << 32 } else { 0 };
+    let micros = ((timestamp & 0xFFFF_FFFF) * 1_000_000 + (1 << 31)) >> 32;
+    let unix_secs = (secs as u64 + era).checked_sub(NTP_TO_UNIX_SECS)?;
+    Some(unix_secs * 1_000_000 + micros)
+}
+
+fn timestamp(packet: &[u8], at: usize) -> u64 {
+    u64::from_be_bytes(packet[at..at + 8].try_into().unwrap())
+}
+
+/// Writes a request sent at `transmit_us` into `out`, returns its length.
+///
+/// `out` must hold at least [`PACKET_LEN`] bytes.
+pub fn request(transmit_us: u64, out: &mut [u8]) -> usize {
+    let packet = &mut out[..PACKET_LEN];
+    packet.fill(0);
+    packet[0] = VERSION << 3 | MODE_CLIENT;
+    packet[TRANSMIT..TRANSMIT + 8].copy_from_slice(&to_ntp(transmit_us).to_be_bytes());
+    PACKET_LEN
+}
+
+/// Checks the reply to a request sent at `sent_us` and received at `received_us`.
+pub fn parse_reply(reply: &[u8], sent_us: u64, received_us: u64) -> Result<Sample, SntpError> {
+    if reply.len() < PACKET_LEN {
+        return Err(SntpError::TooShort);
+    }
+    let mode = reply[0] & 0x07;
+    if mode != MODE_SERVER && mode != MODE_BROADCAST {
+        return Err(SntpError::NotAServerReply);
+    }
+    // The server echoes our transmit time, which also keeps spoofed replies out
+    if timestamp(reply, ORIGINATE) != to_ntp(sent_us) {
+        return Err(SntpError::UnexpectedReply);
+    }
+    let stratum = reply[STRATUM];
+    if stratum == 0 {
+        let code = reply[REFERENCE_ID..REFERENCE_ID + 4].try_into().unwrap();
+        return Err(SntpError::KissOfDeath(code));
+    }
+    let receive = timestamp(reply, RECEIVE);
+    let transmit = timestamp(reply, TRANSMIT);
+    if reply[0] >> 6 == LEAP_UNSYNCHRONIZED || stratum > MAX_STRATUM || transmit == 0 {
+        return Err(SntpError::Unsynchronized);
+    }
+
+    let server_received = from_ntp(receive).ok_or(SntpError::BeforeEpoch)? as i64;
+    let server_sent = from_ntp(transmit).ok_or(SntpError::BeforeEpoch)? as i64;
+    let (sent, received) = (sent_us as i64, received_us as i64);
+    let offset_us = ((server_received - sent) + (server_sent - received)) / 2;
+    let delay_us = ((received - sent) - (server_sent - server_received)).max(0) as u64;
+    Ok(Sample {
+        offset_us,
+        delay_us,
+        stratum,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(sent_us: u64, receive_us: u64, transmit_us: u64) -> [u8; PACKET_LEN] {
+        let mut packet = [0; PACKET_LEN];
+        packet[0] = VERSION << 3 | MODE_SERVER;
+        packet[STRATUM] = 2;
+        packet[ORIGINATE..ORIGINATE + 8].copy_from_slice(&to_ntp(sent_us).to_be_bytes());
+        packet[RECEIVE..RECEIVE + 8].copy_from_slice(&to_ntp(receive_us).to_be_bytes());
+        packet[TRANSMIT..TRANSMIT + 8].copy_from_slice(&to_ntp(transmit_us).to_be_bytes());
+        packet
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(to_ntp(0), NTP_TO_UNIX_SECS << 32);
+        assert_eq!(to_ntp(500_000), NTP_TO_UNIX_SECS << 32 | 0x8000_0000);
+        // 2036-02-07 06:28:16 UTC is where the seconds wrap
+        let wrap = ((1u64 << 32) - NTP_TO_UNIX_SECS) * 1_000_000;
+        assert_eq!(to_ntp(wrap) >> 32, 0);
+        for unix_us in [0, 1_750_000_000_123_456, wrap - 1, wrap, wrap + 1_000_001] {
+            assert_eq!(from_ntp(to_ntp(unix_us)), Some(unix_us));
+        }
+        // 1968 and 1969 are before what the clock can hold
+        assert_eq!(from_ntp((ERA_PIVOT as u64) << 32), None);
+        assert_eq!(from_ntp((NTP_TO_UNIX_SECS - 1) << 32), None);
+        assert_eq!(from_ntp(NTP_TO_UNIX_SECS << 32), Some(0));
+    }
+
+    #[test]
+    fn request_packet() {
+        let mut out = [0xAA; 64];
+        assert_eq!(request(1_000_000, &mut out), PACKET_LEN);
+        assert_eq!(out[0], 0x23);
+        assert!(out[1..TRANSMIT].iter().all(|&b| b == 0));
+        assert_eq!(timestamp(&out, TRANSMIT), to_ntp(1_000_000));
+        assert_eq!(out[PACKET_LEN], 0xAA);
+    }
+
+    #[test]
+    fn offset_and_delay() {
+        // Local clock still near 1970 after a reset, the server is in 2025
+        let now = 1_750_000_000_000_000;
+        let sent = 2_000_000;
+        // 10 ms each way, 1 ms in the server
+        let packet = reply(sent, now + 10_000, now + 11_000);
+        let sample = parse_reply(&packet, sent, sent + 21_000).unwrap();
+        assert_eq!(sample.delay_us, 20_000);
+        assert_eq!(sample.offset_us, (now - sent) as i64);
+        assert_eq!(sample.stratum, 2);
+
+        // A clock that is ahead gets a negative offset
+        let packet = reply(now + 5_000, now, now);
+        let sample = parse_reply(&packet, now + 5_000, now + 5_000).unwrap();
+        assert_eq!(sample.offset_us, -5_000);
+        assert_eq!(sample.delay_us, 0);
+    }
+
+    #[test]
+    fn bad_replies() {
+        let sent = 1_000_000;
+        let good = reply(sent, 2_000_000, 2_000_000);
+        assert_eq!(
+            parse_reply(&good[..47], sent, sent),
+            Err(SntpError::TooShort)
+        );
+        assert_eq!(
+            parse_reply(&good, sent + 1, sent),
+            Err(SntpError::UnexpectedReply)
+        );
+
+        let mut client = good;
+        client[0] = VERSION << 3 | MODE_CLIENT;
+        assert_eq!(
+            parse_reply(&client, sent, sent),
+            Err(SntpError::NotAServerReply)
+        );
+
+        let mut kod = good;
+        kod[STRATUM] = 0;
+        kod[REFERENCE_ID..REFERENCE_ID + 4].copy_from_slice(b"RATE");
+        let error = parse_reply(&kod, sent, sent).unwrap_err();
+        assert_eq!(error, SntpError::KissOfDeath(*b"RATE"));
+        assert_eq!(error.to_string(), "kiss of death RATE");
+
+        let mut alarm = good;
+        alarm[0] |= LEAP_UNSYNCHRONIZED << 6;
+        assert_eq!(
+            parse_reply(&alarm, sent, sent),
+            Err(SntpError::Unsynchronized)
+        );
+    }
+}
@@ -0,0 +1,379 @@
+//! Device status on an LED, driven by connection events.
+//!
+//! Network and BLE tasks publish [`Event`]s, [`StatusTracker`] folds them into
+//! a single [`Status`] and [`Status::effect`] picks the LED pattern for it, so
+//! the state of the board can be seen without a serial console. With the
+//! `embassy` feature, [`run`] connects a `PubSubChannel` of events to a
+//! [`LedEffect`](crate::led::LedEffect), and [`StatusLed`] bundles both for
+//! the usual single status LED.
+
+use crate::led::Effect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// The station is trying to join a network, also sent before every retry.
+    WifiConnecting,
+    WifiConnected,
+    WifiDisconnected,
+    GotIp,
+    LostIp,
+    ApStarted,
+    ApStopped,
+    ApStationConnected,
+    ApStationDisconnected,
+    BleAdvertising,
+    BleConnected,
+    BleDisconnected,
+    /// Something the device cannot recover from, shown until reset.
+    Fatal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Off,
+    Connecting,
+    WaitingForIp,
+    /// Waiting for a client, e.g. BLE advertising or an access point nobody has joined yet.
+    WaitingForPeer,
+    Online,
+    Fatal,
+}
+
+impl Status {
+    /// LED pattern for this status.
+    pub fn effect(self) -> Effect {
+        match self {
+            Status::Off => Effect::Off,
+            Status::Connecting => Effect::Blink {
+                period_ms: 200,
+                duty: 0.5,
+            },
+            Status::WaitingForIp => Effect::Blink {
+                period_ms: 1000,
+                duty: 0.5,
+            },
+            Status::WaitingForPeer => Effect::Breathe { period_ms: 3000 },
+            Status::Online => Effect::Solid(1.0),
+            Status::Fatal => Effect::SOS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Station {
+    Idle,
+    Connecting,
+    Connected { has_ip: bool },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Ble {
+    Idle,
+    Advertising,
+    Connected,
+}
+
+/// Combined state of all the connections.
+///
+/// A station connection wins over the access point, which wins over BLE. A
+/// fatal error wins over everything and stays.
+#[derive(Debug, Clone, Copy)]
+pub struct StatusTracker {
+    station: Station,
+    ap_started: bool,
+    ap_stations: u8,
+    ble: Ble,
+    fatal: bool,
+}
+
+impl StatusTracker {
+    pub const fn new() -> Self {
+        Self {
+            station: Station::Idle,
+            ap_started: false,
+            ap_stations: 0,
+            ble: Ble::Idle,
+            fatal: false,
+        }
+    }
+
+    pub fn status(&self) -> Status {
+        if self.fatal {
+            return Status::Fatal;
+        }
+        match self.station {
+            Station::Connecting => return Status::Connecting,
+            Station::Connected { has_ip: false } => return Status::WaitingForIp,
+            Station::Connected { has_ip: true } => return Status::Online,
+            Station::Idle => {}
+        }
+        if self.ap_started {
+            return if self.ap_stations > 0 {
+                Status::Online
+            } else {
+                Status::WaitingForPeer
+            };
+        }
+        match self.ble {
+            Ble::Advertising => Status::WaitingForPeer,
+            Ble::Connected => Status::Online,
+            Ble::Idle => Status::Off,
+        }
+    }
+
+    /// Applies `event`, returns the new status if it changed.
+    pub fn update(&mut self, event: Event) -> Option<Status> {
+        let before = self.status();
+        match event {
+            Event::WifiConnecting => self.station = Station::Connecting,
+            Event::WifiConnected => {
+                // A static address can come up before the link event is seen
+                if !matches!(self.station, Station::Connected { .. }) {
+                    self.station = Station::Connected { has_ip: false }
+                }
+            }
+            // The connection tasks keep retrying after a disconnect
+            Event::WifiDisconnected => self.station = Station::Connecting,
+            Event::GotIp => self.station = Station::Connected { has_ip: true },
+            Event::LostIp => {
+                if let Station::Connected { .. } = self.station {
+                    self.station = Station::Connected { has_ip: false }
+                }
+            }
+            Event::ApStarted => self.ap_started = true,
+            Event::ApStopped => {
+                self.ap_started = false;
+                self.ap_stations = 0;
+            }
+            Event::ApStationConnected => self.ap_stations = self.ap_stations.saturating_add(1),
+            Event::ApStationDisconnected => self.ap_stations = self.ap_stations.saturating_sub(1),
+            Event::BleAdvertising | Event::BleDisconnected => self.ble = Ble::Advertising,
+            Event::BleConnected => self.ble = Ble::Connected,
+            Event::Fatal => self.fatal = true,
+        }
+        let after = self.status();
+        (after != before).then_some(after)
+    }
+}
+
+impl Default for StatusTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "embassy")]
+pub use driver::{run, StatusLed, StatusSubscriber};
+
+#[cfg(feature = "embassy")]
+mod driver {
+    use core::future::pending;
+
+    use embassy_futures::select::{select, Either};
+    use embassy_sync::blocking_mutex::raw::RawMutex;
+    use embassy_sync::pubsub::{PubSubChannel, Subscriber};
+    use embedded_hal::pwm::SetDutyCycle;
+
+    use super::{Event, StatusTracker};
+    use crate::led::LedEffect;
+
+    // Events published faster than the LED task takes them
+    const QUEUE: usize = 8;
+
+    pub type StatusSubscriber<'a, M> = Subscriber<'a, M, Event, QUEUE, 1, 1>;
+
+    /// Shows the status on `led` as the events come in, forever.
+    ///
+    /// [`StatusLed`] does this with a channel of its own, `run` is for events that
+    /// other tasks subscribe to as well.
+    ///
+    /// ```ignore
+    /// // The display task is the second subscriber
+    /// static EVENTS: PubSubChannel<CriticalSectionRawMutex, Event, 8, 2, 0> = PubSubChannel::new();
+    /// static LED: LedEffect<CriticalSectionRawMutex> = LedEffect::new();
+    ///
+    /// #[embassy_executor::task]
+    /// async fn status_task(
+    ///     events: Subscriber<'static, CriticalSectionRawMutex, Event, 8, 2, 0>,
+    ///     mut channel: channel::Channel<'static, HighSpeed>,
+    /// ) {
+    ///     match select(status::run(events, &LED), LED.run(&mut channel)).await {
+    ///         Either::First(never) | Either::Second(never) => never,
+    ///     }
+    /// }
+    /// ```
+    pub async fn run<M, L, const CAP: usize, const SUBS: usize, const PUBS: usize>(
+        mut events: Subscriber<'_, M, Event, CAP, SUBS, PUBS>,
+        led: &LedEffect<L>,
+    ) -> !
+    where
+        M: RawMutex,
+        L: RawMutex,
+    {
+        let mut tracker = StatusTracker::new();
+        led.set(tracker.status().effect()).await;
+        loop {
+            // Missed events are skipped, the later ones still make the state right
+            let event = events.next_message_pure().await;
+            if let Some(status) = tracker.update(event) {
+                led.set(status.effect()).await;
+            }
+        }
+    }
+
+    /// Events in, LED pattern out, for a board with one status LED.
+    ///
+    /// ```ignore
+    /// static STATUS: StatusLed<CriticalSectionRawMutex> = StatusLed::new();
+    ///
+    /// #[embassy_executor::task]
+    /// async fn status_task(
+    ///     events: StatusSubscriber<'static, CriticalSectionRawMutex>,
+    ///     mut channel: channel::Channel<'static, HighSpeed>,
+    /// ) {
+    ///     STATUS.run(events, &mut channel).await
+    /// }
+    ///
+    /// // Subscribe before spawning, so no event published from now on is lost
+    /// spawner.must_spawn(status_task(STATUS.subscriber().unwrap(), channel0));
+    /// ```
+    pub struct StatusLed<M: RawMutex> {
+        events: PubSubChannel<M, Event, QUEUE, 1, 1>,
+        led: LedEffect<M>,
+    }
+
+    impl<M: RawMutex> StatusLed<M> {
+        pub const fn new() -> Self {
+            Self {
+                events: PubSubChannel::new(),
+                led: LedEffect::new(),
+            }
+        }
+
+        /// The one subscriber [`run`](Self::run) takes, `None` if it was handed out already.
+        pub fn subscriber(&self) -> Option<StatusSubscriber<'_, M>> {
+            self.events.subscriber().ok()
+        }
+
+        /// Never waits, the oldest event is dropped when the queue is full.
+        pub fn publish(&self, event: Event) {
+            self.events.immediate_publisher().publish_immediate(event);
+        }
+
+        /// Shows SOS and parks the calling task, for errors the device cannot recover from.
+        pub async fn fatal(&self) -> ! {
+            self.publish(Event::Fatal);
+            pending().await
+        }
+
+        /// Follows `events` on `led` forever.
+        pub async fn run<P: SetDutyCycle>(
+            &self,
+            events: StatusSubscriber<'_, M>,
+            led: &mut P,
+        ) -> ! {
+            match select(run(events, &self.led), self.led.run(led)).await {
+                Either::First(never) | Either::Second(never) => never,
+            }
+        }
+    }
+
+    impl<M: RawMutex> Default for StatusLed<M> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay(events: &[Event]) -> Vec<Option<Status>> {
+        let mut tracker = StatusTracker::new();
+        events.iter().map(|&event| tracker.update(event)).collect()
+    }
+
+    #[test]
+    fn station_lifecycle() {
+        use Event::*;
+        let changes = replay(&[
+            WifiConnecting,
+            WifiConnecting,
+            WifiConnected,
+            GotIp,
+            LostIp,
+            GotIp,
+            WifiDisconnected,
+            WifiConnected,
+        ]);
+        assert_eq!(
+            changes,
+            [
+                Some(Status::Connecting),
+                None,
+                Some(Status::WaitingForIp),
+                Some(Status::Online),
+                Some(Status::WaitingForIp),
+                Some(Status::Online),
+                Some(Status::Connecting),
+                Some(Status::WaitingForIp),
+            ]
+        );
+    }
+
+    #[test]
+    fn static_address_before_link_event() {
+        use Event::*;
+        let changes = replay(&[WifiConnecting, GotIp, WifiConnected, LostIp]);
+        assert_eq!(
+            changes,
+            [
+                Some(Status::Connecting),
+                Some(Status::Online),
+                // Still online, the address is already there
+                None,
+                Some(Status::WaitingForIp),
+            ]
+        );
+    }
+
+    #[test]
+    fn access_point_and_ble() {
+        use Event::*;
+        let changes = replay(&[
+            BleAdvertising,
+            BleConnected,
+            ApStarted,
+            ApStationConnected,
+            ApStationConnected,
+            ApStationDisconnected,
+            ApStationDisconnected,
+            ApStopped,
+        ]);
+        assert_eq!(
+            changes,
+            [
+                Some(Status::WaitingForPeer),
+                Some(Status::Online),
+                // The access point wins over BLE
+                Some(Status::WaitingForPeer),
+                Some(Status::Online),
+                None,
+                None,
+                Some(Status::WaitingForPeer),
+                // Back to the BLE connection
+                Some(Status::Online),
+            ]
+        );
+    }
+
+    #[test]
+    fn fatal_stays() {
+        let mut tracker = StatusTracker::new();
+        tracker.update(Event::GotIp);
+        assert_eq!(tracker.update(Event::Fatal), Some(Status::Fatal));
+        assert_eq!(tracker.update(Event::WifiConnected), None);
+        assert_eq!(tracker.status().effect(), Effect::SOS);
+    }
+}
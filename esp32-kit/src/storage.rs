@@ -0,0 +1,206 @@
+//! Small settings records kept in flash, e.g. through `esp_storage::FlashStorage`.
+//!
+//! Each record is stored as `magic, length, payload, CRC-16` so erased flash
+//! (all 0xFF), an older layout or a torn write all read back as "nothing saved".
+
+use embedded_storage::{ReadStorage, Storage};
+
+/// Largest payload a record may have.
+pub const MAX_RECORD_LEN: usize = 128;
+
+const HEADER_LEN: usize = 6;
+const CRC_LEN: usize = 2;
+
+/// Settings that can be saved with [`save`] and read back with [`load`].
+pub trait Record: Sized {
+    /// Tells records apart, change it when the layout changes.
+    const MAGIC: u32;
+    /// Encoded length, at most [`MAX_RECORD_LEN`].
+    const LEN: usize;
+
+    /// Writes exactly `LEN` bytes into `buf`.
+    fn encode(&self, buf: &mut [u8]);
+    /// Decodes `LEN` bytes, `None` if they make no sense.
+    fn decode(buf: &[u8]) -> Option<Self>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StorageError<E> {
+    Flash(E),
+    /// `Record::LEN` is larger than [`MAX_RECORD_LEN`].
+    TooLarge,
+}
+
+/// CRC-16/CCITT-FALSE
+pub fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xFFFF;
+    for &byte in bytes {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Reads the record at `offset`, `Ok(None)` if nothing valid is stored there.
+pub fn load<R: Record, S: ReadStorage>(
+    storage: &mut S,
+    offset: u32,
+) -> Result<Option<R>, StorageError<S::Error>> {
+    if R::LEN > MAX_RECORD_LEN {
+        return Err(StorageError::TooLarge);
+    }
+    let mut buf = [0u8; HEADER_LEN + MAX_RECORD_LEN + CRC_LEN];
+    let buf = &mut buf[..HEADER_LEN + R::LEN + CRC_LEN];
+    storage.read(offset, buf).map_err(StorageError::Flash)?;
+
+    let magic = u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]);
+    let len = u16::from_le_bytes([buf[4], buf[5]]) as usize;
+    if magic != R::MAGIC || len != R::LEN {
+        return Ok(None);
+    }
+    let (data, crc) = buf.split_at(HEADER_LEN + R::LEN);
+    if crc16(data) != u16::from_le_bytes([crc[0], crc[1]]) {
+        return Ok(None);
+    }
+    Ok(R::decode(&data[HEADER_LEN..]))
+}
+
+/// Writes `record` at `offset`, the storage takes care of erasing.
+pub fn save<R: Record, S: Storage>(
+    storage: &mut S,
+    offset: u32,
+    record: &R,
+) -> Result<(), StorageError<S::Error>> {
+    if R::LEN > MAX_RECORD_LEN {
+        return Err(StorageError::TooLarge);
+    }
+    let mut buf = [0u8; HEADER_LEN + MAX_RECORD_LEN + CRC_LEN];
+    let buf = &mut buf[..HEADER_LEN + R::LEN + CRC_LEN];
+    buf[..4].copy_from_slice(&R::MAGIC.to_le_bytes());
+    buf[4..6].copy_from_slice(&(R::LEN as u16).to_le_bytes());
+    record.encode(&mut buf[HEADER_LEN..HEADER_LEN + R::LEN]);
+    let crc = crc16(&buf[..HEADER_LEN + R::LEN]);
+    buf[HEADER_LEN + R::LEN..].copy_from_slice(&crc.to_le_bytes());
+    storage.write(offset, buf).map_err(StorageError::Flash)
+}
+
+/// Overwrites the record at `offset` with erased flash, so [`load`] finds nothing there.
+pub fn clear<R: Record, S: Storage>(
+    storage: &mut S,
+    offset: u32,
+) -> Result<(), StorageError<S::Error>> {
+    if R::LEN > MAX_RECORD_LEN {
+        return Err(StorageError::TooLarge);
+    }
+    let erased = [0xFF; HEADER_LEN + MAX_RECORD_LEN + CRC_LEN];
+    storage
+        .write(offset, &erased[..HEADER_LEN + R::LEN + CRC_LEN])
+        .map_err(StorageError::Flash)
+}
+
+/// Flash simulated in RAM, erased to 0xFF.
+#[cfg(test)]
+pub(crate) struct RamStorage(pub [u8; 4096]);
+
+#[cfg(test)]
+impl RamStorage {
+    pub fn new() -> Self {
+        Self([0xFF; 4096])
+    }
+}
+
+#[cfg(test)]
+impl ReadStorage for RamStorage {
+    type Error = ();
+
+    fn read(&mut self, offset: u32, bytes: &mut [u8]) -> Result<(), ()> {
+        let start = offset as usize;
+        bytes.copy_from_slice(self.0.get(start..start + bytes.len()).ok_or(())?);
+        Ok(())
+    }
+
+    fn capacity(&self) -> usize {
+        self.0.len()
+    }
+}
+
+#[cfg(test)]
+impl Storage for RamStorage {
+    fn write(&mut self, offset: u32, bytes: &[u8]) -> Result<(), ()> {
+        let start = offset as usize;
+        self.0
+            .get_mut(start..start + bytes.len())
+            .ok_or(())?
+            .copy_from_slice(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    struct Counter(u32);
+
+    impl Record for Counter {
+        const MAGIC: u32 = 0x434E_5452;
+        const LEN: usize = 4;
+
+        fn encode(&self, buf: &mut [u8]) {
+            buf.copy_from_slice(&self.0.to_le_bytes());
+        }
+
+        fn decode(buf: &[u8]) -> Option<Self> {
+            Some(Self(u32::from_le_bytes(buf.try_into().ok()?)))
+        }
+    }
+
+    #[test]
+    fn crc_check_value() {
+        assert_eq!(crc16(b"123456789"), 0x29B1);
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut flash = RamStorage::new();
+        assert_eq!(load::<Counter, _>(&mut flash, 0x100), Ok(None));
+        save(&mut flash, 0x100, &Counter(42)).unwrap();
+        assert_eq!(load(&mut flash, 0x100), Ok(Some(Counter(42))));
+        save(&mut flash, 0x100, &Counter(7)).unwrap();
+        assert_eq!(load(&mut flash, 0x100), Ok(Some(Counter(7))));
+        assert_eq!(
+            load::<Counter, _>(&mut flash, 4094),
+            Err(StorageError::Flash(()))
+        );
+    }
+
+    #[test]
+    fn cleared_records_read_as_nothing() {
+        let mut flash = RamStorage::new();
+        save(&mut flash, 0, &Counter(42)).unwrap();
+        save(&mut flash, 0x100, &Counter(7)).unwrap();
+        clear::<Counter, _>(&mut flash, 0).unwrap();
+        assert_eq!(load::<Counter, _>(&mut flash, 0), Ok(None));
+        assert_eq!(load(&mut flash, 0x100), Ok(Some(Counter(7))));
+    }
+
+    #[test]
+    fn corruption_reads_as_nothing() {
+        let mut flash = RamStorage::new();
+        save(&mut flash, 0, &Counter(42)).unwrap();
+        flash.0[HEADER_LEN] ^= 0x01;
+        assert_eq!(load::<Counter, _>(&mut flash, 0), Ok(None));
+
+        // Same bytes under another magic belong to a different record
+        save(&mut flash, 0, &Counter(42)).unwrap();
+        flash.0[0] = 0;
+        assert_eq!(load::<Counter, _>(&mut flash, 0), Ok(None));
+    }
+}
@@ -0,0 +1,316 @@
+use crate::adc::AdcCalibration;
+
+// 开尔文转摄氏度
+pub const fn kelvin_to_celsius(kelvin: f64) -> f64 {
+    kelvin - 273.15
+}
+
+// 摄氏度转开尔文
+pub const fn celsius_to_kelvin(celsius: f64) -> f64 {
+    celsius + 273.15
+}
+
+// 4095 for 12-bit ADC
+pub const ADC_MAX: f64 = 4095.0;
+// Top rail of a raw 12-bit reading
+const ADC_RAW_MAX: u16 = 4095;
+
+/// How resistance maps to temperature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Model<'a> {
+    /// B equation: `r0` ohms at `t0` °C with the product B value `beta`.
+    Beta { beta: f64, r0: f64, t0: f64 },
+    /// Steinhart–Hart equation: 1/T = A + B·ln(R) + C·ln(R)³, T in kelvin.
+    SteinhartHart { a: f64, b: f64, c: f64 },
+    /// Datasheet R/T points as `(°C, ohms)`, sorted by temperature.
+    Table(&'a [(f64, f64)]),
+}
+
+impl Model<'_> {
+    /// Fits the Steinhart–Hart coefficients through three `(°C, ohms)` points.
+    pub fn steinhart_hart_from_points(points: [(f64, f64); 3]) -> Self {
+        let [(t1, r1), (t2, r2), (t3, r3)] = points;
+        let (l1, l2, l3) = (libm::log(r1), libm::log(r2), libm::log(r3));
+        let (y1, y2, y3) = (
+            1.0 / celsius_to_kelvin(t1),
+            1.0 / celsius_to_kelvin(t2),
+            1.0 / celsius_to_kelvin(t3),
+        );
+        let g2 = (y2 - y1) / (l2 - l1);
+        let g3 = (y3 - y1) / (l3 - l1);
+        let c = (g3 - g2) / (l3 - l2) / (l1 + l2 + l3);
+        let b = g2 - c * (l1 * l1 + l1 * l2 + l2 * l2);
+        let a = y1 - (b + l1 * l1 * c) * l1;
+        Model::SteinhartHart { a, b, c }
+    }
+
+    /// Temperature in °C for the given thermistor resistance.
+    pub fn temperature(&self, res: f64) -> Result<f64, ThermistorError> {
+        match *self {
+            Model::Beta { beta, r0, t0 } => {
+                // Use libm for `no_std`
+                let ln_value = libm::log(res / r0);
+                let inv_t = (1.0 / celsius_to_kelvin(t0)) + ((1.0 / beta) * ln_value);
+                Ok(kelvin_to_celsius(1.0 / inv_t))
+            }
+            Model::SteinhartHart { a, b, c } => {
+                let ln_r = libm::log(res);
+                let inv_t = a + b * ln_r + c * ln_r * ln_r * ln_r;
+                Ok(kelvin_to_celsius(1.0 / inv_t))
+            }
+            Model::Table(points) => points
+                .windows(2)
+                .find_map(|pair| {
+                    let ((t_a, r_a), (t_b, r_b)) = (pair[0], pair[1]);
+                    let (lo, hi) = if r_a < r_b { (r_a, r_b) } else { (r_b, r_a) };
+                    if res < lo || res > hi {
+                        return None;
+                    }
+                    // R/T curves are close to linear in ln(R)
+                    let (ln_a, ln_b) = (libm::log(r_a), libm::log(r_b));
+                    let frac = (libm::log(res) - ln_a) / (ln_b - ln_a);
+                    Some(t_a + frac * (t_b - t_a))
+                })
+                .ok_or(ThermistorError::OutOfRange),
+        }
+    }
+}
+
+/// Where the thermistor sits in the voltage divider feeding the ADC pin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Divider {
+    /// NTC between 3.3V and the ADC pin, series resistor to GND.
+    NtcHigh,
+    /// Series resistor between 3.3V and the ADC pin, NTC to GND.
+    NtcLow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThermistorError {
+    /// The ADC reads as if the thermistor were disconnected.
+    OpenCircuit,
+    /// The ADC reads as if the thermistor were shorted.
+    ShortCircuit,
+    /// The resistance falls outside the model's table.
+    OutOfRange,
+}
+
+/// A thermistor in a voltage divider read by a (corrected) ADC value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Thermistor<'a> {
+    model: Model<'a>,
+    divider: Divider,
+    series_res: f64,
+    adc_max: f64,
+}
+
+impl<'a> Thermistor<'a> {
+    /// `series_res` is the resistor value that is connected with the thermistor in the divider.
+    pub const fn new(model: Model<'a>, divider: Divider, series_res: f64) -> Self {
+        Self {
+            model,
+            divider,
+            series_res,
+            adc_max: ADC_MAX,
+        }
+    }
+
+    /// Overrides the full-scale ADC reading, 4095 by default.
+    pub const fn with_adc_max(mut self, adc_max: f64) -> Self {
+        self.adc_max = adc_max;
+        self
+    }
+
+    // Errors for a reading at 0 and at full scale
+    fn rail_errors(&self) -> (ThermistorError, ThermistorError) {
+        match self.divider {
+            Divider::NtcHigh => (ThermistorError::OpenCircuit, ThermistorError::ShortCircuit),
+            Divider::NtcLow => (ThermistorError::ShortCircuit, ThermistorError::OpenCircuit),
+        }
+    }
+
+    /// Corrects a raw 12-bit reading with `calibration`, a reading at either rail is an error.
+    ///
+    /// The rails are checked before correcting, see [`AdcCalibration`].
+    pub fn correct(&self, raw: u16, calibration: &AdcCalibration) -> Result<f64, ThermistorError> {
+        let (at_zero, at_max) = self.rail_errors();
+        match raw {
+            0 => Err(at_zero),
+            ADC_RAW_MAX.. => Err(at_max),
+            _ => Ok(calibration.correct(raw)),
+        }
+    }
+
+    pub fn resistance(&self, adc_value: f64) -> Result<f64, ThermistorError> {
+        let (at_zero, at_max) = self.rail_errors();
+        if adc_value <= 0.0 {
+            return Err(at_zero);
+        }
+        if adc_value >= self.adc_max {
+            return Err(at_max);
+        }
+
+        let x = adc_value / (self.adc_max - adc_value);
+        Ok(match self.divider {
+            Divider::NtcHigh => self.series_res / x,
+            Divider::NtcLow => self.series_res * x,
+        })
+    }
+
+    /// Temperature in °C for the given ADC value.
+    pub fn temperature(&self, adc_value: f64) -> Result<f64, ThermistorError> {
+        self.model.temperature(self.resistance(adc_value)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adc::Attenuation;
+
+    // Vishay NTCLE100E3103 (10K, B25/85 = 3977K) R/T table
+    const NTCLE100E3103: &[(f64, f64)] = &[
+        (-40.0, 332_094.0),
+        (-30.0, 175_200.0),
+        (-20.0, 96_358.0),
+        (-10.0, 55_046.0),
+        (0.0, 32_554.0),
+        (10.0, 19_872.0),
+        (20.0, 12_488.0),
+        (25.0, 10_000.0),
+        (30.0, 8_059.0),
+        (40.0, 5_330.0),
+        (50.0, 3_605.0),
+        (60.0, 2_490.0),
+        (70.0, 1_753.0),
+        (80.0, 1_256.0),
+        (90.0, 915.4),
+        (100.0, 677.3),
+    ];
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "{actual} is not within {tolerance} of {expected}"
+        );
+    }
+
+    #[test]
+    fn beta_matches_datasheet_near_reference() {
+        let model = Model::Beta {
+            beta: 3977.0,
+            r0: 10_000.0,
+            t0: 25.0,
+        };
+        for &(t, r) in NTCLE100E3103 {
+            if (0.0..=100.0).contains(&t) {
+                assert_close(model.temperature(r).unwrap(), t, 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn steinhart_hart_matches_datasheet() {
+        let model =
+            Model::steinhart_hart_from_points([(0.0, 32_554.0), (25.0, 10_000.0), (50.0, 3_605.0)]);
+        for &(t, r) in NTCLE100E3103 {
+            assert_close(model.temperature(r).unwrap(), t, 0.1);
+        }
+    }
+
+    #[test]
+    fn table_interpolates_between_points() {
+        let model = Model::Table(NTCLE100E3103);
+        for &(t, r) in NTCLE100E3103 {
+            assert_close(model.temperature(r).unwrap(), t, 1e-9);
+        }
+        let sh =
+            Model::steinhart_hart_from_points([(0.0, 32_554.0), (25.0, 10_000.0), (50.0, 3_605.0)]);
+        for r in [45_000.0, 15_000.0, 7_000.0, 1_500.0] {
+            assert_close(
+                model.temperature(r).unwrap(),
+                sh.temperature(r).unwrap(),
+                0.3,
+            );
+        }
+        assert_eq!(
+            model.temperature(400_000.0),
+            Err(ThermistorError::OutOfRange)
+        );
+        assert_eq!(model.temperature(500.0), Err(ThermistorError::OutOfRange));
+    }
+
+    #[test]
+    fn mid_scale_is_reference_temperature_on_both_sides() {
+        let model = Model::Beta {
+            beta: 3950.0,
+            r0: 10_000.0,
+            t0: 25.0,
+        };
+        for divider in [Divider::NtcHigh, Divider::NtcLow] {
+            let thermistor = Thermistor::new(model, divider, 10_000.0);
+            assert_close(
+                thermistor.resistance(ADC_MAX / 2.0).unwrap(),
+                10_000.0,
+                1e-6,
+            );
+            assert_close(thermistor.temperature(ADC_MAX / 2.0).unwrap(), 25.0, 1e-9);
+        }
+    }
+
+    #[test]
+    fn divider_side_sets_direction() {
+        let model = Model::Table(NTCLE100E3103);
+        let low = Thermistor::new(model, Divider::NtcLow, 10_000.0);
+        let high = Thermistor::new(model, Divider::NtcHigh, 10_000.0);
+        // Warmer means lower NTC resistance, so the low side reads less and the high side more
+        assert!(low.temperature(1000.0).unwrap() > 25.0);
+        assert!(high.temperature(1000.0).unwrap() < 25.0);
+    }
+
+    #[test]
+    fn rails_are_open_or_short_circuits() {
+        let model = Model::Beta {
+            beta: 3950.0,
+            r0: 10_000.0,
+            t0: 25.0,
+        };
+        let low = Thermistor::new(model, Divider::NtcLow, 10_000.0);
+        assert_eq!(low.temperature(0.0), Err(ThermistorError::ShortCircuit));
+        assert_eq!(low.temperature(ADC_MAX), Err(ThermistorError::OpenCircuit));
+
+        let high = Thermistor::new(model, Divider::NtcHigh, 10_000.0).with_adc_max(1023.0);
+        assert_eq!(high.temperature(0.0), Err(ThermistorError::OpenCircuit));
+        assert_eq!(high.temperature(1023.0), Err(ThermistorError::ShortCircuit));
+        assert!(high.temperature(1023.0 / 2.0).is_ok());
+    }
+
+    #[test]
+    fn saturated_raw_readings_are_open_or_short_circuits() {
+        let model = Model::Beta {
+            beta: 3950.0,
+            r0: 10_000.0,
+            t0: 25.0,
+        };
+        let calibration = AdcCalibration::new(Attenuation::_11dB);
+        let low = Thermistor::new(model, Divider::NtcLow, 10_000.0);
+        // A saturated ADC corrects to less than full scale, only the raw reading shows the rail
+        assert!(low.temperature(calibration.correct(4095)).is_ok());
+        assert_eq!(
+            low.correct(4095, &calibration),
+            Err(ThermistorError::OpenCircuit)
+        );
+        assert_eq!(
+            low.correct(0, &calibration),
+            Err(ThermistorError::ShortCircuit)
+        );
+
+        let high = Thermistor::new(model, Divider::NtcHigh, 10_000.0);
+        assert_eq!(
+            high.correct(4095, &calibration),
+            Err(ThermistorError::ShortCircuit)
+        );
+        let mid = high.correct(2048, &calibration).unwrap();
+        assert_eq!(mid, calibration.correct(2048));
+    }
+}
@@ -0,0 +1,321 @@
+//! WiFi station logic that does not need the radio.
+//!
+//! Which known network to join and how long to wait before retrying are plain
+//! functions here, so they can be tested on the host. With the `embassy`
+//! feature, [`manager::WifiManager`] drives a [`manager::Station`], e.g. an
+//! `esp_wifi` controller, with them.
+
+use core::net::Ipv4Addr;
+
+use crate::storage::Record;
+
+#[cfg(feature = "embassy")]
+pub mod manager;
+pub mod portal;
+
+pub const MAX_SSID_LEN: usize = 32;
+pub const MAX_PASSWORD_LEN: usize = 64;
+/// Known networks past this many are never picked.
+pub const MAX_NETWORKS: usize = 16;
+
+/// A network the device may join.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Network<'a> {
+    pub ssid: &'a str,
+    /// Empty for an open network.
+    pub password: &'a str,
+}
+
+/// Network settings chosen at runtime, kept in flash with [`crate::storage`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Credentials {
+    pub ssid: heapless::String<MAX_SSID_LEN>,
+    pub password: heapless::String<MAX_PASSWORD_LEN>,
+}
+
+impl Credentials {
+    pub fn network(&self) -> Network<'_> {
+        Network {
+            ssid: &self.ssid,
+            password: &self.password,
+        }
+    }
+}
+
+impl Record for Credentials {
+    const MAGIC: u32 = u32::from_le_bytes(*b"WIF1");
+    // Length prefixed, zero padded SSID and password
+    const LEN: usize = 2 + MAX_SSID_LEN + MAX_PASSWORD_LEN;
+
+    fn encode(&self, buf: &mut [u8]) {
+        buf.fill(0);
+        let (ssid, password) = buf.split_at_mut(1 + MAX_SSID_LEN);
+        ssid[0] = self.ssid.len() as u8;
+        ssid[1..1 + self.ssid.len()].copy_from_slice(self.ssid.as_bytes());
+        password[0] = self.password.len() as u8;
+        password[1..1 + self.password.len()].copy_from_slice(self.password.as_bytes());
+    }
+
+    fn decode(buf: &[u8]) -> Option<Self> {
+        let (ssid, password) = buf.split_at(1 + MAX_SSID_LEN);
+        fn field(field: &[u8]) -> Option<&str> {
+            let text = field.get(1..1 + field[0] as usize)?;
+            core::str::from_utf8(text).ok()
+        }
+        let ssid = field(ssid).filter(|ssid| !ssid.is_empty())?;
+        Some(Self {
+            ssid: ssid.try_into().ok()?,
+            password: field(password)?.try_into().ok()?,
+        })
+    }
+}
+
+/// One result of a scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessPoint {
+    pub ssid: heapless::String<MAX_SSID_LEN>,
+    /// Signal strength in dBm.
+    pub rssi: i8,
+    pub channel: u8,
+    pub secured: bool,
+}
+
+/// Index into `known` of the network to try on the `attempt`th try in a row.
+///
+/// Known networks seen in `scan` come first, strongest signal first, with the
+/// order of `known` breaking ties. Failed attempts step through them, and once
+/// none is in sight, through the first [`MAX_NETWORKS`] of `known` in order,
+/// as they may be hidden.
+pub fn select_network<'s>(
+    known: &[Network<'_>],
+    scan: impl IntoIterator<Item = (&'s str, i8)>,
+    attempt: usize,
+) -> Option<usize> {
+    if known.is_empty() {
+        return None;
+    }
+    // Best signal of every known network
+    let mut best = [None::<i8>; MAX_NETWORKS];
+    for (ssid, rssi) in scan {
+        if let Some(i) = known.iter().take(best.len()).position(|n| n.ssid == ssid) {
+            best[i] = Some(best[i].map_or(rssi, |b| b.max(rssi)));
+        }
+    }
+    let mut visible = [0usize; MAX_NETWORKS];
+    let mut count = 0;
+    for (i, rssi) in best.iter().enumerate() {
+        if rssi.is_some() {
+            visible[count] = i;
+            count += 1;
+        }
+    }
+    let visible = &mut visible[..count];
+    // Equal signals keep the order of `known`
+    visible.sort_unstable_by_key(|&i| (core::cmp::Reverse(best[i]), i));
+    if visible.is_empty() {
+        Some(attempt % known.len().min(MAX_NETWORKS))
+    } else {
+        Some(visible[attempt % visible.len()])
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackoffConfig {
+    pub initial_ms: u64,
+    pub max_ms: u64,
+    /// Up to this fraction is taken off every delay at random, so devices that
+    /// lost the same AP do not all come back at the same moment.
+    pub jitter: f32,
+}
+
+impl Default for BackoffConfig {
+    fn default() -> Self {
+        Self {
+            initial_ms: 1000,
+            max_ms: 60_000,
+            jitter: 0.25,
+        }
+    }
+}
+
+/// Exponential backoff between retries.
+#[derive(Debug, Clone, Copy)]
+pub struct Backoff {
+    config: BackoffConfig,
+    failures: u32,
+}
+
+impl Backoff {
+    pub fn new(config: BackoffConfig) -> Self {
+        Self {
+            config,
+            failures: 0,
+        }
+    }
+
+    /// Failures since the last success.
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn reset(&mut self) {
+        self.failures = 0;
+    }
+
+    /// Records a failure and returns how long to wait before the next try.
+    ///
+    /// `random` is any random number, e.g. from `esp_hal::rng::Rng`.
+    pub fn next_delay_ms(&mut self, random: u32) -> u64 {
+        let base = self
+            .config
+            .initial_ms
+            .saturating_mul(1u64 << self.failures.min(32))
+            .min(self.config.max_ms);
+        self.failures = self.failures.saturating_add(1);
+        let cut = self.config.jitter.clamp(0.0, 1.0) * (random as f32 / u32::MAX as f32);
+        libm::roundf(base as f32 * (1.0 - cut)) as u64
+    }
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self::new(BackoffConfig::default())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    /// Trying `network`, an index into the list the manager was given.
+    Connecting {
+        network: usize,
+    },
+    Connected {
+        network: usize,
+        rssi: Option<i8>,
+    },
+    Disconnected,
+    GotIp(Ipv4Addr),
+    LostIp,
+}
+
+impl From<Event> for crate::status::Event {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Connecting { .. } => Self::WifiConnecting,
+            Event::Connected { .. } => Self::WifiConnected,
+            Event::Disconnected => Self::WifiDisconnected,
+            Event::GotIp(_) => Self::GotIp,
+            Event::LostIp => Self::LostIp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KNOWN: [Network; 3] = [
+        Network {
+            ssid: "home",
+            password: "secret",
+        },
+        Network {
+            ssid: "office",
+            password: "secret",
+        },
+        Network {
+            ssid: "phone",
+            password: "",
+        },
+    ];
+
+    #[test]
+    fn strongest_known_network_first() {
+        let scan = [
+            ("neighbour", -30),
+            ("phone", -70),
+            ("office", -55),
+            ("home", -80),
+            // Second AP of the same network
+            ("home", -50),
+        ];
+        let picks: Vec<_> = (0..4)
+            .map(|attempt| select_network(&KNOWN, scan, attempt))
+            .collect();
+        // Failed attempts move on to the next best, then start over
+        assert_eq!(picks, [Some(0), Some(1), Some(2), Some(0)]);
+
+        // Equal signals keep the order of the list
+        let scan = [("phone", -60), ("office", -60)];
+        assert_eq!(select_network(&KNOWN, scan, 0), Some(1));
+    }
+
+    #[test]
+    fn hidden_networks_in_list_order() {
+        let picks: Vec<_> = (0..4)
+            .map(|attempt| select_network(&KNOWN, [("neighbour", -40)], attempt))
+            .collect();
+        assert_eq!(picks, [Some(0), Some(1), Some(2), Some(0)]);
+        assert_eq!(select_network(&[], [("home", -40)], 0), None);
+
+        // Networks past MAX_NETWORKS are never tried
+        let many = [KNOWN[0]; MAX_NETWORKS + 4];
+        let picks: Vec<_> = (0..many.len())
+            .map(|attempt| select_network(&many, [], attempt))
+            .collect();
+        assert!(picks.iter().all(|&pick| pick < Some(MAX_NETWORKS)));
+        assert_eq!(picks[MAX_NETWORKS], Some(0));
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_limit() {
+        let mut backoff = Backoff::default();
+        let delays: Vec<_> = (0..8).map(|_| backoff.next_delay_ms(0)).collect();
+        assert_eq!(
+            delays,
+            [1000, 2000, 4000, 8000, 16_000, 32_000, 60_000, 60_000]
+        );
+        assert_eq!(backoff.failures(), 8);
+        backoff.reset();
+        assert_eq!(backoff.next_delay_ms(0), 1000);
+
+        // Thousands of failures must not overflow
+        for _ in 0..1000 {
+            backoff.next_delay_ms(0);
+        }
+        assert_eq!(backoff.next_delay_ms(0), 60_000);
+    }
+
+    #[test]
+    fn credentials_round_trip() {
+        let mut flash = crate::storage::RamStorage::new();
+        let credentials = Credentials {
+            ssid: "Café 5G".try_into().unwrap(),
+            password: "p4ssw0rd!".try_into().unwrap(),
+        };
+        crate::storage::save(&mut flash, 0, &credentials).unwrap();
+        let loaded: Credentials = crate::storage::load(&mut flash, 0).unwrap().unwrap();
+        assert_eq!(loaded, credentials);
+        assert_eq!(loaded.network().ssid, "Café 5G");
+
+        // Full length fields fit
+        let credentials = Credentials {
+            ssid: "s".repeat(MAX_SSID_LEN).as_str().try_into().unwrap(),
+            password: "p".repeat(MAX_PASSWORD_LEN).as_str().try_into().unwrap(),
+        };
+        crate::storage::save(&mut flash, 0, &credentials).unwrap();
+        assert_eq!(crate::storage::load(&mut flash, 0), Ok(Some(credentials)));
+    }
+
+    #[test]
+    fn jitter_shortens_delays() {
+        let mut backoff = Backoff::default();
+        assert_eq!(backoff.next_delay_ms(u32::MAX), 750);
+        assert_eq!(backoff.next_delay_ms(u32::MAX / 2), 1750);
+        let config = BackoffConfig {
+            jitter: 0.0,
+            ..BackoffConfig::default()
+        };
+        assert_eq!(Backoff::new(config).next_delay_ms(u32::MAX), 1000);
+    }
+}
@@ -0,0 +1,151 @@
+//! Keeps a station connected to the best known network.
+//!
+//! ```ignore
+//! static WIFI: WifiManager<CriticalSectionRawMutex> = WifiManager::new();
+//!
+//! #[embassy_executor::task]
+//! async fn wifi_task(mut station: EspStation, mut rng: Rng) {
+//!     WIFI.run(&mut station, &NETWORKS, BackoffConfig::default(), || rng.random()).await
+//! }
+//! ```
+
+use core::cell::Cell;
+use core::future::Future;
+use core::net::Ipv4Addr;
+
+use embassy_futures::select::{select, Either};
+use embassy_sync::blocking_mutex::raw::RawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::pubsub::{PubSubChannel, Subscriber};
+use embassy_time::{Duration, Timer};
+
+use super::{select_network, AccessPoint, Backoff, BackoffConfig, Event, Network};
+
+/// Most scan results looked at.
+pub const MAX_SCAN: usize = 16;
+/// Most tasks that can follow the events.
+pub const MAX_SUBSCRIBERS: usize = 4;
+
+const EVENT_QUEUE: usize = 8;
+// How often the signal strength is read while connected
+const RSSI_INTERVAL: Duration = Duration::from_secs(10);
+
+pub type EventSubscriber<'a, M> = Subscriber<'a, M, Event, EVENT_QUEUE, MAX_SUBSCRIBERS, 0>;
+
+/// The radio side of a WiFi station, e.g. an `esp_wifi::wifi::WifiController`.
+pub trait Station {
+    type Error: core::fmt::Debug;
+
+    /// Access points in range.
+    fn scan(
+        &mut self,
+    ) -> impl Future<Output = Result<heapless::Vec<AccessPoint, MAX_SCAN>, Self::Error>>;
+    /// Joins `network`, starting the radio first if needed.
+    fn connect(&mut self, network: &Network<'_>) -> impl Future<Output = Result<(), Self::Error>>;
+    /// Returns once the connection is lost.
+    fn wait_disconnected(&mut self) -> impl Future<Output = ()>;
+    /// Signal strength of the current connection in dBm.
+    fn rssi(&mut self) -> Option<i8>;
+}
+
+pub struct WifiManager<M: RawMutex> {
+    events: PubSubChannel<M, Event, EVENT_QUEUE, MAX_SUBSCRIBERS, 0>,
+    rssi: Mutex<M, Cell<Option<i8>>>,
+    address: Mutex<M, Cell<Option<Ipv4Addr>>>,
+}
+
+impl<M: RawMutex> WifiManager<M> {
+    pub const fn new() -> Self {
+        Self {
+            events: PubSubChannel::new(),
+            rssi: Mutex::new(Cell::new(None)),
+            address: Mutex::new(Cell::new(None)),
+        }
+    }
+
+    /// Follows the events from now on, `None` if [`MAX_SUBSCRIBERS`] already do.
+    pub fn subscriber(&self) -> Option<EventSubscriber<'_, M>> {
+        self.events.subscriber().ok()
+    }
+
+    /// Signal strength while connected, updated every few seconds.
+    pub fn rssi(&self) -> Option<i8> {
+        self.rssi.lock(Cell::get)
+    }
+
+    pub fn address(&self) -> Option<Ipv4Addr> {
+        self.address.lock(Cell::get)
+    }
+
+    /// Reports the IPv4 address of the network stack, e.g. from `Stack::config_v4`.
+    ///
+    /// The manager does not know the stack, so whoever runs it calls this on every change.
+    pub fn set_address(&self, address: Option<Ipv4Addr>) {
+        let before = self.address.lock(|cell| cell.replace(address));
+        match address {
+            Some(address) if before != Some(address) => self.publish(Event::GotIp(address)),
+            None if before.is_some() => self.publish(Event::LostIp),
+            _ => {}
+        }
+    }
+
+    fn publish(&self, event: Event) {
+        // Slow subscribers miss the oldest events rather than stall the manager
+        self.events.immediate_publisher().publish_immediate(event);
+    }
+
+    /// Keeps `station` connected to one of `networks` forever.
+    ///
+    /// Every attempt scans first and picks the strongest known network, failed
+    /// attempts back off according to `backoff` with jitter from `random`.
+    pub async fn run<S: Station>(
+        &self,
+        station: &mut S,
+        networks: &[Network<'_>],
+        backoff: BackoffConfig,
+        mut random: impl FnMut() -> u32,
+    ) -> ! {
+        let mut backoff = Backoff::new(backoff);
+        loop {
+            // A failed scan still leaves the hidden network fallback
+            let scan = station.scan().await.unwrap_or_default();
+            let visible = scan.iter().map(|ap| (ap.ssid.as_str(), ap.rssi));
+            let Some(network) = select_network(networks, visible, backoff.failures() as usize)
+            else {
+                // Nothing to connect to, ever
+                core::future::pending::<()>().await;
+                continue;
+            };
+
+            self.publish(Event::Connecting { network });
+            if station.connect(&networks[network]).await.is_err() {
+                let delay = backoff.next_delay_ms(random());
+                Timer::after(Duration::from_millis(delay)).await;
+                continue;
+            }
+            backoff.reset();
+            let rssi = station.rssi();
+            self.rssi.lock(|cell| cell.set(rssi));
+            self.publish(Event::Connected { network, rssi });
+
+            loop {
+                let refresh = Timer::after(RSSI_INTERVAL);
+                match select(station.wait_disconnected(), refresh).await {
+                    Either::First(()) => break,
+                    Either::Second(()) => {
+                        let rssi = station.rssi();
+                        self.rssi.lock(|cell| cell.set(rssi));
+                    }
+                }
+            }
+            self.rssi.lock(|cell| cell.set(None));
+            self.publish(Event::Disconnected);
+        }
+    }
+}
+
+impl<M: RawMutex> Default for WifiManager<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,339 @@
+//! The provisioning page of a captive portal and its form.
+//!
+//! The page lists the networks found by a scan and posts
+//! `application/x-www-form-urlencoded` credentials back, which
+//! [`parse_form`] turns into [`Credentials`] to be saved.
+
+use core::fmt::{self, Write};
+
+use super::{AccessPoint, Credentials};
+
+/// Most networks listed on the page.
+pub const MAX_LISTED: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormError {
+    MissingSsid,
+    SsidTooLong,
+    /// WPA2 needs at least 8 characters, leave it empty for an open network.
+    PasswordTooShort,
+    PasswordTooLong,
+    /// Broken percent encoding or not UTF-8.
+    Malformed,
+}
+
+impl FormError {
+    /// What to tell the user.
+    pub fn message(self) -> &'static str {
+        match self {
+            FormError::MissingSsid => "Choose a network or enter its name",
+            FormError::SsidTooLong => "Network names are at most 32 bytes long",
+            FormError::PasswordTooShort => "The password needs at least 8 characters",
+            FormError::PasswordTooLong => "The password is at most 64 characters long",
+            FormError::Malformed => "The form could not be read, please try again",
+        }
+    }
+}
+
+impl fmt::Display for FormError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+/// Reads the `ssid`, `hidden` and `password` fields of a submitted form.
+///
+/// A name typed into `hidden`, for a network that is not in the list, wins
+/// over the one picked in `ssid`. Other fields are ignored.
+pub fn parse_form(body: &[u8]) -> Result<Credentials, FormError> {
+    let mut picked = None;
+    let mut hidden = None;
+    let mut password = None;
+    for pair in body.split(|&b| b == b'&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = match pair.iter().position(|&b| b == b'=') {
+            Some(i) => (&pair[..i], &pair[i + 1..]),
+            None => (pair, &[][..]),
+        };
+        match key {
+            b"ssid" => picked = Some(value),
+            b"hidden" => hidden = Some(value),
+            b"password" => password = Some(value),
+            _ => {}
+        }
+    }
+
+    let mut credentials = Credentials::default();
+    let ssid = hidden
+        .filter(|hidden| !hidden.is_empty())
+        .or(picked)
+        .ok_or(FormError::MissingSsid)?;
+    url_decode(ssid, &mut credentials.ssid).map_err(|e| e.unwrap_or(FormError::SsidTooLong))?;
+    if credentials.ssid.is_empty() {
+        return Err(FormError::MissingSsid);
+    }
+    url_decode(password.unwrap_or_default(), &mut credentials.password)
+        .map_err(|e| e.unwrap_or(FormError::PasswordTooLong))?;
+    if (1..8).contains(&credentials.password.chars().count()) {
+        return Err(FormError::PasswordTooShort);
+    }
+    Ok(credentials)
+}
+
+// Err(None) when `out` is too small
+fn url_decode<const N: usize>(
+    value: &[u8],
+    out: &mut heapless::String<N>,
+) -> Result<(), Option<FormError>> {
+    let mut bytes = heapless::Vec::<u8, N>::new();
+    let mut i = 0;
+    while i < value.len() {
+        let byte = match value[i] {
+            b'+' => b' ',
+            b'%' => {
+                let hex = value.get(i + 1..i + 3).ok_or(Some(FormError::Malformed))?;
+                // from_str_radix alone would also take a sign, e.g. "%+5"
+                if !hex.iter().all(u8::is_ascii_hexdigit) {
+                    return Err(Some(FormError::Malformed));
+                }
+                let hex = core::str::from_utf8(hex).map_err(|_| Some(FormError::Malformed))?;
+                i += 2;
+                u8::from_str_radix(hex, 16).map_err(|_| Some(FormError::Malformed))?
+            }
+            byte => byte,
+        };
+        bytes.push(byte).map_err(|_| None)?;
+        i += 1;
+    }
+    let text = core::str::from_utf8(&bytes).map_err(|_| Some(FormError::Malformed))?;
+    out.clear();
+    out.push_str(text).map_err(|_| None)
+}
+
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                '\'' => f.write_str("&#39;")?,
+                c => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Ends the page, always written so the form can be sent
+const FORM_END: &str = concat!(
+    "<p><label>Other network <input name=\"hidden\" maxlength=\"32\"></label></p>",
+    "<p><label>Password ",
+    "<input name=\"password\" type=\"password\" maxlength=\"64\"></label></p>",
+    "<p><button type=\"submit\">Connect</button></p></form></body></html>",
+);
+const NO_NETWORKS: &str = "<p>No networks found.</p>";
+
+// One network of the list
+struct Entry<'a>(&'a AccessPoint);
+
+impl fmt::Display for Entry<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            concat!(
+                "<p><label><input type=\"radio\" name=\"ssid\" value=\"{0}\"> ",
+                "{0} ({1} dBm{2})</label></p>",
+            ),
+            Escaped(&self.0.ssid),
+            self.0.rssi,
+            if self.0.secured { ", secured" } else { "" }
+        )
+    }
+}
+
+// Counts what would be written
+struct Len(usize);
+
+impl Write for Len {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+/// Writes the provisioning page, `message` is shown above the form, e.g. a [`FormError`].
+///
+/// Every network is listed once, strongest first, and the form posts to `action`.
+/// Networks that do not fit into `out` are left out, so escaped SSIDs can never
+/// push the form off the page. Fails only if the page without any network does
+/// not fit.
+pub fn write_page<const N: usize>(
+    out: &mut heapless::String<N>,
+    networks: &[AccessPoint],
+    message: Option<&str>,
+    action: &str,
+) -> fmt::Result {
+    out.write_str(concat!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\">",
+        "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">",
+        "<title>WiFi setup</title></head><body><h1>WiFi setup</h1>",
+    ))?;
+    if let Some(message) = message {
+        write!(out, "<p><strong>{}</strong></p>", Escaped(message))?;
+    }
+    write!(out, "<form method=\"post\" action=\"{}\">", Escaped(action))?;
+
+    // Strongest access point of every network, strongest network first
+    let mut order = [0usize; MAX_LISTED];
+    let mut count = 0;
+    for (i, ap) in networks.iter().enumerate() {
+        if ap.ssid.is_empty() || count == MAX_LISTED {
+            continue;
+        }
+        match order[..count]
+            .iter()
+            .position(|&j| networks[j].ssid == ap.ssid)
+        {
+            Some(k) if networks[order[k]].rssi < ap.rssi => order[k] = i,
+            Some(_) => {}
+            None => {
+                order[count] = i;
+                count += 1;
+            }
+        }
+    }
+    let order = &mut order[..count];
+    order.sort_unstable_by_key(|&i| (core::cmp::Reverse(networks[i].rssi), i));
+
+    let mut listed = 0;
+    for &i in order.iter() {
+        let entry = Entry(&networks[i]);
+        let mut len = Len(0);
+        write!(len, "{}", entry)?;
+        // Room for the end of the form, and the note in case nothing fits
+        let reserved = FORM_END.len() + if listed == 0 { NO_NETWORKS.len() } else { 0 };
+        if out.len() + len.0 + reserved > N {
+            continue;
+        }
+        write!(out, "{}", entry)?;
+        listed += 1;
+    }
+    if listed == 0 {
+        out.write_str(NO_NETWORKS)?;
+    }
+    out.write_str(FORM_END)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ap(ssid: &str, rssi: i8, secured: bool) -> AccessPoint {
+        AccessPoint {
+            ssid: ssid.try_into().unwrap(),
+            rssi,
+            channel: 6,
+            secured,
+        }
+    }
+
+    #[test]
+    fn form_fields() {
+        let credentials = parse_form(b"ssid=Home+WiFi&password=s%C3%A9cret%26%3D42").unwrap();
+        assert_eq!(credentials.ssid, "Home WiFi");
+        assert_eq!(credentials.password, "sécret&=42");
+
+        // A typed name wins, unknown fields and an empty password are fine
+        let credentials = parse_form(b"ssid=Home&hidden=Attic&remember=on&password=").unwrap();
+        assert_eq!(credentials.ssid, "Attic");
+        assert_eq!(credentials.password, "");
+        let credentials = parse_form(b"hidden=&ssid=Home").unwrap();
+        assert_eq!(credentials.ssid, "Home");
+    }
+
+    #[test]
+    fn form_errors() {
+        assert_eq!(
+            parse_form(b"password=secret123"),
+            Err(FormError::MissingSsid)
+        );
+        assert_eq!(parse_form(b"ssid=&hidden="), Err(FormError::MissingSsid));
+        assert_eq!(parse_form(b""), Err(FormError::MissingSsid));
+        assert_eq!(
+            parse_form(b"ssid=Home&password=short"),
+            Err(FormError::PasswordTooShort)
+        );
+        let long = format!("ssid={}", "x".repeat(33));
+        assert_eq!(parse_form(long.as_bytes()), Err(FormError::SsidTooLong));
+        let long = format!("ssid=Home&password={}", "x".repeat(65));
+        assert_eq!(parse_form(long.as_bytes()), Err(FormError::PasswordTooLong));
+        // Percent escapes count once toward the limit
+        let exact = format!("ssid={}", "%41".repeat(32));
+        assert_eq!(parse_form(exact.as_bytes()).unwrap().ssid.len(), 32);
+        assert_eq!(parse_form(b"ssid=Home%2"), Err(FormError::Malformed));
+        assert_eq!(parse_form(b"ssid=Home%zz"), Err(FormError::Malformed));
+        assert_eq!(parse_form(b"ssid=Home%+5"), Err(FormError::Malformed));
+        assert_eq!(parse_form(b"ssid=Home%-0"), Err(FormError::Malformed));
+        assert_eq!(parse_form(b"ssid=%FF%FE"), Err(FormError::Malformed));
+    }
+
+    #[test]
+    fn page_lists_networks_once_strongest_first() {
+        let networks = [
+            ap("Office", -70, true),
+            ap("<script>", -60, false),
+            ap("", -40, true),
+            ap("Home", -50, true),
+            ap("Office", -45, true),
+        ];
+        let mut page = heapless::String::<4096>::new();
+        write_page(
+            &mut page,
+            &networks,
+            Some(FormError::PasswordTooShort.message()),
+            "/connect",
+        )
+        .unwrap();
+        let office = page.find("value=\"Office\"").unwrap();
+        let home = page.find("value=\"Home\"").unwrap();
+        let script = page.find("&lt;script&gt;").unwrap();
+        assert!(office < home && home < script);
+        assert_eq!(page.matches("name=\"ssid\"").count(), 3);
+        assert!(page.contains("(-45 dBm, secured)"));
+        assert!(!page.contains("<script>"));
+        assert!(page.contains("<strong>The password needs at least 8 characters</strong>"));
+        assert!(page.contains("action=\"/connect\""));
+
+        let mut page = heapless::String::<4096>::new();
+        write_page(&mut page, &[], None, "/").unwrap();
+        assert!(page.contains("No networks found."));
+        assert!(!page.contains("<strong>"));
+    }
+
+    #[test]
+    fn hostile_ssids_never_push_the_form_off_the_page() {
+        // Neighbours can pick SSIDs that escape to six times their length
+        let networks: Vec<AccessPoint> = (0..16)
+            .map(|i| {
+                let ssid: String = core::iter::repeat_n('"', 31)
+                    .chain([(b'a' + i) as char])
+                    .collect();
+                ap(&ssid, -40 - i as i8, true)
+            })
+            .collect();
+        let mut page = heapless::String::<4096>::new();
+        write_page(&mut page, &networks, Some("Pick one"), "/").unwrap();
+        assert!(page.ends_with(FORM_END));
+        let listed = page.matches("name=\"ssid\"").count();
+        assert!((1..16).contains(&listed), "{listed} listed");
+        // The strongest ones made it
+        assert!(page.contains("&quot;a\""));
+
+        // Not even the form fits
+        let mut page = heapless::String::<256>::new();
+        assert!(write_page(&mut page, &networks, None, "/").is_err());
+    }
+}
@@ -1,8 +1,7 @@
 #![no_std]
 #![no_main]
 
-use embedded_hal::pwm::SetDutyCycle;
-use esp_hal::clock::CpuClock;
+use esp32_kit::servo::{Calibration, PwmServo, Servo};
 use esp_hal::delay::Delay;
 use esp_hal::ledc::channel::ChannelIFace;
 use esp_hal::ledc::timer::TimerIFace;
@@ -11,21 +10,15 @@ use esp_hal::main;
 use esp_hal::time::Rate;
 //use esp_println as _;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[main]
 fn main() -> ! {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
-    let servo = peripherals.GPIO33;
+    let servo_pin = peripherals.GPIO33;
     let ledc = Ledc::new(peripherals.LEDC);
 
     let mut hstimer0 = ledc.timer::<HighSpeed>(timer::Number::Timer0);
@@ -37,7 +30,7 @@ fn main() -> ! {
         })
         .unwrap();
 
-    let mut channel0 = ledc.channel(channel::Number::Channel0, servo);
+    let mut channel0 = ledc.channel(channel::Number::Channel0, servo_pin);
     channel0
         .configure(channel::config::Config {
             timer: &hstimer0,
@@ -48,39 +41,20 @@ fn main() -> ! {
 
     let delay = Delay::new();
 
-    let max_duty_cycle = channel0.max_duty_cycle() as u32;
-
-    // Minimum duty (2.5%)
-    // For 12bit -> 25 * 4096 /1000 => ~ 102
-    let min_duty = (25 * max_duty_cycle) / 1000;
-    // Maximum duty (12.5%)
-    // For 12bit -> 125 * 4096 /1000 => 512
-    let max_duty = (125 * max_duty_cycle) / 1000;
-    // 512 - 102 => 410
-    let duty_gap = max_duty - min_duty;
+    // 0.5 ms..2.5 ms for 0°..180°, a 12 bit channel moves in 4.9 µs steps
+    let mut servo = PwmServo::new(channel0, Calibration::default()).unwrap();
 
     loop {
         for deg in 0..=180 {
-            let duty = duty_from_angle(deg, min_duty, duty_gap);
-            channel0.set_duty_cycle(duty).unwrap();
+            servo.set_angle(deg as f32).unwrap();
             delay.delay_millis(10);
         }
         delay.delay_millis(500);
 
         for deg in (0..=180).rev() {
-            let duty = duty_from_angle(deg, min_duty, duty_gap);
-            channel0.set_duty_cycle(duty).unwrap();
+            servo.set_angle(deg as f32).unwrap();
             delay.delay_millis(10);
         }
         delay.delay_millis(500);
     }
 }
-
-fn duty_from_angle(deg: u32, min_duty: u32, duty_gap: u32) -> u16 {
-    let duty = min_duty + ((deg * duty_gap) / 180);
-    duty as u16
-}
-
-
-
-
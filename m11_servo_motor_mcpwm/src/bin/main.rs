@@ -1,7 +1,7 @@
 #![no_std]
 #![no_main]
 
-use esp_hal::clock::CpuClock;
+use esp32_kit::servo::{Calibration, PwmServo, Servo};
 use esp_hal::delay::Delay;
 use esp_hal::main;
 use esp_hal::mcpwm::operator::PwmPinConfig;
@@ -10,19 +10,13 @@ use esp_hal::mcpwm::{McPwm, PeripheralClockConfig};
 use esp_hal::time::Rate;
 //use esp_println as _;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[main]
 fn main() -> ! {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     let delay = Delay::new();
 
@@ -32,7 +26,7 @@ fn main() -> ! {
     // connect operator0 to timer0
     mcpwm.operator0.set_timer(&mcpwm.timer0);
     // connect operator0 to pin
-    let mut pwm_pin = mcpwm
+    let pwm_pin = mcpwm
         .operator0
         .with_pin_a(peripherals.GPIO33, PwmPinConfig::UP_ACTIVE_HIGH);
 
@@ -43,17 +37,13 @@ fn main() -> ! {
         .unwrap();
     mcpwm.timer0.start(timer_clock_cfg);
 
-    loop {
-        // 0 degree (2.5% of 20_000 => 500)
-        pwm_pin.set_timestamp(500);
-        delay.delay_millis(1500);
-
-        // 90 degree (7.5% of 20_000 => 1500)
-        pwm_pin.set_timestamp(1500);
-        delay.delay_millis(1500);
+    // One timestamp per µs, so pulses are exact
+    let mut servo = PwmServo::new(pwm_pin, Calibration::default()).unwrap();
 
-        // 180 degree (12.5% of 20_000 => 2500)
-        pwm_pin.set_timestamp(2500);
-        delay.delay_millis(1500);
+    loop {
+        for deg in [0.0, 90.0, 180.0] {
+            servo.set_angle(deg).unwrap();
+            delay.delay_millis(1500);
+        }
     }
-}
\ No newline at end of file
+}
@@ -1,17 +1,18 @@
 #![no_std]
 #![no_main]
 
+use esp32_kit::adc::{self, AdcCalibration};
+use esp32_kit::filter::{oversample, Ema, Filter, Hysteresis, Median};
 use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
-use esp_hal::clock::CpuClock;
 use esp_hal::delay::Delay;
 use esp_hal::gpio::{Level, Output, OutputConfig};
 use esp_hal::main;
 use esp_println as _;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
+// Correcting ADC non-linearity issue, see esp32-kit/calibration
+const ADC_CALIBRATION: AdcCalibration = AdcCalibration::new(adc::Attenuation::_11dB);
+// Readings averaged into every sample
+const OVERSAMPLING: usize = 8;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
@@ -19,8 +20,7 @@ esp_bootloader_esp_idf::esp_app_desc!();
 fn main() -> ! {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     let mut led = Output::new(peripherals.GPIO33, Level::Low, OutputConfig::default());
 
@@ -30,16 +30,25 @@ fn main() -> ! {
     let mut adc2 = Adc::new(peripherals.ADC2, adc2_config);
     let delay = Delay::new();
 
+    // Spike rejection, then smoothing
+    let mut light = Median::<5>::new().then(Ema::new(0.3));
+    // Switch on above 2100 and off below 1900 so the LED does not flicker around 2000
+    let mut threshold = Hysteresis::new(1900.0, 2100.0);
+
     loop {
-        let pin_value: u16 = nb::block!(adc2.read_oneshot(&mut pin)).unwrap();
+        let pin_value = oversample(OVERSAMPLING, || {
+            nb::block!(adc2.read_oneshot(&mut pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        })
+        .unwrap();
+        let pin_value = light.update(pin_value);
         esp_println::println!("{}", pin_value);
 
-        if pin_value > 2000 {
+        if threshold.update(pin_value) {
             led.set_high();
         } else {
             led.set_low();
         }
 
-        delay.delay_millis(500);
+        delay.delay_millis(100);
     }
 }
@@ -4,7 +4,6 @@
 use blocking_network_stack::Stack;
 use defmt::info;
 use embedded_io::{Read, Write};
-use esp_hal::clock::CpuClock;
 use esp_hal::delay::Delay;
 use esp_hal::peripherals::Peripherals;
 use esp_hal::rng::Rng;
@@ -20,11 +19,6 @@ use smoltcp::wire::{DhcpOption, IpAddress};
 use esp_wifi::wifi::AccessPointInfo;
 use alloc::vec::Vec;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 extern crate alloc;
 
 const SSID: &str = env!("SSID");
@@ -103,8 +97,7 @@ fn timestamp() -> smoltcp::time::Instant {
 }
 
 fn init_hardware() -> Peripherals {
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
     esp_alloc::heap_allocator!(size: 72 * 1024);
     peripherals
 }
@@ -213,4 +206,3 @@ fn http_loop(
         delay.delay_millis(1000);
     }
 }
-
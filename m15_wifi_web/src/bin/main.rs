@@ -3,16 +3,10 @@
 
 use defmt::info;
 use embassy_executor::Spawner;
-use esp_hal::clock::CpuClock;
 use esp_hal::rng::Rng;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 extern crate alloc;
 
 esp_bootloader_esp_idf::esp_app_desc!();
@@ -24,13 +18,11 @@ use m15_wifi_web as lib;
 async fn main(spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     esp_alloc::heap_allocator!(size: 72 * 1024);
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -42,6 +34,9 @@ async fn main(spawner: Spawner) {
     // )
     // .unwrap();
 
+    // On-board LED shows the connection status
+    lib::status::start_status_led(peripherals.LEDC, peripherals.GPIO2, &spawner);
+
     let rng = Rng::new(peripherals.RNG);
     let esp_wifi_ctrl = &*lib::mk_static!(
         EspWifiController<'static>,
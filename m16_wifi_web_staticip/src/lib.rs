@@ -4,12 +4,5 @@
 pub mod web;
 pub mod wifi;
 
-#[macro_export]
-macro_rules! mk_static {
-    ($t:ty,$val:expr) => {{
-        static STATIC_CELL: static_cell::StaticCell<$t> = static_cell::StaticCell::new();
-        #[deny(unused_attributes)]
-        let x = STATIC_CELL.uninit().write(($val));
-        x
-    }};
-}
\ No newline at end of file
+pub use esp32_kit::mk_static;
+pub use esp32_kit_wifi::status;
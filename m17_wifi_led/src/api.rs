@@ -0,0 +1,169 @@
+//! JSON resources under `/api`, one per peripheral.
+//!
+//! Bodies that are not valid JSON for the request type are turned away with
+//! 400 by picoserve's extractor, values out of range get a 400 with
+//! `{"error": "..."}` from here.
+
+use embassy_time::Instant;
+use esp32_kit::hcsr04::{Hcsr04Error, RangeEnd, MIN_DISTANCE_CM};
+use picoserve::extract::Json;
+use picoserve::response::{self, IntoResponse, Response, StatusCode};
+use serde::{Deserialize, Serialize};
+
+use crate::{led, sensors, servo, wifi};
+
+#[derive(Serialize, Deserialize)]
+pub struct LedState {
+    pub is_on: bool,
+}
+
+#[derive(Serialize)]
+pub struct Temperature {
+    pub celsius: f32,
+}
+
+#[derive(Serialize)]
+pub struct Distance {
+    /// `null` while nothing is within 400 cm, 2 for anything closer than 2 cm.
+    pub cm: Option<f32>,
+}
+
+#[derive(Deserialize)]
+pub struct ServoRequest {
+    pub angle: f32,
+}
+
+#[derive(Serialize)]
+pub struct ServoState {
+    pub id: usize,
+    pub angle: f32,
+}
+
+#[derive(Serialize)]
+pub struct JoystickState {
+    pub x: f32,
+    pub y: f32,
+    pub pressed: bool,
+}
+
+#[derive(Serialize)]
+pub struct SystemState {
+    pub heap_used: usize,
+    pub heap_free: usize,
+    pub uptime_s: u64,
+    /// `null` while the station is not connected.
+    pub rssi: Option<i8>,
+}
+
+pub struct ApiError {
+    status: StatusCode,
+    message: &'static str,
+}
+
+impl ApiError {
+    fn bad_request(message: &'static str) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message,
+        }
+    }
+
+    fn not_found(message: &'static str) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            message,
+        }
+    }
+
+    // The sensor is missing or has not been read yet
+    fn unavailable(message: &'static str) -> Self {
+        Self {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            message,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum Reply<T> {
+    Ok(T),
+    Error { error: &'static str },
+}
+
+// Successes and errors share one response type
+fn reply<T: Serialize>(result: Result<T, ApiError>) -> impl IntoResponse {
+    let (status, body) = match result {
+        Ok(value) => (StatusCode::OK, Reply::Ok(value)),
+        Err(e) => (e.status, Reply::Error { error: e.message }),
+    };
+    Response::new(status, response::Json(body))
+}
+
+pub async fn get_led() -> impl IntoResponse {
+    reply(Ok(LedState {
+        is_on: led::is_on(),
+    }))
+}
+
+pub async fn put_led(Json(request): Json<LedState, 0>) -> impl IntoResponse {
+    led::set(request.is_on);
+    reply(Ok(request))
+}
+
+pub async fn get_temperature() -> impl IntoResponse {
+    reply(match sensors::readings().temperature {
+        Some(Ok(celsius)) => Ok(Temperature { celsius }),
+        Some(Err(_)) => Err(ApiError::unavailable("Check the thermistor wiring")),
+        None => Err(ApiError::unavailable("No reading yet")),
+    })
+}
+
+pub async fn get_distance() -> impl IntoResponse {
+    reply(match sensors::readings().distance_cm {
+        Some(Ok(cm)) => Ok(Distance { cm: Some(cm) }),
+        // Closer than the sensor can measure
+        Some(Err(Hcsr04Error::OutOfRange(RangeEnd::Near))) => Ok(Distance {
+            cm: Some(MIN_DISTANCE_CM as f32),
+        }),
+        Some(Err(Hcsr04Error::OutOfRange(RangeEnd::Far))) => Ok(Distance { cm: None }),
+        Some(Err(Hcsr04Error::Timeout)) => Err(ApiError::unavailable("No echo, check the HC-SR04")),
+        None => Err(ApiError::unavailable("No reading yet")),
+    })
+}
+
+pub async fn put_servo(id: usize, Json(request): Json<ServoRequest, 0>) -> impl IntoResponse {
+    reply(set_servo(id, request.angle))
+}
+
+fn set_servo(id: usize, angle: f32) -> Result<ServoState, ApiError> {
+    if servo::angle(id).is_none() {
+        return Err(ApiError::not_found("No such servo"));
+    }
+    // Also rejects NaN
+    if !(servo::MIN_ANGLE..=servo::MAX_ANGLE).contains(&angle) {
+        return Err(ApiError::bad_request("The angle must be between 0 and 180"));
+    }
+    servo::set_angle(id, angle);
+    Ok(ServoState { id, angle })
+}
+
+pub async fn get_joystick() -> impl IntoResponse {
+    reply(match sensors::readings().joystick {
+        Some(joystick) => Ok(JoystickState {
+            x: joystick.x,
+            y: joystick.y,
+            pressed: joystick.pressed,
+        }),
+        None => Err(ApiError::unavailable("No reading yet")),
+    })
+}
+
+pub async fn get_system() -> impl IntoResponse {
+    reply(Ok(SystemState {
+        heap_used: esp_alloc::HEAP.used(),
+        heap_free: esp_alloc::HEAP.free(),
+        uptime_s: Instant::now().as_secs(),
+        rssi: wifi::WIFI.rssi(),
+    }))
+}
@@ -1,37 +1,64 @@
 #![no_std]
 #![no_main]
 
-use defmt::info;
+use defmt::{info, warn};
 use embassy_executor::Spawner;
-use esp_hal::clock::CpuClock;
-use esp_hal::gpio::{Level, Output, OutputConfig};
+use embassy_time::{Duration, Instant, Ticker};
+use esp32_kit::adc::{self, AdcCalibration};
+use esp32_kit::filter::oversample;
+use esp32_kit::hcsr04::Hcsr04;
+use esp32_kit::joystick::{Calibration, Joystick, JoystickConfig};
+use esp32_kit::servo::{self, PwmServo, Servo};
+use esp32_kit::storage;
+use esp32_kit::thermistor::{Divider, Model, Thermistor};
+use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
+use esp_hal::gpio::{Input, InputConfig, Level, Output, OutputConfig, Pull};
+use esp_hal::ledc::channel::{self, ChannelIFace};
+use esp_hal::ledc::timer::{self, TimerIFace};
+use esp_hal::ledc::{HighSpeed, Ledc};
 use esp_hal::rng::Rng;
+use esp_hal::time::Rate;
 use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
-
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
+use esp_storage::FlashStorage;
 
 extern crate alloc;
 
 use esp_wifi::EspWifiController;
 use m15_wifi_web as lib;
 
+// 10K NTC (B 3950) to GND with a 10K series resistor, as in m26_thermitor
+const THERMISTOR: Thermistor = Thermistor::new(
+    Model::Beta {
+        beta: 3950.0,
+        r0: 10_000.0,
+        t0: 25.0,
+    },
+    Divider::NtcLow,
+    10_000.0,
+);
+// Correcting ADC non-linearity issue, see esp32-kit/calibration
+const ADC_CALIBRATION: AdcCalibration = AdcCalibration::new(adc::Attenuation::_11dB);
+// Readings averaged into every sample
+const OVERSAMPLING: usize = 4;
+const NTC_OVERSAMPLING: usize = 16;
+// Joystick calibration saved by m33_joystick or m49_pantilt
+const CALIBRATION_OFFSET: u32 = 0x9000;
+// One update per servo frame, the temperature every 50
+const PERIOD: Duration = Duration::from_millis(20);
+const TEMPERATURE_EVERY: u32 = 50;
+
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[esp_hal_embassy::main]
 async fn main(spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     esp_alloc::heap_allocator!(size: 72 * 1024);
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -70,4 +97,96 @@ async fn main(spawner: Spawner) {
         ));
     }
     info!("Web server started...");
-}
\ No newline at end of file
+
+    // HC-SR04 as in m6_hcsr04
+    let trig = Output::new(peripherals.GPIO5, Level::Low, OutputConfig::default());
+    let echo = Input::new(
+        peripherals.GPIO18,
+        InputConfig::default().with_pull(Pull::Down),
+    );
+    spawner.must_spawn(lib::sensors::distance_task(Hcsr04::new(trig, echo)));
+
+    // ADC2 is taken by the WiFi driver, everything analog goes on ADC1
+    let mut adc1_config = AdcConfig::new();
+    let mut ntc_pin = adc1_config.enable_pin(peripherals.GPIO33, Attenuation::_11dB);
+    let mut vrx_pin = adc1_config.enable_pin(peripherals.GPIO34, Attenuation::_11dB);
+    let mut vry_pin = adc1_config.enable_pin(peripherals.GPIO35, Attenuation::_11dB);
+    let mut adc1 = Adc::new(peripherals.ADC1, adc1_config);
+    let btn = Input::new(
+        peripherals.GPIO32,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+
+    let calibration = match storage::load(&mut FlashStorage::new(), CALIBRATION_OFFSET) {
+        Ok(Some(calibration)) => calibration,
+        _ => {
+            warn!("No joystick calibration stored, calibrate with m33_joystick");
+            Calibration::default()
+        }
+    };
+    let mut joystick = Joystick::new(JoystickConfig::default(), calibration);
+    let ledc = Ledc::new(peripherals.LEDC);
+    let mut hstimer0 = ledc.timer::<HighSpeed>(timer::Number::Timer0);
+    hstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty12Bit,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency: Rate::from_hz(50),
+        })
+        .unwrap();
+    let servo0 = ledc.channel(channel::Number::Channel0, peripherals.GPIO26);
+    let servo1 = ledc.channel(channel::Number::Channel1, peripherals.GPIO27);
+    let mut servos = [servo0, servo1].map(|mut channel| {
+        channel
+            .configure(channel::config::Config {
+                timer: &hstimer0,
+                duty_pct: 0,
+                pin_config: channel::config::PinConfig::PushPull,
+            })
+            .unwrap();
+        let mut servo = PwmServo::new(channel, servo::Calibration::default()).unwrap();
+        servo.set_angle(lib::servo::CENTER).unwrap();
+        servo
+    });
+
+    // Sensors are read here and served from the latest values
+    let mut ticker = Ticker::every(PERIOD);
+    let mut tick = 0u32;
+    loop {
+        let x = oversample(OVERSAMPLING, || {
+            nb::block!(adc1.read_oneshot(&mut vrx_pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        });
+        let y = oversample(OVERSAMPLING, || {
+            nb::block!(adc1.read_oneshot(&mut vry_pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        });
+        if let (Ok(x), Ok(y)) = (x, y) {
+            // Only the axes and the button are served, the gestures go unused
+            let _ = joystick.update(Instant::now().as_millis(), [x, y], btn.is_low());
+            let [x, y] = joystick.axes();
+            let pressed = joystick.is_pressed();
+            lib::sensors::update(|readings| {
+                readings.joystick = Some(lib::sensors::JoystickReading { x, y, pressed })
+            });
+        }
+
+        if tick % TEMPERATURE_EVERY == 0 {
+            let adc_value = oversample(NTC_OVERSAMPLING, || {
+                let raw = nb::block!(adc1.read_oneshot(&mut ntc_pin)).unwrap();
+                THERMISTOR.correct(raw, &ADC_CALIBRATION)
+            });
+            let temperature = adc_value
+                .and_then(|adc_value| THERMISTOR.temperature(adc_value))
+                .map(|t| t as f32);
+            lib::sensors::update(|readings| readings.temperature = Some(temperature));
+        }
+        tick = tick.wrapping_add(1);
+
+        for (id, servo) in servos.iter_mut().enumerate() {
+            if let Some(angle) = lib::servo::take_target(id) {
+                servo.set_angle(angle).ok();
+            }
+        }
+
+        ticker.next().await;
+    }
+}
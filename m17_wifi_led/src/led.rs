@@ -1,18 +1,27 @@
-use core::sync::atomic::{AtomicBool, Ordering};
+use core::cell::Cell;
 
-use embassy_time::{Duration, Timer};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::signal::Signal;
 use esp_hal::gpio::Output;
 
-pub static LED_STATE: AtomicBool = AtomicBool::new(false);
+// Wakes the LED task with the new level
+static LED: Signal<CriticalSectionRawMutex, bool> = Signal::new();
+static LED_STATE: Mutex<CriticalSectionRawMutex, Cell<bool>> = Mutex::new(Cell::new(false));
+
+pub fn is_on() -> bool {
+    LED_STATE.lock(Cell::get)
+}
+
+pub fn set(is_on: bool) {
+    LED_STATE.lock(|state| state.set(is_on));
+    LED.signal(is_on);
+}
 
 #[embassy_executor::task]
 pub async fn led_task(mut led: Output<'static>) {
     loop {
-        if LED_STATE.load(Ordering::Relaxed) {
-            led.set_high();
-        } else {
-            led.set_low();
-        }
-        Timer::after(Duration::from_millis(50)).await;
+        let is_on = LED.wait().await;
+        led.set_level(is_on.into());
     }
-}
\ No newline at end of file
+}
@@ -1,16 +1,11 @@
 #![no_std]
 #![feature(impl_trait_in_assoc_type)]
 
+pub mod api;
 pub mod web;
 pub mod wifi;
 pub mod led;
+pub mod sensors;
+pub mod servo;
 
-#[macro_export]
-macro_rules! mk_static {
-    ($t:ty,$val:expr) => {{
-        static STATIC_CELL: static_cell::StaticCell<$t> = static_cell::StaticCell::new();
-        #[deny(unused_attributes)]
-        let x = STATIC_CELL.uninit().write(($val));
-        x
-    }};
-}
\ No newline at end of file
+pub use esp32_kit::mk_static;
@@ -0,0 +1,57 @@
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_time::{Duration, Timer};
+use esp32_kit::hcsr04::{Hcsr04, Hcsr04Error};
+use esp32_kit::thermistor::ThermistorError;
+use esp_hal::gpio::{Input, Output};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct JoystickReading {
+    /// -1..=1, right and up are positive.
+    pub x: f32,
+    pub y: f32,
+    pub pressed: bool,
+}
+
+/// Latest value of every sensor, `None` until it was read once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Readings {
+    /// °C
+    pub temperature: Option<Result<f32, ThermistorError>>,
+    pub distance_cm: Option<Result<f32, Hcsr04Error>>,
+    pub joystick: Option<JoystickReading>,
+}
+
+static READINGS: Mutex<CriticalSectionRawMutex, Cell<Readings>> = Mutex::new(Cell::new(Readings {
+    temperature: None,
+    distance_cm: None,
+    joystick: None,
+}));
+
+pub fn readings() -> Readings {
+    READINGS.lock(Cell::get)
+}
+
+pub fn update(f: impl FnOnce(&mut Readings)) {
+    READINGS.lock(|readings| {
+        let mut latest = readings.get();
+        f(&mut latest);
+        readings.set(latest);
+    });
+}
+
+// The echo takes up to 38 ms, too long for the loop in main
+#[embassy_executor::task]
+pub async fn distance_task(mut sensor: Hcsr04<Output<'static>, Input<'static>>) {
+    loop {
+        // Sound is faster in warm air
+        if let Some(Ok(celsius)) = readings().temperature {
+            sensor.set_temperature(celsius as f64);
+        }
+        let distance = sensor.measure().await.map(|distance| distance.cm() as f32);
+        update(|readings| readings.distance_cm = Some(distance));
+        Timer::after(Duration::from_millis(200)).await;
+    }
+}
@@ -0,0 +1,38 @@
+use core::cell::Cell;
+
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::signal::Signal;
+
+/// Servos on GPIO26 and GPIO27, `/api/servo/0` and `/api/servo/1`.
+pub const SERVO_COUNT: usize = 2;
+/// Travel of an SG90 with the default calibration.
+pub const MIN_ANGLE: f32 = 0.0;
+pub const MAX_ANGLE: f32 = 180.0;
+pub const CENTER: f32 = 90.0;
+
+// Only the latest angle matters, one signal per servo keeps them apart
+static TARGETS: [Signal<CriticalSectionRawMutex, f32>; SERVO_COUNT] =
+    [const { Signal::new() }; SERVO_COUNT];
+static ANGLES: Mutex<CriticalSectionRawMutex, Cell<[f32; SERVO_COUNT]>> =
+    Mutex::new(Cell::new([CENTER; SERVO_COUNT]));
+
+/// Last angle sent to servo `id`, `None` if there is no such servo.
+pub fn angle(id: usize) -> Option<f32> {
+    ANGLES.lock(Cell::get).get(id).copied()
+}
+
+/// Moves servo `id`, the angle has to be within [`MIN_ANGLE`] and [`MAX_ANGLE`].
+pub fn set_angle(id: usize, angle: f32) {
+    ANGLES.lock(|angles| {
+        let mut all = angles.get();
+        all[id] = angle;
+        angles.set(all);
+    });
+    TARGETS[id].signal(angle);
+}
+
+/// The angle servo `id` should move to, once per request.
+pub fn take_target(id: usize) -> Option<f32> {
+    TARGETS[id].try_take()
+}
@@ -1,11 +1,14 @@
-use core::{include_str, sync::atomic::Ordering};
+use core::include_str;
 use embassy_net::Stack;
 use embassy_time::Duration;
 use picoserve::{
-    response::{File, IntoResponse},
-    routing, AppBuilder, AppRouter, Router,
+    response::File,
+    routing::{self, get, parse_path_segment, put},
+    AppBuilder, AppRouter, Router,
 };
 
+use crate::api;
+
 pub struct Application;
 
 impl AppBuilder for Application {
@@ -17,7 +20,15 @@ impl AppBuilder for Application {
                 "/",
                 routing::get_service(File::html(include_str!("index.html"))),
             )
-            .route("/led", routing::post(led_handler))
+            .route("/api/led", get(api::get_led).put(api::put_led))
+            .route("/api/temperature", get(api::get_temperature))
+            .route("/api/distance", get(api::get_distance))
+            .route(
+                ("/api/servo", parse_path_segment::<usize>()),
+                put(api::put_servo),
+            )
+            .route("/api/joystick", get(api::get_joystick))
+            .route("/api/system", get(api::get_system))
     }
 }
 
@@ -70,19 +81,3 @@ impl Default for WebApp {
         Self { router, config }
     }
 }
-
-#[derive(serde::Serialize)]
-struct LedResponse {
-    success: bool,
-}
-
-#[derive(serde::Deserialize)]
-struct LedRequest {
-    is_on: bool,
-}
-
-async fn led_handler(input: picoserve::extract::Json<LedRequest, 0>) -> impl IntoResponse {
-    crate::led::LED_STATE.store(input.0.is_on, Ordering::Relaxed);
-
-    picoserve::response::Json(LedResponse { success: true })
-}
\ No newline at end of file
@@ -1,4 +1,3 @@
-
 use core::net::Ipv4Addr;
 use core::str::FromStr;
 
@@ -6,6 +5,9 @@ use anyhow::anyhow;
 use embassy_executor::Spawner;
 use embassy_net::{Ipv4Cidr, Runner, Stack, StackResources, StaticConfigV4};
 use embassy_time::{Duration, Timer};
+use esp32_kit::dhcp::ServerConfig;
+use esp32_kit::status::Event;
+use esp32_kit_wifi::dhcp::dhcp_task;
 use esp_hal::rng::Rng;
 use esp_println as _;
 use esp_println::println;
@@ -13,6 +15,7 @@ use esp_wifi::wifi::{self, WifiController, WifiDevice, WifiEvent, WifiState};
 use esp_wifi::EspWifiController;
 
 use crate::mk_static;
+use crate::status;
 
 const SSID: &str = env!("SSID");
 const PASSWORD: &str = env!("PASSWORD");
@@ -23,6 +26,9 @@ const STATIC_IP: &str = "192.168.4.1/24";
 
 const GATEWAY_IP: &str = "192.168.4.1";
 
+// Each lease lasts two hours
+const DHCP_LEASE_SECS: u32 = 2 * 60 * 60;
+
 pub async fn start_wifi(
     esp_wifi_ctrl: &'static EspWifiController<'static>,
     wifi: esp_hal::peripherals::WIFI<'static>,
@@ -55,16 +61,25 @@ pub async fn start_wifi(
     //     dns_servers: Default::default(),
     // });
 
+    // Hand out the addresses after the AP's own
+    let dhcp_config = ServerConfig {
+        address: ip_addr.address(),
+        netmask: ip_addr.netmask(),
+        pool_start: Ipv4Addr::from(u32::from(ip_addr.address()) + 1),
+        lease_secs: DHCP_LEASE_SECS,
+    };
+
     // Init network stack
     let (stack, runner) = embassy_net::new(
         wifi_interface,
         net_config,
-        mk_static!(StackResources<3>, StackResources::<3>::new()),
+        mk_static!(StackResources<4>, StackResources::<4>::new()),
         net_seed,
     );
 
     spawner.spawn(connection_task(controller)).ok();
     spawner.spawn(net_task(runner)).ok();
+    spawner.spawn(dhcp_task(stack, dhcp_config)).ok();
 
     wait_for_connection(stack).await;
 
@@ -96,9 +111,26 @@ async fn connection_task(mut controller: WifiController<'static>) {
     loop {
         match esp_wifi::wifi::wifi_state() {
             WifiState::ApStarted => {
-                // wait until we're no longer connected
-                controller.wait_for_event(WifiEvent::ApStop).await;
-                Timer::after(Duration::from_millis(5000)).await
+                // Follow the stations joining and leaving until the AP stops
+                let events = controller
+                    .wait_for_events(
+                        WifiEvent::ApStaconnected
+                            | WifiEvent::ApStadisconnected
+                            | WifiEvent::ApStop,
+                        true,
+                    )
+                    .await;
+                if events.contains(WifiEvent::ApStaconnected) {
+                    status::publish(Event::ApStationConnected);
+                }
+                if events.contains(WifiEvent::ApStadisconnected) {
+                    status::publish(Event::ApStationDisconnected);
+                }
+                if events.contains(WifiEvent::ApStop) {
+                    status::publish(Event::ApStopped);
+                    Timer::after(Duration::from_millis(5000)).await
+                }
+                continue;
             }
             _ => {}
         }
@@ -111,8 +143,12 @@ async fn connection_task(mut controller: WifiController<'static>) {
             });
             controller.set_configuration(&client_config).unwrap();
             println!("Starting wifi");
-            controller.start_async().await.unwrap();
+            if let Err(e) = controller.start_async().await {
+                println!("Failed to start wifi: {:?}", e);
+                status::fatal().await;
+            }
             println!("Wifi started!");
+            status::publish(Event::ApStarted);
         }
     }
 }
@@ -120,4 +156,4 @@ async fn connection_task(mut controller: WifiController<'static>) {
 #[embassy_executor::task]
 async fn net_task(mut runner: Runner<'static, WifiDevice<'static>>) {
     runner.run().await
-}
\ No newline at end of file
+}
@@ -1,33 +1,20 @@
 #![no_std]
 #![no_main]
 
-use esp_hal::clock::CpuClock;
 use esp_hal::gpio::{Level, Output, OutputConfig};
 use esp_hal::main;
-use esp_hal::time::{Duration, Instant};
-
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
+use esp_hal::time::Duration;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[main]
 fn main() -> ! {
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     let mut led = Output::new(peripherals.GPIO2, Level::High, OutputConfig::default());
 
     loop {
         led.toggle();
-        blocking_delay(Duration::from_millis(500));
+        esp32_kit::blocking_delay!(Duration::from_millis(500));
     }
 }
-
-fn blocking_delay(duration: Duration) {
-    let delay_start = Instant::now();
-    while delay_start.elapsed() < duration {}
-}
-
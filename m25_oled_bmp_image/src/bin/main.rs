@@ -7,8 +7,7 @@ use embassy_time::{Duration, Timer};
 use embedded_graphics::image::Image;
 use embedded_graphics::prelude::Point;
 use embedded_graphics::prelude::*;
-use esp_hal::timer::timg::TimerGroup;
-use esp_hal::{clock::CpuClock, time::Rate};
+use esp_hal::time::Rate;
 use esp_println as _;
 use ssd1306::mode::DisplayConfigAsync;
 use ssd1306::{
@@ -16,11 +15,6 @@ use ssd1306::{
 };
 use tinybmp::Bmp;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 esp_bootloader_esp_idf::esp_app_desc!();
 
 
@@ -28,11 +22,9 @@ esp_bootloader_esp_idf::esp_app_desc!();
 async fn main(_spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -67,4 +59,3 @@ async fn main(_spawner: Spawner) {
         Timer::after(Duration::from_secs(1)).await;
     }
 }
-
@@ -5,10 +5,10 @@ use core::fmt::Write;
 use defmt::info;
 use embassy_executor::Spawner;
 use embassy_time::{Duration, Timer};
+use esp32_kit::adc::{self, AdcCalibration};
+use esp32_kit::thermistor::{Divider, Model, Thermistor};
 use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
-use esp_hal::clock::CpuClock;
 use esp_hal::time::Rate;
-use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
 
 use embedded_graphics::prelude::*;
@@ -24,44 +24,19 @@ use ssd1306::{
     I2CDisplayInterface, Ssd1306Async,
 };
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
-const fn kelvin_to_celsius(kelvin: f64) -> f64 {
-    kelvin - 273.15
-}
-
-const fn celsius_to_kelvin(celsius: f64) -> f64 {
-    celsius + 273.15
-}
-
-const ADC_MAX: f64 = 4095.0; // 4095 for 12-bit ADC
-const B_VALUE: f64 = 3950.0;
-
-const REF_TEMP: f64 = 25.0; // Reference temperature 25°C
-const REF_RES: f64 = 10_000.0; // Thermistor resistance at the Reference Temperature(25°C)
-const REF_TEMP_K: f64 = celsius_to_kelvin(REF_TEMP);
-
-// The resistor value that is connected with thermistor in the voltage divider
-const R1_RES: f64 = REF_RES; // 10_000.0 ohms
+// 10K NTC (product B value 3950) on the low side of the divider, with a 10K series resistor
+const THERMISTOR: Thermistor = Thermistor::new(
+    Model::Beta {
+        beta: 3950.0,
+        r0: 10_000.0,
+        t0: 25.0,
+    },
+    Divider::NtcLow,
+    10_000.0,
+);
 
-fn adc_to_resistance(adc_value: f64) -> f64 {
-    let x: f64 = adc_value / (ADC_MAX - adc_value);
-    R1_RES * x
-
-    //alternative calculating vout and then calculating R2
-    // let vout = (adc_value as f64 / ADC_MAX as f64) * VREF;
-    // R1_RES * (vout / (VREF - vout))
-}
-
-// B Equation to convert resistance to temperature
-fn calculate_temperature(current_res: f64, b_val: f64) -> f64 {
-    let ln_value = libm::log(current_res / REF_RES); // Use libm for `no_std`
-    let inv_t = (1.0 / REF_TEMP_K) + ((1.0 / b_val) * ln_value);
-    1.0 / inv_t
-}
+// Correcting ADC non-linearity issue, see esp32-kit/calibration
+const ADC_CALIBRATION: AdcCalibration = AdcCalibration::new(adc::Attenuation::_11dB);
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
@@ -69,11 +44,9 @@ esp_bootloader_esp_idf::esp_app_desc!();
 async fn main(_spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -110,15 +83,15 @@ async fn main(_spawner: Spawner) {
 
         let adc_value: u16 = nb::block!(adc2.read_oneshot(&mut pin)).unwrap();
         //esp_println::println!("ADC: {}", adc_value);
-        let adc_value: f64 = ADC_LUT[adc_value as usize];
-        //esp_println::println!("Corrected ADC: {}", adc_value);
-
-        let current_res = adc_to_resistance(adc_value);
-        //esp_println::println!("R2: {}", current_res);
-        let temperature_kelvin = calculate_temperature(current_res, B_VALUE);
-        let temperature_celsius = kelvin_to_celsius(temperature_kelvin);
-
-        write!(buffer, "Temperature: {:.2} C", temperature_celsius).unwrap();
+        let adc_value = THERMISTOR.correct(adc_value, &ADC_CALIBRATION);
+        //esp_println::println!("Corrected ADC: {:?}", adc_value);
+
+        match adc_value.and_then(|adc_value| THERMISTOR.temperature(adc_value)) {
+            Ok(temperature_celsius) => {
+                write!(buffer, "Temperature: {:.2} C", temperature_celsius).unwrap()
+            }
+            Err(e) => write!(buffer, "Sensor: {:?}", e).unwrap(),
+        }
         Text::with_baseline(&buffer, Point::new(0, 16), text_style, Baseline::Top)
             .draw(&mut display)
             .unwrap();
@@ -128,508 +101,3 @@ async fn main(_spawner: Spawner) {
         Timer::after(Duration::from_secs(1)).await;
     }
 }
-
-// Lookup table (LUT) to be used for correcting ADC non-linearity issue
-// https://github.com/e-tinkers/esp32-adc-calibrate
-// https://www.e-tinkers.com/2019/10/using-a-thermistor-with-arduino-and-unexpected-esp32-adc-non-linearity/
-const ADC_LUT: &[f64; 4096] = &[
-    0.0, 7.4000, 17.0000, 18.8000, 20.8000, 22.8000, 24.8000, 26.6000, 28.6000, 30.4000, 32.2000,
-    33.6000, 34.8000, 36.0000, 37.2000, 38.6000, 39.8000, 41.0000, 42.2000, 43.8000, 44.8000,
-    46.0000, 47.2000, 48.6000, 49.6000, 50.6000, 51.6000, 52.4000, 53.4000, 54.4000, 55.4000,
-    56.4000, 57.2000, 58.2000, 59.2000, 60.2000, 61.2000, 62.2000, 63.0000, 64.0000, 65.2000,
-    66.6000, 67.8000, 68.8000, 69.8000, 71.0000, 72.0000, 73.0000, 74.2000, 75.4000, 76.6000,
-    77.8000, 78.8000, 80.0000, 81.0000, 82.4000, 83.8000, 85.0000, 86.2000, 87.6000, 88.8000,
-    90.0000, 91.4000, 92.8000, 94.0000, 95.0000, 96.4000, 97.2000, 98.0000, 99.0000, 99.8000,
-    100.8000, 101.8000, 102.6000, 103.4000, 104.0000, 105.0000, 106.0000, 106.8000, 107.8000,
-    108.8000, 109.6000, 110.4000, 111.0000, 112.0000, 113.4000, 114.8000, 116.0000, 117.2000,
-    118.8000, 120.0000, 121.2000, 122.8000, 124.0000, 125.2000, 126.8000, 128.0000, 129.0000,
-    130.0000, 131.0000, 132.0000, 133.2000, 134.4000, 135.4000, 136.6000, 137.8000, 138.8000,
-    139.8000, 140.8000, 141.8000, 143.0000, 144.0000, 145.0000, 146.6000, 147.8000, 149.0000,
-    150.6000, 151.8000, 153.0000, 154.4000, 155.8000, 157.0000, 158.2000, 159.8000, 160.8000,
-    161.4000, 162.0000, 163.0000, 163.8000, 164.6000, 165.0000, 166.0000, 166.8000, 167.6000,
-    168.2000, 169.0000, 169.8000, 170.8000, 171.2000, 172.0000, 172.8000, 173.8000, 174.4000,
-    175.0000, 175.8000, 177.0000, 178.0000, 179.2000, 180.6000, 181.8000, 182.8000, 184.0000,
-    185.0000, 186.4000, 187.8000, 188.8000, 190.0000, 191.0000, 192.2000, 193.2000, 194.2000,
-    195.4000, 196.4000, 197.4000, 198.4000, 199.4000, 200.6000, 201.6000, 202.6000, 203.6000,
-    204.8000, 205.8000, 206.8000, 207.8000, 208.8000, 209.8000, 210.8000, 211.8000, 212.8000,
-    214.0000, 215.0000, 216.0000, 217.0000, 218.0000, 219.0000, 220.0000, 221.2000, 222.2000,
-    223.4000, 224.4000, 225.8000, 226.8000, 228.0000, 229.0000, 230.2000, 231.4000, 232.8000,
-    233.8000, 235.0000, 236.0000, 237.2000, 238.6000, 239.8000, 241.0000, 242.6000, 244.0000,
-    245.8000, 247.0000, 248.8000, 250.2000, 251.8000, 253.2000, 254.8000, 256.2000, 257.2000,
-    258.2000, 259.0000, 260.0000, 261.0000, 262.0000, 263.0000, 264.0000, 265.0000, 266.0000,
-    267.0000, 267.8000, 268.8000, 269.8000, 270.8000, 271.8000, 272.6000, 273.0000, 273.8000,
-    274.6000, 275.0000, 275.8000, 276.6000, 277.0000, 277.8000, 278.4000, 279.0000, 279.8000,
-    280.4000, 281.0000, 281.8000, 282.2000, 283.0000, 283.8000, 284.2000, 285.0000, 285.8000,
-    286.2000, 287.0000, 287.8000, 288.0000, 291.0000, 293.8000, 296.4000, 299.0000, 301.8000,
-    304.2000, 305.2000, 306.2000, 307.2000, 308.2000, 309.2000, 310.0000, 311.0000, 312.0000,
-    313.0000, 314.0000, 315.0000, 316.0000, 317.0000, 318.0000, 319.0000, 320.0000, 321.0000,
-    322.2000, 323.6000, 324.8000, 325.8000, 326.8000, 328.0000, 329.0000, 330.0000, 331.4000,
-    332.6000, 333.8000, 334.8000, 335.8000, 336.8000, 337.8000, 338.8000, 339.8000, 340.6000,
-    341.4000, 342.2000, 343.2000, 344.0000, 345.0000, 346.0000, 347.0000, 347.8000, 348.8000,
-    349.8000, 350.8000, 351.6000, 352.4000, 353.2000, 354.0000, 355.0000, 356.0000, 357.0000,
-    357.8000, 358.8000, 359.8000, 360.8000, 361.6000, 362.4000, 363.2000, 364.0000, 365.0000,
-    366.0000, 367.0000, 367.8000, 369.0000, 370.4000, 371.8000, 373.0000, 374.8000, 376.0000,
-    377.4000, 378.8000, 380.0000, 381.8000, 383.0000, 384.4000, 386.0000, 387.8000, 389.0000,
-    390.8000, 392.2000, 393.8000, 395.4000, 397.0000, 398.8000, 400.0000, 401.6000, 402.8000,
-    404.0000, 405.4000, 406.8000, 408.0000, 409.2000, 410.8000, 411.8000, 413.0000, 414.6000,
-    415.8000, 417.0000, 418.0000, 419.0000, 420.2000, 421.4000, 422.6000, 423.8000, 424.8000,
-    425.8000, 427.0000, 428.0000, 429.0000, 430.2000, 431.4000, 432.4000, 433.0000, 434.0000,
-    434.8000, 435.6000, 436.2000, 437.0000, 437.8000, 438.8000, 439.4000, 440.0000, 441.0000,
-    441.8000, 442.6000, 443.2000, 444.0000, 444.8000, 445.8000, 446.4000, 447.0000, 448.0000,
-    448.8000, 449.8000, 450.8000, 451.6000, 452.4000, 453.2000, 454.0000, 455.0000, 456.0000,
-    456.8000, 457.8000, 458.8000, 459.8000, 460.6000, 461.4000, 462.2000, 463.0000, 464.0000,
-    465.6000, 467.0000, 468.8000, 470.2000, 472.0000, 473.6000, 475.0000, 476.8000, 478.2000,
-    479.8000, 480.8000, 481.8000, 482.6000, 483.2000, 484.0000, 485.0000, 485.8000, 486.8000,
-    487.4000, 488.0000, 489.0000, 489.8000, 490.8000, 491.6000, 492.4000, 493.0000, 494.0000,
-    494.8000, 495.8000, 496.8000, 498.2000, 499.8000, 501.4000, 503.0000, 504.8000, 506.0000,
-    507.8000, 509.4000, 511.0000, 512.4000, 513.8000, 514.8000, 516.0000, 517.0000, 518.0000,
-    519.4000, 520.6000, 521.8000, 522.8000, 524.0000, 525.0000, 526.2000, 527.6000, 528.6000,
-    529.4000, 530.2000, 531.0000, 532.0000, 533.0000, 534.0000, 534.8000, 535.8000, 536.8000,
-    537.8000, 538.6000, 539.4000, 540.2000, 541.0000, 542.0000, 543.0000, 544.0000, 545.4000,
-    547.0000, 548.8000, 550.6000, 552.2000, 554.0000, 555.8000, 557.6000, 559.0000, 560.8000,
-    561.8000, 562.8000, 563.6000, 564.6000, 565.6000, 566.6000, 567.4000, 568.4000, 569.4000,
-    570.4000, 571.2000, 572.2000, 573.2000, 574.2000, 575.0000, 576.0000, 576.8000, 577.4000,
-    578.0000, 578.6000, 579.0000, 579.8000, 580.2000, 580.8000, 581.4000, 582.0000, 582.8000,
-    583.0000, 583.8000, 584.2000, 585.0000, 585.6000, 586.0000, 586.8000, 587.2000, 587.8000,
-    588.4000, 589.0000, 589.8000, 590.0000, 590.8000, 591.2000, 592.0000, 593.0000, 594.8000,
-    596.6000, 598.0000, 599.8000, 601.8000, 603.2000, 605.0000, 606.8000, 608.2000, 609.2000,
-    610.0000, 611.0000, 612.0000, 613.0000, 614.0000, 615.0000, 615.8000, 616.8000, 617.8000,
-    618.8000, 619.8000, 620.6000, 621.6000, 622.4000, 623.4000, 624.2000, 625.2000, 626.2000,
-    627.4000, 628.4000, 629.4000, 630.4000, 631.6000, 632.6000, 633.6000, 634.6000, 635.8000,
-    636.8000, 637.8000, 638.8000, 639.8000, 640.8000, 641.8000, 642.8000, 643.8000, 644.8000,
-    645.8000, 646.8000, 647.8000, 648.8000, 649.8000, 650.8000, 651.8000, 652.8000, 653.8000,
-    654.8000, 655.8000, 657.0000, 658.8000, 660.2000, 662.0000, 663.8000, 665.2000, 667.0000,
-    668.6000, 670.0000, 671.8000, 673.0000, 674.0000, 675.4000, 676.6000, 677.8000, 678.8000,
-    680.0000, 681.0000, 682.0000, 683.2000, 684.6000, 685.8000, 686.8000, 688.0000, 689.0000,
-    690.2000, 691.6000, 692.8000, 693.8000, 695.0000, 696.2000, 697.6000, 698.8000, 700.0000,
-    701.0000, 702.2000, 703.6000, 704.8000, 705.8000, 706.8000, 707.8000, 708.8000, 709.8000,
-    710.8000, 712.0000, 713.0000, 714.0000, 715.0000, 716.0000, 717.0000, 718.0000, 719.0000,
-    720.0000, 721.2000, 722.2000, 723.4000, 724.4000, 725.6000, 726.6000, 727.8000, 728.8000,
-    729.8000, 730.8000, 731.8000, 732.8000, 733.8000, 735.0000, 736.0000, 737.0000, 738.0000,
-    739.2000, 740.4000, 741.8000, 742.8000, 743.8000, 745.0000, 746.0000, 747.0000, 748.2000,
-    749.4000, 750.6000, 751.8000, 752.8000, 753.4000, 754.0000, 755.0000, 755.8000, 756.6000,
-    757.2000, 758.0000, 758.8000, 759.8000, 760.4000, 761.0000, 762.0000, 762.8000, 763.6000,
-    764.2000, 765.0000, 765.8000, 766.8000, 767.4000, 768.0000, 774.0000, 780.8000, 784.8000,
-    785.8000, 786.6000, 787.4000, 788.4000, 789.2000, 790.2000, 791.0000, 792.0000, 793.0000,
-    794.0000, 795.0000, 795.8000, 796.8000, 797.8000, 798.8000, 799.8000, 800.8000, 801.6000,
-    802.4000, 803.2000, 804.0000, 805.0000, 806.0000, 806.8000, 807.8000, 808.8000, 809.6000,
-    810.4000, 811.2000, 812.0000, 813.0000, 814.0000, 814.8000, 815.8000, 816.8000, 818.0000,
-    819.0000, 820.4000, 821.8000, 822.8000, 824.0000, 825.2000, 826.6000, 827.8000, 829.0000,
-    830.0000, 831.2000, 832.4000, 833.0000, 834.0000, 834.8000, 835.8000, 836.4000, 837.0000,
-    838.0000, 838.8000, 839.8000, 840.4000, 841.0000, 842.0000, 842.8000, 843.8000, 844.4000,
-    845.0000, 846.0000, 846.8000, 847.6000, 848.4000, 849.8000, 850.8000, 852.0000, 853.2000,
-    854.8000, 855.8000, 857.0000, 858.2000, 859.6000, 860.8000, 862.0000, 863.0000, 864.4000,
-    865.4000, 866.4000, 867.4000, 868.4000, 869.4000, 870.4000, 871.4000, 872.2000, 873.2000,
-    874.2000, 875.2000, 876.2000, 877.2000, 878.2000, 879.2000, 880.2000, 881.8000, 882.8000,
-    884.0000, 885.2000, 886.6000, 887.8000, 889.0000, 890.0000, 891.4000, 892.8000, 893.8000,
-    895.0000, 896.2000, 897.2000, 898.2000, 899.2000, 900.2000, 901.2000, 902.0000, 903.0000,
-    904.0000, 905.0000, 906.0000, 907.0000, 908.0000, 909.0000, 910.0000, 911.0000, 912.0000,
-    913.4000, 914.8000, 916.0000, 917.0000, 918.6000, 919.8000, 921.0000, 922.2000, 923.8000,
-    924.8000, 926.0000, 927.6000, 928.6000, 929.4000, 930.0000, 931.0000, 931.8000, 932.8000,
-    933.6000, 934.2000, 935.0000, 936.0000, 936.8000, 937.8000, 938.6000, 939.2000, 940.0000,
-    941.0000, 941.8000, 942.8000, 943.6000, 944.2000, 945.4000, 946.6000, 947.8000, 948.8000,
-    949.8000, 950.8000, 952.0000, 953.0000, 954.0000, 955.2000, 956.4000, 957.6000, 958.8000,
-    959.8000, 960.8000, 962.0000, 963.0000, 964.2000, 965.6000, 966.8000, 967.8000, 969.0000,
-    970.0000, 971.4000, 972.8000, 973.8000, 975.0000, 976.0000, 977.2000, 978.4000, 979.8000,
-    980.8000, 982.0000, 983.0000, 984.2000, 985.6000, 986.8000, 987.8000, 989.0000, 990.0000,
-    991.2000, 992.4000, 993.0000, 993.8000, 994.6000, 995.0000, 995.8000, 996.8000, 997.2000,
-    998.0000, 998.8000, 999.4000, 1000.0000, 1000.8000, 1001.6000, 1002.0000, 1003.0000, 1003.8000,
-    1004.2000, 1005.0000, 1005.8000, 1006.4000, 1007.0000, 1007.8000, 1008.8000, 1010.0000,
-    1011.2000, 1012.8000, 1013.8000, 1015.0000, 1016.2000, 1017.8000, 1018.8000, 1020.0000,
-    1021.2000, 1022.8000, 1023.8000, 1025.0000, 1026.0000, 1027.0000, 1028.0000, 1029.0000,
-    1030.2000, 1031.4000, 1032.4000, 1033.6000, 1034.8000, 1035.8000, 1036.8000, 1037.8000,
-    1038.8000, 1040.0000, 1041.2000, 1042.8000, 1044.0000, 1045.8000, 1047.0000, 1048.8000,
-    1050.0000, 1051.6000, 1053.0000, 1054.6000, 1056.0000, 1057.0000, 1057.8000, 1058.8000,
-    1059.6000, 1060.4000, 1061.2000, 1062.0000, 1063.0000, 1064.0000, 1064.8000, 1065.8000,
-    1066.6000, 1067.4000, 1068.2000, 1069.0000, 1070.0000, 1071.0000, 1071.8000, 1072.8000,
-    1073.8000, 1074.8000, 1075.6000, 1076.6000, 1077.4000, 1078.4000, 1079.2000, 1080.2000,
-    1081.0000, 1082.0000, 1083.0000, 1084.0000, 1085.0000, 1086.0000, 1086.8000, 1087.8000,
-    1088.8000, 1090.0000, 1091.0000, 1092.0000, 1093.2000, 1094.4000, 1095.6000, 1096.8000,
-    1097.8000, 1098.8000, 1099.8000, 1101.0000, 1102.0000, 1103.0000, 1104.0000, 1105.0000,
-    1105.8000, 1106.8000, 1107.6000, 1108.2000, 1109.0000, 1109.8000, 1110.8000, 1111.6000,
-    1112.2000, 1113.0000, 1114.0000, 1114.8000, 1115.8000, 1116.4000, 1117.0000, 1118.0000,
-    1118.8000, 1119.8000, 1120.6000, 1121.6000, 1122.8000, 1123.8000, 1124.8000, 1125.8000,
-    1126.8000, 1127.8000, 1128.8000, 1129.8000, 1130.8000, 1131.8000, 1132.8000, 1134.0000,
-    1135.0000, 1136.0000, 1137.0000, 1138.0000, 1139.0000, 1140.0000, 1141.0000, 1142.0000,
-    1143.0000, 1144.0000, 1145.0000, 1146.0000, 1146.8000, 1147.8000, 1148.8000, 1149.8000,
-    1150.8000, 1151.8000, 1153.0000, 1154.4000, 1155.8000, 1157.0000, 1158.8000, 1160.0000,
-    1161.2000, 1162.8000, 1164.0000, 1165.4000, 1166.8000, 1168.0000, 1169.0000, 1169.8000,
-    1170.6000, 1171.2000, 1172.0000, 1172.8000, 1173.8000, 1174.6000, 1175.2000, 1176.0000,
-    1176.8000, 1177.8000, 1178.4000, 1179.0000, 1180.0000, 1180.8000, 1181.8000, 1182.4000,
-    1183.0000, 1184.0000, 1185.6000, 1187.2000, 1189.0000, 1191.0000, 1192.8000, 1194.8000,
-    1196.8000, 1198.4000, 1200.0000, 1201.2000, 1202.2000, 1203.4000, 1204.4000, 1205.6000,
-    1206.6000, 1207.8000, 1208.8000, 1209.8000, 1210.8000, 1211.8000, 1212.8000, 1213.8000,
-    1215.0000, 1216.0000, 1217.0000, 1218.0000, 1218.8000, 1219.8000, 1220.8000, 1221.8000,
-    1222.8000, 1223.8000, 1224.8000, 1225.8000, 1226.8000, 1227.6000, 1228.6000, 1229.6000,
-    1230.4000, 1231.4000, 1232.2000, 1233.0000, 1234.0000, 1234.8000, 1235.6000, 1236.2000,
-    1237.0000, 1237.8000, 1238.8000, 1239.4000, 1240.0000, 1241.0000, 1241.8000, 1242.8000,
-    1243.4000, 1244.0000, 1245.0000, 1245.8000, 1246.8000, 1247.4000, 1248.0000, 1249.8000,
-    1251.8000, 1253.2000, 1255.0000, 1256.8000, 1258.8000, 1260.2000, 1262.0000, 1263.8000,
-    1265.0000, 1266.0000, 1267.2000, 1268.2000, 1269.4000, 1270.6000, 1271.8000, 1272.8000,
-    1273.8000, 1275.0000, 1276.0000, 1277.0000, 1278.0000, 1279.2000, 1280.2000, 1281.0000,
-    1282.0000, 1283.0000, 1284.0000, 1284.8000, 1285.8000, 1286.8000, 1287.8000, 1288.6000,
-    1289.4000, 1290.2000, 1291.0000, 1292.0000, 1293.0000, 1294.0000, 1294.8000, 1295.8000,
-    1296.8000, 1297.8000, 1298.6000, 1299.6000, 1300.4000, 1301.2000, 1302.0000, 1303.0000,
-    1304.0000, 1305.0000, 1306.0000, 1306.8000, 1307.8000, 1308.8000, 1309.8000, 1310.8000,
-    1311.6000, 1312.8000, 1314.8000, 1316.8000, 1318.8000, 1320.6000, 1322.6000, 1324.4000,
-    1326.4000, 1328.2000, 1329.8000, 1331.0000, 1332.2000, 1333.8000, 1335.0000, 1336.2000,
-    1337.8000, 1339.0000, 1340.2000, 1341.8000, 1343.0000, 1344.0000, 1345.0000, 1346.0000,
-    1347.0000, 1348.0000, 1349.0000, 1349.8000, 1350.8000, 1351.8000, 1352.8000, 1353.8000,
-    1354.6000, 1355.4000, 1356.4000, 1357.2000, 1358.0000, 1359.0000, 1360.0000, 1360.8000,
-    1361.8000, 1362.4000, 1363.0000, 1363.8000, 1364.8000, 1365.4000, 1366.0000, 1367.0000,
-    1367.8000, 1368.4000, 1369.0000, 1370.0000, 1370.8000, 1371.4000, 1372.0000, 1373.0000,
-    1373.8000, 1374.6000, 1375.0000, 1376.0000, 1377.0000, 1378.4000, 1379.8000, 1381.0000,
-    1382.2000, 1383.8000, 1385.0000, 1386.0000, 1387.6000, 1388.8000, 1390.0000, 1391.4000,
-    1392.8000, 1393.6000, 1394.4000, 1395.2000, 1396.2000, 1397.0000, 1398.0000, 1399.0000,
-    1400.0000, 1400.8000, 1401.8000, 1402.8000, 1403.8000, 1404.8000, 1405.6000, 1406.4000,
-    1407.4000, 1408.6000, 1411.0000, 1413.8000, 1416.4000, 1419.0000, 1421.8000, 1424.0000,
-    1424.8000, 1425.6000, 1426.0000, 1426.8000, 1427.4000, 1428.0000, 1428.8000, 1429.2000,
-    1430.0000, 1430.8000, 1431.0000, 1431.8000, 1432.6000, 1433.0000, 1433.8000, 1434.4000,
-    1435.0000, 1435.8000, 1436.2000, 1436.8000, 1437.6000, 1438.0000, 1438.8000, 1439.4000,
-    1440.0000, 1441.2000, 1442.8000, 1444.0000, 1445.8000, 1447.0000, 1448.6000, 1450.0000,
-    1451.4000, 1452.8000, 1454.2000, 1455.8000, 1456.8000, 1457.8000, 1458.8000, 1459.8000,
-    1460.6000, 1461.4000, 1462.2000, 1463.0000, 1464.0000, 1465.0000, 1466.0000, 1466.8000,
-    1467.8000, 1468.8000, 1469.8000, 1470.6000, 1471.4000, 1472.4000, 1473.8000, 1475.0000,
-    1476.6000, 1477.8000, 1479.0000, 1480.8000, 1482.0000, 1483.4000, 1484.8000, 1486.0000,
-    1487.6000, 1488.8000, 1489.8000, 1490.8000, 1491.8000, 1492.8000, 1493.8000, 1494.8000,
-    1495.8000, 1496.8000, 1497.8000, 1498.8000, 1499.8000, 1500.8000, 1501.8000, 1502.8000,
-    1503.8000, 1504.8000, 1505.8000, 1506.6000, 1507.4000, 1508.2000, 1509.0000, 1510.0000,
-    1510.8000, 1511.8000, 1512.8000, 1513.6000, 1514.4000, 1515.0000, 1516.0000, 1517.0000,
-    1517.8000, 1518.8000, 1519.8000, 1520.8000, 1522.4000, 1524.0000, 1525.8000, 1527.8000,
-    1529.2000, 1531.0000, 1532.8000, 1534.6000, 1536.0000, 1537.0000, 1538.0000, 1539.0000,
-    1540.0000, 1540.8000, 1541.8000, 1542.8000, 1543.8000, 1544.8000, 1545.8000, 1546.6000,
-    1547.4000, 1548.4000, 1549.2000, 1550.0000, 1551.0000, 1552.0000, 1553.6000, 1555.0000,
-    1556.4000, 1557.8000, 1559.2000, 1560.8000, 1562.0000, 1563.8000, 1565.0000, 1566.8000,
-    1568.0000, 1569.0000, 1569.8000, 1570.8000, 1571.4000, 1572.0000, 1573.0000, 1573.8000,
-    1574.8000, 1575.6000, 1576.2000, 1577.0000, 1578.0000, 1578.8000, 1579.8000, 1580.4000,
-    1581.0000, 1582.0000, 1582.8000, 1583.8000, 1584.8000, 1585.8000, 1586.8000, 1587.8000,
-    1588.8000, 1589.8000, 1590.8000, 1591.8000, 1592.8000, 1593.8000, 1594.8000, 1595.8000,
-    1596.8000, 1597.8000, 1598.8000, 1599.8000, 1600.8000, 1601.8000, 1602.4000, 1603.0000,
-    1604.0000, 1605.0000, 1605.8000, 1606.8000, 1607.4000, 1608.2000, 1609.0000, 1610.0000,
-    1610.8000, 1611.8000, 1612.6000, 1613.2000, 1614.0000, 1615.0000, 1615.8000, 1617.0000,
-    1618.2000, 1619.8000, 1621.0000, 1622.4000, 1623.8000, 1625.0000, 1626.6000, 1627.8000,
-    1629.0000, 1630.6000, 1631.8000, 1633.0000, 1634.2000, 1635.6000, 1636.8000, 1638.0000,
-    1639.0000, 1640.2000, 1641.6000, 1642.8000, 1644.0000, 1645.0000, 1646.2000, 1647.6000,
-    1648.8000, 1649.6000, 1650.4000, 1651.2000, 1652.0000, 1653.0000, 1654.0000, 1654.8000,
-    1655.8000, 1656.8000, 1657.6000, 1658.4000, 1659.2000, 1660.0000, 1661.0000, 1662.0000,
-    1662.8000, 1663.8000, 1664.8000, 1665.8000, 1666.8000, 1668.0000, 1669.0000, 1670.0000,
-    1671.0000, 1672.0000, 1673.0000, 1674.2000, 1675.4000, 1676.4000, 1677.6000, 1678.6000,
-    1679.8000, 1680.8000, 1681.4000, 1682.2000, 1683.0000, 1684.0000, 1684.8000, 1685.8000,
-    1686.8000, 1687.4000, 1688.2000, 1689.0000, 1690.0000, 1690.8000, 1691.8000, 1692.6000,
-    1693.4000, 1694.0000, 1695.0000, 1696.0000, 1697.4000, 1699.0000, 1700.8000, 1702.8000,
-    1704.2000, 1706.0000, 1707.8000, 1709.6000, 1711.2000, 1712.8000, 1713.4000, 1714.0000,
-    1715.0000, 1715.8000, 1716.6000, 1717.2000, 1718.0000, 1718.8000, 1719.8000, 1720.6000,
-    1721.0000, 1722.0000, 1722.8000, 1723.8000, 1724.4000, 1725.0000, 1726.0000, 1726.8000,
-    1727.8000, 1728.4000, 1729.6000, 1730.8000, 1731.8000, 1732.8000, 1733.8000, 1735.0000,
-    1736.0000, 1737.0000, 1738.0000, 1739.0000, 1740.2000, 1741.4000, 1742.6000, 1743.8000,
-    1744.8000, 1745.8000, 1746.8000, 1748.0000, 1749.0000, 1750.0000, 1751.0000, 1752.2000,
-    1753.4000, 1754.6000, 1755.8000, 1756.8000, 1757.8000, 1758.8000, 1760.0000, 1761.0000,
-    1762.0000, 1763.0000, 1764.0000, 1765.0000, 1766.0000, 1767.0000, 1768.0000, 1769.0000,
-    1770.0000, 1771.2000, 1772.2000, 1773.2000, 1774.2000, 1775.4000, 1776.4000, 1777.8000,
-    1779.0000, 1780.4000, 1781.8000, 1783.0000, 1784.4000, 1785.8000, 1787.0000, 1788.4000,
-    1789.8000, 1791.0000, 1792.4000, 1793.8000, 1795.0000, 1796.4000, 1797.8000, 1799.0000,
-    1800.4000, 1801.8000, 1803.0000, 1804.6000, 1805.8000, 1807.0000, 1808.4000, 1809.4000,
-    1810.2000, 1811.2000, 1812.0000, 1813.0000, 1814.0000, 1815.0000, 1816.0000, 1817.0000,
-    1818.0000, 1819.0000, 1819.8000, 1820.8000, 1821.8000, 1822.8000, 1823.8000, 1824.8000,
-    1825.6000, 1826.4000, 1827.2000, 1828.0000, 1829.0000, 1830.0000, 1831.0000, 1831.8000,
-    1832.8000, 1833.8000, 1834.6000, 1835.4000, 1836.2000, 1837.0000, 1838.0000, 1839.0000,
-    1839.8000, 1841.0000, 1842.2000, 1843.8000, 1844.8000, 1846.0000, 1847.2000, 1848.8000,
-    1849.8000, 1851.0000, 1852.4000, 1853.8000, 1855.0000, 1856.0000, 1857.0000, 1858.0000,
-    1858.8000, 1859.8000, 1860.8000, 1861.6000, 1862.4000, 1863.2000, 1864.0000, 1865.0000,
-    1866.0000, 1866.8000, 1867.8000, 1868.8000, 1869.8000, 1870.4000, 1871.2000, 1872.0000,
-    1873.6000, 1874.8000, 1876.0000, 1877.2000, 1878.8000, 1879.8000, 1881.0000, 1882.4000,
-    1883.8000, 1885.0000, 1886.0000, 1887.6000, 1888.8000, 1890.0000, 1891.4000, 1892.8000,
-    1894.0000, 1895.4000, 1896.8000, 1898.0000, 1899.4000, 1900.8000, 1902.0000, 1903.2000,
-    1904.8000, 1905.8000, 1906.8000, 1907.8000, 1909.0000, 1910.0000, 1911.0000, 1912.0000,
-    1913.2000, 1914.4000, 1915.6000, 1916.8000, 1917.8000, 1918.8000, 1919.8000, 1920.8000,
-    1921.6000, 1922.0000, 1923.0000, 1923.8000, 1924.6000, 1925.0000, 1926.0000, 1926.8000,
-    1927.4000, 1928.0000, 1929.0000, 1929.8000, 1930.4000, 1931.0000, 1932.0000, 1932.8000,
-    1933.4000, 1934.0000, 1934.8000, 1935.8000, 1936.6000, 1937.8000, 1938.8000, 1939.8000,
-    1941.0000, 1942.0000, 1943.0000, 1944.2000, 1945.4000, 1946.6000, 1947.8000, 1948.8000,
-    1950.0000, 1951.0000, 1952.0000, 1953.2000, 1954.6000, 1955.8000, 1956.8000, 1958.0000,
-    1959.0000, 1960.4000, 1961.8000, 1962.8000, 1964.0000, 1965.0000, 1966.2000, 1967.6000,
-    1968.8000, 1970.4000, 1972.0000, 1973.4000, 1975.0000, 1976.6000, 1978.0000, 1979.8000,
-    1981.0000, 1982.8000, 1984.0000, 1985.0000, 1985.8000, 1986.8000, 1987.6000, 1988.2000,
-    1989.0000, 1990.0000, 1990.8000, 1991.6000, 1992.4000, 1993.0000, 1994.0000, 1994.8000,
-    1995.8000, 1996.4000, 1997.2000, 1998.0000, 1999.0000, 1999.8000, 2000.8000, 2001.8000,
-    2002.8000, 2003.8000, 2004.8000, 2005.8000, 2006.8000, 2007.8000, 2008.8000, 2009.8000,
-    2010.8000, 2011.8000, 2012.8000, 2013.8000, 2014.8000, 2015.8000, 2017.0000, 2018.6000,
-    2019.8000, 2021.0000, 2022.6000, 2023.8000, 2025.0000, 2026.6000, 2027.8000, 2029.0000,
-    2030.6000, 2031.8000, 2032.6000, 2033.0000, 2033.4000, 2033.8000, 2034.2000, 2034.8000,
-    2035.0000, 2035.8000, 2036.0000, 2036.6000, 2037.0000, 2037.4000, 2037.8000, 2038.2000,
-    2038.8000, 2039.0000, 2039.8000, 2040.0000, 2040.6000, 2041.0000, 2041.4000, 2041.8000,
-    2042.0000, 2042.8000, 2043.0000, 2043.8000, 2044.0000, 2044.4000, 2044.8000, 2045.2000,
-    2045.8000, 2046.0000, 2046.8000, 2047.0000, 2047.6000, 2048.0000, 2048.8000, 2050.0000,
-    2051.2000, 2052.6001, 2053.8000, 2055.0000, 2056.0000, 2057.3999, 2058.8000, 2060.0000,
-    2061.0000, 2062.3999, 2063.8000, 2064.8000, 2065.8000, 2066.8000, 2067.8000, 2068.8000,
-    2069.8000, 2070.8000, 2071.8000, 2072.8000, 2073.8000, 2074.8000, 2075.8000, 2076.8000,
-    2077.6001, 2078.6001, 2079.6001, 2080.8000, 2081.8000, 2083.0000, 2084.2000, 2085.6001,
-    2086.8000, 2088.0000, 2089.0000, 2090.3999, 2091.8000, 2092.8000, 2094.0000, 2095.2000,
-    2096.3999, 2097.0000, 2098.0000, 2098.8000, 2099.6001, 2100.2000, 2101.0000, 2101.8000,
-    2102.8000, 2103.3999, 2104.0000, 2105.0000, 2105.8000, 2106.6001, 2107.2000, 2108.0000,
-    2108.8000, 2109.8000, 2110.3999, 2111.0000, 2112.0000, 2113.0000, 2114.3999, 2115.8000,
-    2117.0000, 2118.3999, 2119.8000, 2121.0000, 2122.3999, 2123.8000, 2125.0000, 2126.3999,
-    2127.8000, 2129.0000, 2130.8000, 2132.0000, 2133.8000, 2135.2000, 2136.8000, 2138.2000,
-    2139.8000, 2141.2000, 2142.8000, 2144.2000, 2145.3999, 2146.6001, 2147.8000, 2148.8000,
-    2149.8000, 2151.0000, 2152.0000, 2153.0000, 2154.2000, 2155.3999, 2156.6001, 2157.8000,
-    2158.8000, 2159.8000, 2160.8000, 2161.6001, 2162.2000, 2163.0000, 2164.0000, 2164.8000,
-    2165.8000, 2166.3999, 2167.0000, 2168.0000, 2168.8000, 2169.8000, 2170.3999, 2171.0000,
-    2172.0000, 2172.8000, 2173.8000, 2174.3999, 2175.0000, 2176.0000, 2177.0000, 2178.0000,
-    2179.0000, 2179.8000, 2180.8000, 2181.8000, 2182.8000, 2183.8000, 2184.8000, 2185.8000,
-    2186.8000, 2187.8000, 2188.8000, 2189.8000, 2190.8000, 2191.8000, 2192.8000, 2193.8000,
-    2194.8000, 2195.8000, 2196.8000, 2197.8000, 2199.0000, 2200.0000, 2201.0000, 2202.0000,
-    2203.0000, 2204.0000, 2205.0000, 2206.0000, 2207.0000, 2208.0000, 2209.0000, 2209.8000,
-    2210.8000, 2211.8000, 2212.8000, 2213.6001, 2214.3999, 2215.2000, 2216.0000, 2217.0000,
-    2218.0000, 2219.0000, 2219.8000, 2220.8000, 2221.8000, 2222.6001, 2223.3999, 2224.3999,
-    2225.3999, 2226.3999, 2227.6001, 2228.6001, 2229.6001, 2230.8000, 2231.8000, 2232.8000,
-    2233.8000, 2234.8000, 2235.8000, 2236.8000, 2237.8000, 2238.8000, 2240.0000, 2241.8000,
-    2243.6001, 2245.6001, 2247.3999, 2249.2000, 2251.2000, 2253.0000, 2255.0000, 2256.8000,
-    2257.3999, 2258.0000, 2259.0000, 2259.8000, 2260.6001, 2261.2000, 2262.0000, 2262.8000,
-    2263.8000, 2264.6001, 2265.2000, 2266.0000, 2266.8000, 2267.8000, 2268.3999, 2269.0000,
-    2270.0000, 2270.8000, 2271.8000, 2272.3999, 2273.2000, 2274.0000, 2275.0000, 2276.0000,
-    2277.0000, 2277.8000, 2278.8000, 2279.8000, 2280.8000, 2281.6001, 2282.3999, 2283.2000,
-    2284.0000, 2285.0000, 2286.0000, 2287.0000, 2287.8000, 2289.0000, 2290.3999, 2291.8000,
-    2293.0000, 2294.6001, 2295.8000, 2297.0000, 2298.8000, 2300.0000, 2301.3999, 2302.8000,
-    2304.0000, 2305.3999, 2306.8000, 2308.0000, 2309.0000, 2310.6001, 2311.8000, 2313.0000,
-    2314.2000, 2315.8000, 2316.8000, 2318.0000, 2319.3999, 2320.8000, 2321.8000, 2322.8000,
-    2323.8000, 2324.8000, 2325.8000, 2326.8000, 2327.8000, 2329.0000, 2330.0000, 2331.0000,
-    2332.0000, 2333.0000, 2334.0000, 2335.0000, 2336.0000, 2337.3999, 2338.8000, 2339.8000,
-    2341.0000, 2342.2000, 2343.6001, 2344.8000, 2346.0000, 2347.0000, 2348.3999, 2349.8000,
-    2350.8000, 2352.0000, 2353.2000, 2354.8000, 2356.0000, 2357.2000, 2358.8000, 2360.0000,
-    2361.2000, 2362.8000, 2364.0000, 2365.2000, 2366.8000, 2368.0000, 2368.8000, 2369.8000,
-    2370.3999, 2371.0000, 2372.0000, 2372.8000, 2373.8000, 2374.2000, 2375.0000, 2376.0000,
-    2376.8000, 2377.6001, 2378.2000, 2379.0000, 2379.8000, 2380.8000, 2381.3999, 2382.0000,
-    2383.0000, 2383.8000, 2385.0000, 2387.0000, 2389.0000, 2391.0000, 2393.0000, 2395.0000,
-    2397.0000, 2399.0000, 2400.8000, 2401.2000, 2402.0000, 2402.8000, 2403.8000, 2404.3999,
-    2405.0000, 2406.0000, 2406.8000, 2407.6001, 2408.2000, 2409.0000, 2409.8000, 2410.8000,
-    2411.2000, 2412.0000, 2412.8000, 2413.8000, 2414.3999, 2415.0000, 2416.0000, 2417.0000,
-    2418.3999, 2419.8000, 2421.0000, 2422.2000, 2423.8000, 2425.0000, 2426.0000, 2427.6001,
-    2428.8000, 2430.0000, 2431.6001, 2432.8000, 2433.6001, 2434.3999, 2435.2000, 2436.0000,
-    2437.0000, 2438.0000, 2439.0000, 2439.8000, 2440.8000, 2441.8000, 2442.8000, 2443.6001,
-    2444.3999, 2445.2000, 2446.0000, 2447.0000, 2448.0000, 2449.0000, 2450.2000, 2451.6001,
-    2452.8000, 2453.8000, 2455.0000, 2456.2000, 2457.3999, 2458.8000, 2459.8000, 2461.0000,
-    2462.0000, 2463.3999, 2464.6001, 2465.2000, 2466.0000, 2467.0000, 2468.0000, 2468.8000,
-    2469.8000, 2470.8000, 2471.6001, 2472.3999, 2473.2000, 2474.0000, 2475.0000, 2476.0000,
-    2476.8000, 2477.8000, 2478.8000, 2479.6001, 2480.6001, 2481.8000, 2482.8000, 2484.0000,
-    2485.2000, 2486.6001, 2487.8000, 2489.0000, 2490.0000, 2491.2000, 2492.6001, 2493.8000,
-    2495.0000, 2496.0000, 2497.0000, 2498.2000, 2499.2000, 2500.3999, 2501.3999, 2502.6001,
-    2503.8000, 2504.8000, 2505.8000, 2506.8000, 2507.8000, 2508.8000, 2509.8000, 2511.0000,
-    2512.0000, 2513.0000, 2514.2000, 2515.3999, 2516.8000, 2517.8000, 2518.8000, 2520.0000,
-    2521.0000, 2522.2000, 2523.6001, 2524.8000, 2525.8000, 2527.0000, 2528.0000, 2529.6001,
-    2531.0000, 2532.6001, 2534.0000, 2535.6001, 2537.0000, 2538.6001, 2540.0000, 2541.6001,
-    2543.0000, 2544.6001, 2546.0000, 2547.8000, 2549.0000, 2550.8000, 2552.2000, 2553.8000,
-    2555.3999, 2557.0000, 2558.8000, 2560.0000, 2561.0000, 2561.8000, 2562.6001, 2563.0000,
-    2564.0000, 2564.8000, 2565.8000, 2566.2000, 2567.0000, 2567.8000, 2568.8000, 2569.3999,
-    2570.0000, 2571.0000, 2571.8000, 2572.6001, 2573.2000, 2574.0000, 2574.8000, 2575.8000,
-    2576.3999, 2577.8000, 2578.8000, 2579.8000, 2581.0000, 2582.0000, 2583.2000, 2584.6001,
-    2585.8000, 2586.8000, 2588.0000, 2589.0000, 2590.0000, 2591.3999, 2592.6001, 2593.3999,
-    2594.3999, 2595.2000, 2596.0000, 2597.0000, 2598.0000, 2599.0000, 2600.0000, 2601.0000,
-    2601.8000, 2602.8000, 2603.8000, 2604.8000, 2605.8000, 2606.8000, 2607.6001, 2608.6001,
-    2609.8000, 2611.0000, 2612.0000, 2613.6001, 2614.8000, 2616.0000, 2617.0000, 2618.3999,
-    2619.8000, 2620.8000, 2622.0000, 2623.2000, 2624.6001, 2625.3999, 2626.3999, 2627.2000,
-    2628.0000, 2629.0000, 2630.0000, 2631.0000, 2632.0000, 2633.0000, 2633.8000, 2634.8000,
-    2635.8000, 2636.8000, 2637.8000, 2638.6001, 2639.6001, 2640.3999, 2641.6001, 2642.6001,
-    2643.8000, 2644.8000, 2645.8000, 2646.8000, 2647.8000, 2648.8000, 2649.8000, 2651.0000,
-    2652.0000, 2653.0000, 2654.0000, 2655.0000, 2656.0000, 2657.2000, 2658.3999, 2659.6001,
-    2660.8000, 2661.8000, 2663.0000, 2664.0000, 2665.0000, 2666.2000, 2667.3999, 2668.6001,
-    2669.8000, 2670.8000, 2672.0000, 2673.0000, 2674.0000, 2675.0000, 2676.0000, 2677.0000,
-    2678.0000, 2679.0000, 2680.0000, 2681.0000, 2682.0000, 2683.0000, 2684.0000, 2685.0000,
-    2686.0000, 2687.0000, 2688.0000, 2689.0000, 2690.0000, 2691.3999, 2692.6001, 2693.8000,
-    2694.8000, 2695.8000, 2697.0000, 2698.0000, 2699.0000, 2700.0000, 2701.3999, 2702.6001,
-    2703.8000, 2704.8000, 2705.8000, 2706.8000, 2707.8000, 2708.8000, 2709.8000, 2710.6001,
-    2711.6001, 2712.6001, 2713.6001, 2714.6001, 2715.6001, 2716.3999, 2717.3999, 2718.3999,
-    2719.3999, 2720.8000, 2722.8000, 2725.0000, 2727.0000, 2729.3999, 2731.6001, 2733.8000,
-    2736.0000, 2737.0000, 2737.8000, 2738.8000, 2739.6001, 2740.3999, 2741.0000, 2742.0000,
-    2743.0000, 2743.8000, 2744.8000, 2745.6001, 2746.2000, 2747.0000, 2748.0000, 2748.8000,
-    2749.8000, 2750.8000, 2751.6001, 2752.2000, 2753.0000, 2753.8000, 2754.0000, 2754.8000,
-    2755.6001, 2756.0000, 2756.8000, 2757.3999, 2758.0000, 2758.8000, 2759.3999, 2760.0000,
-    2760.8000, 2761.2000, 2762.0000, 2762.8000, 2763.0000, 2763.8000, 2764.6001, 2765.0000,
-    2765.8000, 2766.3999, 2767.0000, 2767.8000, 2768.6001, 2769.8000, 2771.0000, 2772.0000,
-    2773.3999, 2774.8000, 2775.8000, 2777.0000, 2778.2000, 2779.6001, 2780.8000, 2782.0000,
-    2783.0000, 2784.2000, 2785.0000, 2786.0000, 2787.0000, 2787.8000, 2788.8000, 2789.8000,
-    2790.6001, 2791.3999, 2792.0000, 2793.0000, 2794.0000, 2794.8000, 2795.8000, 2796.8000,
-    2797.6001, 2798.3999, 2799.2000, 2800.0000, 2801.8000, 2803.0000, 2804.6001, 2805.8000,
-    2807.2000, 2808.8000, 2810.0000, 2811.8000, 2813.0000, 2814.3999, 2815.8000, 2817.3999,
-    2819.0000, 2820.6001, 2822.0000, 2823.8000, 2825.0000, 2826.8000, 2828.3999, 2830.0000,
-    2831.6001, 2832.8000, 2833.8000, 2834.6001, 2835.2000, 2836.0000, 2837.0000, 2838.0000,
-    2838.8000, 2839.8000, 2840.8000, 2841.6001, 2842.3999, 2843.2000, 2844.0000, 2845.0000,
-    2845.8000, 2846.8000, 2847.8000, 2848.8000, 2849.8000, 2851.0000, 2852.2000, 2853.8000,
-    2854.8000, 2856.0000, 2857.0000, 2858.6001, 2859.8000, 2861.0000, 2862.0000, 2863.3999,
-    2864.6001, 2865.2000, 2866.0000, 2867.0000, 2867.8000, 2868.8000, 2869.6001, 2870.3999,
-    2871.0000, 2872.0000, 2873.0000, 2873.8000, 2874.8000, 2875.3999, 2876.2000, 2877.0000,
-    2878.0000, 2878.8000, 2879.8000, 2880.6001, 2881.6001, 2882.6001, 2883.6001, 2884.6001,
-    2885.3999, 2886.3999, 2887.3999, 2888.3999, 2889.3999, 2890.2000, 2891.2000, 2892.2000,
-    2893.2000, 2894.0000, 2895.0000, 2896.0000, 2898.0000, 2900.0000, 2902.0000, 2904.0000,
-    2906.0000, 2908.0000, 2910.0000, 2912.0000, 2913.0000, 2914.0000, 2915.0000, 2916.0000,
-    2917.0000, 2918.0000, 2919.0000, 2920.0000, 2921.0000, 2922.0000, 2923.0000, 2924.0000,
-    2925.0000, 2926.0000, 2927.0000, 2928.0000, 2928.8000, 2929.8000, 2930.8000, 2931.3999,
-    2932.2000, 2933.0000, 2934.0000, 2935.0000, 2935.8000, 2936.8000, 2937.8000, 2938.3999,
-    2939.2000, 2940.0000, 2941.0000, 2942.0000, 2942.8000, 2943.8000, 2944.6001, 2945.3999,
-    2946.2000, 2947.0000, 2948.0000, 2949.0000, 2950.0000, 2950.8000, 2951.8000, 2952.8000,
-    2953.6001, 2954.3999, 2955.2000, 2956.0000, 2957.0000, 2958.0000, 2958.8000, 2959.8000,
-    2960.8000, 2961.8000, 2962.8000, 2963.6001, 2964.6001, 2965.3999, 2966.3999, 2967.2000,
-    2968.2000, 2969.0000, 2970.0000, 2971.0000, 2972.0000, 2973.0000, 2974.0000, 2974.8000,
-    2975.8000, 2977.0000, 2978.8000, 2980.0000, 2981.8000, 2983.0000, 2984.6001, 2986.0000,
-    2987.6001, 2989.0000, 2990.3999, 2992.0000, 2993.0000, 2994.0000, 2995.0000, 2996.0000,
-    2997.0000, 2998.0000, 2999.0000, 3000.0000, 3001.0000, 3002.0000, 3003.0000, 3004.0000,
-    3005.0000, 3006.0000, 3007.0000, 3008.0000, 3009.0000, 3010.8000, 3012.0000, 3013.6001,
-    3015.0000, 3016.3999, 3017.8000, 3019.2000, 3020.8000, 3022.0000, 3023.8000, 3024.8000,
-    3025.8000, 3026.6001, 3027.3999, 3028.2000, 3029.0000, 3030.0000, 3031.0000, 3032.0000,
-    3032.8000, 3033.8000, 3034.8000, 3035.6001, 3036.3999, 3037.2000, 3038.0000, 3039.0000,
-    3040.0000, 3040.8000, 3041.6001, 3042.2000, 3043.0000, 3043.8000, 3044.3999, 3045.0000,
-    3046.0000, 3046.8000, 3047.3999, 3048.0000, 3048.8000, 3049.8000, 3050.2000, 3051.0000,
-    3051.8000, 3052.6001, 3053.0000, 3054.0000, 3054.8000, 3055.3999, 3056.0000, 3057.0000,
-    3058.0000, 3059.0000, 3060.0000, 3061.0000, 3062.0000, 3063.0000, 3064.0000, 3065.0000,
-    3066.0000, 3067.0000, 3067.8000, 3068.8000, 3069.8000, 3070.8000, 3071.8000, 3073.0000,
-    3074.6001, 3075.8000, 3077.0000, 3078.8000, 3080.0000, 3081.3999, 3082.8000, 3084.0000,
-    3085.6001, 3087.0000, 3088.2000, 3089.0000, 3089.8000, 3090.8000, 3091.6001, 3092.2000,
-    3093.0000, 3094.0000, 3094.8000, 3095.6001, 3096.2000, 3097.0000, 3098.0000, 3098.8000,
-    3099.8000, 3100.3999, 3101.0000, 3102.0000, 3102.8000, 3103.8000, 3104.6001, 3105.8000,
-    3106.8000, 3107.8000, 3108.8000, 3110.0000, 3111.0000, 3112.0000, 3113.0000, 3114.2000,
-    3115.2000, 3116.3999, 3117.6001, 3118.8000, 3119.8000, 3120.8000, 3122.0000, 3123.2000,
-    3124.6001, 3125.8000, 3127.0000, 3128.0000, 3129.3999, 3130.8000, 3131.8000, 3133.0000,
-    3134.3999, 3135.8000, 3136.8000, 3138.0000, 3139.2000, 3140.6001, 3141.8000, 3143.0000,
-    3144.0000, 3145.6001, 3146.8000, 3148.0000, 3149.0000, 3150.3999, 3151.8000, 3152.8000,
-    3153.8000, 3154.8000, 3155.8000, 3156.8000, 3157.8000, 3158.8000, 3159.8000, 3160.8000,
-    3161.8000, 3162.8000, 3163.8000, 3164.8000, 3165.8000, 3166.8000, 3168.0000, 3168.8000,
-    3169.8000, 3170.3999, 3171.0000, 3172.0000, 3172.8000, 3173.8000, 3174.6001, 3175.2000,
-    3176.0000, 3176.8000, 3177.8000, 3178.6001, 3179.2000, 3180.0000, 3181.0000, 3181.8000,
-    3182.8000, 3183.3999, 3184.0000, 3185.0000, 3185.8000, 3186.8000, 3187.8000, 3188.3999,
-    3189.0000, 3190.0000, 3190.8000, 3191.8000, 3192.8000, 3193.3999, 3194.0000, 3195.0000,
-    3196.0000, 3196.8000, 3197.8000, 3198.3999, 3199.2000, 3200.0000, 3201.8000, 3203.8000,
-    3205.2000, 3207.0000, 3209.0000, 3210.8000, 3212.6001, 3214.2000, 3216.0000, 3217.0000,
-    3217.8000, 3218.8000, 3219.3999, 3220.0000, 3221.0000, 3221.8000, 3222.8000, 3223.3999,
-    3224.0000, 3225.0000, 3225.8000, 3226.8000, 3227.3999, 3228.0000, 3229.0000, 3229.8000,
-    3230.8000, 3231.3999, 3232.2000, 3233.3999, 3234.8000, 3235.8000, 3237.0000, 3238.0000,
-    3239.2000, 3240.3999, 3241.8000, 3242.8000, 3244.0000, 3245.0000, 3246.0000, 3247.3999,
-    3248.8000, 3249.8000, 3251.0000, 3252.0000, 3253.6001, 3254.8000, 3256.0000, 3257.0000,
-    3258.3999, 3259.8000, 3260.8000, 3262.0000, 3263.0000, 3264.2000, 3264.8000, 3265.2000,
-    3265.8000, 3266.0000, 3266.8000, 3267.0000, 3267.8000, 3268.0000, 3268.8000, 3269.0000,
-    3269.8000, 3270.0000, 3270.8000, 3271.0000, 3271.8000, 3272.0000, 3272.8000, 3273.0000,
-    3273.6001, 3274.0000, 3274.6001, 3275.0000, 3275.6001, 3276.0000, 3276.3999, 3277.0000,
-    3277.3999, 3277.8000, 3278.3999, 3278.8000, 3279.2000, 3279.8000, 3280.8000, 3282.0000,
-    3283.6001, 3285.0000, 3286.3999, 3287.8000, 3289.2000, 3290.8000, 3292.0000, 3293.8000,
-    3295.0000, 3296.6001, 3297.6001, 3298.6001, 3299.6001, 3300.6001, 3301.8000, 3302.8000,
-    3303.8000, 3304.8000, 3305.8000, 3306.8000, 3307.8000, 3308.8000, 3309.8000, 3310.8000,
-    3311.8000, 3312.8000, 3313.8000, 3314.8000, 3315.8000, 3317.0000, 3318.0000, 3319.0000,
-    3320.0000, 3321.0000, 3322.0000, 3323.0000, 3324.0000, 3325.0000, 3326.2000, 3327.2000,
-    3328.2000, 3329.0000, 3329.8000, 3330.3999, 3331.0000, 3331.8000, 3332.6001, 3333.0000,
-    3334.0000, 3334.8000, 3335.2000, 3336.0000, 3336.8000, 3337.6001, 3338.0000, 3338.8000,
-    3339.8000, 3340.2000, 3341.0000, 3341.8000, 3342.3999, 3343.0000, 3343.8000, 3344.6001,
-    3345.2000, 3346.0000, 3347.0000, 3347.8000, 3348.8000, 3349.3999, 3350.0000, 3351.0000,
-    3351.8000, 3352.8000, 3353.3999, 3354.0000, 3355.0000, 3355.8000, 3356.8000, 3357.6001,
-    3358.2000, 3359.0000, 3359.8000, 3361.0000, 3362.8000, 3364.0000, 3365.8000, 3367.0000,
-    3368.8000, 3370.0000, 3371.8000, 3373.2000, 3374.8000, 3376.0000, 3377.0000, 3377.8000,
-    3378.2000, 3379.0000, 3379.8000, 3380.3999, 3381.0000, 3381.8000, 3382.6001, 3383.0000,
-    3384.0000, 3384.8000, 3385.2000, 3386.0000, 3386.8000, 3387.3999, 3388.0000, 3388.8000,
-    3389.6001, 3390.0000, 3391.0000, 3391.8000, 3392.3999, 3393.2000, 3394.2000, 3395.0000,
-    3396.0000, 3397.0000, 3398.0000, 3399.0000, 3400.0000, 3401.0000, 3401.8000, 3402.8000,
-    3403.8000, 3404.8000, 3405.8000, 3406.8000, 3407.6001, 3408.6001, 3409.2000, 3410.0000,
-    3411.0000, 3411.8000, 3412.8000, 3413.6001, 3414.3999, 3415.0000, 3416.0000, 3417.0000,
-    3417.8000, 3418.8000, 3419.6001, 3420.2000, 3421.0000, 3422.0000, 3422.8000, 3423.8000,
-    3424.8000, 3426.0000, 3427.6001, 3428.8000, 3430.0000, 3431.6001, 3433.0000, 3434.2000,
-    3435.8000, 3437.0000, 3438.3999, 3439.8000, 3440.8000, 3441.6001, 3442.0000, 3443.0000,
-    3443.8000, 3444.6001, 3445.2000, 3446.0000, 3446.8000, 3447.8000, 3448.2000, 3449.0000,
-    3449.8000, 3450.8000, 3451.3999, 3452.0000, 3453.0000, 3453.8000, 3454.6001, 3455.0000,
-    3456.0000, 3456.8000, 3457.8000, 3458.3999, 3459.0000, 3460.0000, 3460.8000, 3461.8000,
-    3462.3999, 3463.0000, 3464.0000, 3464.8000, 3465.6001, 3466.2000, 3467.0000, 3468.0000,
-    3468.8000, 3469.6001, 3470.2000, 3471.0000, 3471.8000, 3472.8000, 3473.8000, 3474.8000,
-    3475.8000, 3476.8000, 3477.8000, 3478.8000, 3479.8000, 3480.8000, 3481.8000, 3482.8000,
-    3483.8000, 3484.8000, 3485.8000, 3486.8000, 3487.8000, 3488.8000, 3489.6001, 3490.3999,
-    3491.0000, 3492.0000, 3493.0000, 3493.8000, 3494.8000, 3495.8000, 3496.6001, 3497.2000,
-    3498.0000, 3499.0000, 3500.0000, 3500.8000, 3501.8000, 3502.8000, 3503.3999, 3504.2000,
-    3505.0000, 3505.8000, 3506.0000, 3506.8000, 3507.6001, 3508.0000, 3508.8000, 3509.6001,
-    3510.0000, 3510.8000, 3511.3999, 3512.0000, 3512.8000, 3513.3999, 3514.0000, 3514.8000,
-    3515.2000, 3516.0000, 3516.8000, 3517.2000, 3518.0000, 3518.8000, 3519.0000, 3519.8000,
-    3520.8000, 3521.2000, 3522.0000, 3522.8000, 3523.8000, 3524.2000, 3525.0000, 3525.8000,
-    3526.8000, 3527.2000, 3528.0000, 3528.8000, 3529.8000, 3530.2000, 3531.0000, 3531.8000,
-    3532.6001, 3533.2000, 3534.0000, 3534.8000, 3535.6001, 3536.2000, 3537.2000, 3538.3999,
-    3539.3999, 3540.6001, 3541.6001, 3542.8000, 3543.8000, 3544.8000, 3545.8000, 3546.8000,
-    3547.8000, 3548.8000, 3549.8000, 3550.8000, 3552.0000, 3552.8000, 3553.8000, 3554.2000,
-    3555.0000, 3556.0000, 3556.8000, 3557.6001, 3558.0000, 3559.0000, 3559.8000, 3560.8000,
-    3561.3999, 3562.0000, 3563.0000, 3563.8000, 3564.6001, 3565.2000, 3566.0000, 3566.8000,
-    3567.8000, 3568.3999, 3569.3999, 3570.3999, 3571.2000, 3572.2000, 3573.2000, 3574.0000,
-    3575.0000, 3576.0000, 3577.0000, 3578.0000, 3579.0000, 3580.0000, 3581.0000, 3582.0000,
-    3583.0000, 3584.0000, 3584.8000, 3585.2000, 3586.0000, 3586.8000, 3587.2000, 3588.0000,
-    3588.8000, 3589.0000, 3589.8000, 3590.6001, 3591.0000, 3591.8000, 3592.6001, 3593.0000,
-    3593.8000, 3594.3999, 3595.0000, 3595.8000, 3596.2000, 3597.0000, 3597.8000, 3598.2000,
-    3599.0000, 3599.8000, 3600.2000, 3601.0000, 3602.0000, 3603.0000, 3603.8000, 3604.8000,
-    3605.8000, 3606.8000, 3607.6001, 3608.3999, 3609.2000, 3610.0000, 3611.0000, 3612.0000,
-    3612.8000, 3613.8000, 3614.8000, 3615.8000, 3616.3999, 3617.0000, 3617.8000, 3618.2000,
-    3619.0000, 3619.8000, 3620.0000, 3620.8000, 3621.6001, 3622.0000, 3622.8000, 3623.3999,
-    3624.0000, 3624.8000, 3625.2000, 3625.8000, 3626.6001, 3627.0000, 3627.8000, 3628.3999,
-    3629.0000, 3629.8000, 3630.2000, 3631.0000, 3631.8000, 3632.0000, 3633.0000, 3633.8000,
-    3634.2000, 3635.0000, 3635.8000, 3636.2000, 3637.0000, 3637.8000, 3638.2000, 3639.0000,
-    3639.8000, 3640.3999, 3641.0000, 3641.8000, 3642.3999, 3643.0000, 3643.8000, 3644.3999,
-    3645.0000, 3645.8000, 3646.6001, 3647.0000, 3647.8000, 3648.8000, 3649.6001, 3650.3999,
-    3651.2000, 3652.0000, 3653.0000, 3654.0000, 3654.8000, 3655.8000, 3656.8000, 3657.6001,
-    3658.3999, 3659.0000, 3660.0000, 3661.0000, 3662.0000, 3662.8000, 3663.8000, 3664.3999,
-    3665.0000, 3665.6001, 3666.0000, 3666.8000, 3667.0000, 3667.8000, 3668.2000, 3668.8000,
-    3669.3999, 3670.0000, 3670.6001, 3671.0000, 3671.8000, 3672.0000, 3672.8000, 3673.0000,
-    3673.8000, 3674.2000, 3674.8000, 3675.3999, 3676.0000, 3676.6001, 3677.0000, 3677.8000,
-    3678.0000, 3678.8000, 3679.2000, 3679.8000, 3680.6001, 3681.6001, 3682.3999, 3683.3999,
-    3684.2000, 3685.0000, 3686.0000, 3687.0000, 3688.0000, 3689.0000, 3690.0000, 3690.8000,
-    3691.8000, 3692.8000, 3693.8000, 3694.8000, 3695.8000, 3696.3999, 3696.8000, 3697.2000,
-    3697.8000, 3698.0000, 3698.8000, 3699.0000, 3699.8000, 3700.0000, 3700.8000, 3701.0000,
-    3701.6001, 3702.0000, 3702.3999, 3702.8000, 3703.3999, 3703.8000, 3704.2000, 3704.8000,
-    3705.0000, 3705.8000, 3706.0000, 3706.8000, 3707.0000, 3707.8000, 3708.0000, 3708.6001,
-    3709.0000, 3709.3999, 3710.0000, 3710.3999, 3710.8000, 3711.2000, 3711.8000, 3712.2000,
-    3713.3999, 3714.6001, 3715.8000, 3716.8000, 3718.0000, 3719.0000, 3720.0000, 3721.2000,
-    3722.3999, 3723.6001, 3724.8000, 3725.8000, 3726.8000, 3728.0000, 3728.6001, 3729.0000,
-    3729.2000, 3729.8000, 3730.0000, 3730.6001, 3730.8000, 3731.2000, 3731.8000, 3732.0000,
-    3732.6001, 3732.8000, 3733.0000, 3733.8000, 3734.0000, 3734.3999, 3734.8000, 3735.0000,
-    3735.8000, 3736.0000, 3736.3999, 3736.8000, 3737.0000, 3737.8000, 3738.0000, 3738.3999,
-    3738.8000, 3739.0000, 3739.8000, 3740.0000, 3740.2000, 3740.8000, 3741.0000, 3741.6001,
-    3742.0000, 3742.2000, 3742.8000, 3743.0000, 3743.6001, 3744.0000, 3744.8000, 3746.0000,
-    3747.8000, 3749.2000, 3751.0000, 3752.6001, 3754.0000, 3755.8000, 3757.0000, 3758.8000,
-    3760.2000, 3761.0000, 3761.8000, 3762.6001, 3763.2000, 3764.0000, 3764.8000, 3765.6001,
-    3766.2000, 3767.0000, 3767.8000, 3768.6001, 3769.0000, 3770.0000, 3770.8000, 3771.6001,
-    3772.0000, 3773.0000, 3773.8000, 3774.6001, 3775.0000, 3776.0000, 3776.8000, 3777.0000,
-    3777.8000, 3778.2000, 3778.8000, 3779.3999, 3780.0000, 3780.6001, 3781.0000, 3781.8000,
-    3782.0000, 3782.8000, 3783.2000, 3783.8000, 3784.6001, 3785.0000, 3785.8000, 3786.0000,
-    3786.8000, 3787.2000, 3787.8000, 3788.3999, 3789.0000, 3789.6001, 3790.0000, 3790.8000,
-    3791.0000, 3791.8000, 3792.2000, 3792.8000, 3793.3999, 3794.0000, 3794.6001, 3795.0000,
-    3795.6001, 3796.0000, 3796.8000, 3797.0000, 3797.8000, 3798.0000, 3798.8000, 3799.2000,
-    3799.8000, 3800.2000, 3800.8000, 3801.3999, 3802.0000, 3802.6001, 3803.0000, 3803.8000,
-    3804.0000, 3804.8000, 3805.0000, 3805.8000, 3806.0000, 3806.8000, 3807.2000, 3807.8000,
-    3808.3999, 3809.0000, 3809.8000, 3810.2000, 3810.8000, 3811.6001, 3812.0000, 3812.8000,
-    3813.3999, 3814.0000, 3814.8000, 3815.0000, 3815.8000, 3816.6001, 3817.0000, 3817.8000,
-    3818.3999, 3819.0000, 3819.8000, 3820.0000, 3820.8000, 3821.6001, 3822.0000, 3822.8000,
-    3823.2000, 3824.0000, 3825.0000, 3826.8000, 3828.0000, 3829.6001, 3831.0000, 3832.6001,
-    3834.0000, 3835.3999, 3836.8000, 3838.3999, 3839.8000, 3840.8000, 3841.0000, 3841.8000,
-    3842.0000, 3842.8000, 3843.0000, 3843.8000, 3844.0000, 3844.8000, 3845.2000, 3845.8000,
-    3846.2000, 3846.8000, 3847.3999, 3847.8000, 3848.3999, 3849.0000, 3849.3999, 3850.0000,
-    3850.6001, 3851.0000, 3851.6001, 3852.0000, 3852.8000, 3853.0000, 3853.8000, 3854.0000,
-    3854.8000, 3855.0000, 3855.8000, 3856.0000, 3856.8000, 3857.0000, 3857.8000, 3858.0000,
-    3858.8000, 3859.0000, 3859.6001, 3860.0000, 3860.6001, 3861.0000, 3861.3999, 3862.0000,
-    3862.3999, 3862.8000, 3863.2000, 3863.8000, 3864.2000, 3864.8000, 3865.2000, 3865.8000,
-    3866.0000, 3866.8000, 3867.0000, 3867.8000, 3868.0000, 3868.8000, 3869.0000, 3869.6001,
-    3870.0000, 3870.6001, 3871.0000, 3871.3999, 3872.0000, 3872.6001, 3873.0000, 3873.8000,
-    3874.6001, 3875.0000, 3875.8000, 3876.6001, 3877.0000, 3877.8000, 3878.6001, 3879.0000,
-    3879.8000, 3880.6001, 3881.0000, 3881.8000, 3882.6001, 3883.0000, 3883.8000, 3884.6001,
-    3885.0000, 3885.8000, 3886.6001, 3887.0000, 3887.8000, 3888.6001, 3889.0000, 3889.8000,
-    3890.2000, 3890.8000, 3891.3999, 3892.0000, 3892.8000, 3893.0000, 3893.8000, 3894.3999,
-    3895.0000, 3895.8000, 3896.0000, 3896.8000, 3897.3999, 3898.0000, 3898.8000, 3899.0000,
-    3899.8000, 3900.3999, 3901.0000, 3901.6001, 3902.0000, 3902.8000, 3903.2000, 3904.0000,
-    3904.6001, 3905.0000, 3905.8000, 3906.0000, 3906.8000, 3907.2000, 3908.0000, 3908.6001,
-    3909.0000, 3909.8000, 3910.0000, 3910.8000, 3911.2000, 3912.0000, 3912.6001, 3913.0000,
-    3913.8000, 3914.0000, 3914.8000, 3915.2000, 3915.8000, 3916.6001, 3917.0000, 3917.8000,
-    3918.0000, 3918.8000, 3919.2000, 3919.8000, 3920.6001, 3921.0000, 3921.6001, 3922.0000,
-    3922.8000, 3923.0000, 3923.8000, 3924.2000, 3924.8000, 3925.3999, 3926.0000, 3926.6001,
-    3927.0000, 3927.8000, 3928.0000, 3928.8000, 3929.0000, 3929.8000, 3930.2000, 3930.8000,
-    3931.3999, 3932.0000, 3932.6001, 3933.0000, 3933.8000, 3934.0000, 3934.8000, 3935.0000,
-    3935.8000, 3936.2000, 3937.0000, 3937.8000, 3938.2000, 3939.0000, 3939.8000, 3940.0000,
-    3940.8000, 3941.6001, 3942.0000, 3942.8000, 3943.6001, 3944.0000, 3944.8000, 3945.3999,
-    3946.0000, 3946.8000, 3947.3999, 3948.0000, 3948.8000, 3949.3999, 3950.0000, 3950.8000,
-    3951.2000, 3952.0000, 3952.8000, 3953.2000, 3954.0000, 3954.8000, 3955.2000, 3956.0000,
-    3956.8000, 3957.2000, 3958.0000, 3958.8000, 3959.2000, 3960.0000, 3960.8000, 3961.2000,
-    3962.0000, 3962.8000, 3963.3999, 3964.0000, 3964.8000, 3965.3999, 3966.0000, 3966.8000,
-    3967.3999, 3968.0000, 3968.8000, 3969.2000, 3969.8000, 3970.6001, 3971.0000, 3971.8000,
-    3972.2000, 3973.0000, 3973.6001, 3974.0000, 3974.8000, 3975.3999, 3976.0000, 3976.8000,
-    3977.0000, 3977.8000, 3978.3999, 3979.0000, 3979.8000, 3980.0000, 3980.8000, 3981.3999,
-    3982.0000, 3982.8000, 3983.2000, 3983.8000, 3984.6001, 3985.0000, 3986.0000, 3986.8000,
-    3987.3999, 3988.0000, 3988.8000, 3989.6001, 3990.0000, 3991.0000, 3991.8000, 3992.3999,
-    3993.0000, 3993.8000, 3994.6001, 3995.0000, 3996.0000, 3996.8000, 3997.3999, 3998.0000,
-    3998.8000, 3999.6001, 4000.0000, 4002.6001, 4004.8000, 4007.0000, 4009.3999, 4011.8000,
-    4014.0000, 4016.2000, 4026.80,
-];
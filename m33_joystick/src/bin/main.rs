@@ -1,19 +1,27 @@
 #![no_std]
 #![no_main]
 
-use defmt::{info, println};
+use defmt::{info, println, warn};
 use embassy_executor::Spawner;
-use embassy_time::{Duration, Timer};
+use embassy_time::{Duration, Instant, Ticker, Timer};
+use esp32_kit::adc::{self, AdcCalibration};
+use esp32_kit::filter::{oversample, Ema, Filter, Median};
+use esp32_kit::joystick::{Calibration, CalibrationCapture, Event, Joystick, JoystickConfig};
+use esp32_kit::storage;
 use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
-use esp_hal::clock::CpuClock;
 use esp_hal::gpio::{Input, InputConfig, Pull};
-use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
-
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
+use esp_storage::FlashStorage;
+
+// Correcting ADC non-linearity issue, see esp32-kit/calibration
+const ADC_CALIBRATION: AdcCalibration = AdcCalibration::new(adc::Attenuation::_11dB);
+// Readings averaged into every sample
+const OVERSAMPLING: usize = 4;
+// First sector of the nvs partition, shared with m49_pantilt
+const CALIBRATION_OFFSET: u32 = 0x9000;
+const PERIOD: Duration = Duration::from_millis(20);
+// Axis changes smaller than this are not printed
+const PRINT_STEP: f32 = 0.05;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
@@ -21,11 +29,9 @@ esp_bootloader_esp_idf::esp_app_desc!();
 async fn main(_spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -35,51 +41,89 @@ async fn main(_spawner: Spawner) {
     );
 
     let mut adc2_config = AdcConfig::new();
-    let mut vrx_pin 
-    = adc2_config.enable_pin(peripherals.GPIO13, Attenuation::_11dB);
-
-    let mut vry_pin 
-    = adc2_config.enable_pin(peripherals.GPIO14, Attenuation::_11dB);
-
+    let mut vrx_pin = adc2_config.enable_pin(peripherals.GPIO13, Attenuation::_11dB);
+    let mut vry_pin = adc2_config.enable_pin(peripherals.GPIO14, Attenuation::_11dB);
     let mut adc2 = Adc::new(peripherals.ADC2, adc2_config);
 
-    let mut prev_vrx: u16 = 0;
-    let mut prev_vry: u16 = 0;
-    let mut prev_btn_state = false;
-    let mut print_vals = true;
-
-    loop {
-        let Ok(vry): Result<u16, _> = nb::block!(adc2.read_oneshot(&mut vry_pin)) else {
-            continue;
-        };
-        let Ok(vrx): Result<u16, _> = nb::block!(adc2.read_oneshot(&mut vrx_pin)) else {
-            continue;
-        };
-
-        if vrx.abs_diff(prev_vrx) > 100 {
-            prev_vrx = vrx;
-            print_vals = true;
+    let mut vrx_filter = Median::<3>::new().then(Ema::new(0.5));
+    let mut vry_filter = Median::<3>::new().then(Ema::new(0.5));
+
+    let mut read_stick = || -> Option<[f64; 2]> {
+        let vrx = oversample(OVERSAMPLING, || {
+            nb::block!(adc2.read_oneshot(&mut vrx_pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        });
+        let vry = oversample(OVERSAMPLING, || {
+            nb::block!(adc2.read_oneshot(&mut vry_pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        });
+        Some([vrx_filter.update(vrx.ok()?), vry_filter.update(vry.ok()?)])
+    };
+
+    let mut flash = FlashStorage::new();
+    let mut calibration = match storage::load::<Calibration, _>(&mut flash, CALIBRATION_OFFSET) {
+        Ok(Some(calibration)) => calibration,
+        _ => {
+            warn!("No calibration stored, hold the button while resetting to calibrate");
+            Calibration::default()
         }
-
-        if vry.abs_diff(prev_vry) > 100 {
-            prev_vry = vry;
-            print_vals = true;
+    };
+
+    // Holding the button during boot starts the calibration
+    if btn.is_low() {
+        info!("Calibrating: let go of the stick");
+        Timer::after(Duration::from_secs(2)).await;
+        let mut capture = CalibrationCapture::new();
+        for _ in 0..50 {
+            if let Some(raw) = read_stick() {
+                capture.rest(raw);
+            }
+            Timer::after(PERIOD).await;
         }
-
-        let btn_state = btn.is_low();
-        if btn_state && !prev_btn_state {
-            println!("Button Pressed");
-            print_vals = true;
+        info!("Calibrating: circle the stick along its edge for 5 seconds");
+        for _ in 0..250 {
+            if let Some(raw) = read_stick() {
+                capture.sweep(raw);
+            }
+            Timer::after(PERIOD).await;
+        }
+        match capture.finish() {
+            Some(captured) => {
+                calibration = captured;
+                match storage::save(&mut flash, CALIBRATION_OFFSET, &calibration) {
+                    Ok(()) => info!("Calibration saved"),
+                    Err(_) => warn!("Could not write the calibration to flash"),
+                }
+            }
+            None => warn!("The stick did not move far enough, calibration discarded"),
         }
-        prev_btn_state = btn_state;
+    }
 
-        if print_vals {
-            print_vals = false;
+    let mut joystick = Joystick::new(JoystickConfig::default(), calibration);
+    let mut printed = [0.0f32; 2];
+    let mut ticker = Ticker::every(PERIOD);
 
-            println!("X: {} Y: {}\r\n", vrx, vry);
+    loop {
+        if let Some(raw) = read_stick() {
+            let now_ms = Instant::now().as_millis();
+            for event in joystick.update(now_ms, raw, btn.is_low()) {
+                let name = match event {
+                    Event::Up => "Up",
+                    Event::Down => "Down",
+                    Event::Left => "Left",
+                    Event::Right => "Right",
+                    Event::Click => "Click",
+                    Event::DoubleClick => "DoubleClick",
+                    Event::LongPress => "LongPress",
+                };
+                println!("{}", name);
+            }
+
+            let [x, y] = joystick.axes();
+            if (x - printed[0]).abs() >= PRINT_STEP || (y - printed[1]).abs() >= PRINT_STEP {
+                printed = [x, y];
+                println!("X: {} Y: {}", x, y);
+            }
         }
 
-        Timer::after(Duration::from_millis(50)).await;
+        ticker.next().await;
     }
 }
-
@@ -16,28 +16,15 @@ use embassy_executor::Spawner;
 use embassy_time::Timer;
 use esp_hal::time;
 use esp_hal::timer::timg::TimerGroup;
-use esp_hal::{clock::CpuClock, rng::Rng};
+use esp_hal::rng::Rng;
 use esp_println::{self as _, println};
 use esp_wifi::ble::controller::BleConnector;
 use esp_wifi::{init, EspWifiController};
 use heapless::String;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 extern crate alloc;
 
-// When you are okay with using a nightly compiler it's better to use https://docs.rs/static_cell/2.1.0/static_cell/macro.make_static.html
-macro_rules! mk_static {
-    ($t:ty,$val:expr) => {{
-        static STATIC_CELL: static_cell::StaticCell<$t> = static_cell::StaticCell::new();
-        #[deny(unused_attributes)]
-        let x = STATIC_CELL.uninit().write(($val));
-        x
-    }};
-}
+use esp32_kit::mk_static;
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
@@ -45,13 +32,11 @@ esp_bootloader_esp_idf::esp_app_desc!();
 async fn main(_spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     esp_alloc::heap_allocator!(size: 72 * 1024);
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -145,6 +130,3 @@ const fn scale_random_u32(rand_val: u32) -> u32 {
     let max = 110;
     min + (rand_val % (max - min + 1))
 }
-
-
-
@@ -1,26 +1,19 @@
 #![no_std]
 #![no_main]
 
-use esp_hal::clock::CpuClock;
 use esp_hal::ledc::channel::ChannelIFace;
 use esp_hal::ledc::timer::TimerIFace;
 use esp_hal::ledc::{channel, timer, HighSpeed, Ledc};
 use esp_hal::main;
 use esp_hal::time::Rate;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[main]
 fn main() -> ! {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     // let led = peripherals.GPIO2;
     let led = peripherals.GPIO5;
@@ -52,4 +45,3 @@ fn main() -> ! {
         while channel0.is_duty_fade_running() {}
     }
 }
-
@@ -1 +1,6 @@
 #![no_std]
+
+pub mod time;
+pub mod wifi;
+
+pub use esp32_kit::mk_static;
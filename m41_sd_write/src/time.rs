@@ -0,0 +1,169 @@
+//! Real time for the SD card: SNTP keeps the RTC on UTC, [`CLOCK`] makes it local time.
+
+use core::cell::RefCell;
+
+use embassy_net::dns::DnsQueryType;
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{IpEndpoint, Stack};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{with_timeout, Duration, Timer};
+use embedded_sdmmc::{TimeSource, Timestamp};
+use esp32_kit::clock::{DateTime, TimeZone, WallClock};
+use esp32_kit::sntp::{self, Sample, SntpError};
+use esp_hal::rtc_cntl::Rtc;
+use esp_println::println;
+
+const NTP_SERVER: &str = match option_env!("NTP_SERVER") {
+    Some(server) => server,
+    None => "pool.ntp.org",
+};
+// Files get local time, e.g. TimeZone::CENTRAL_EUROPE with its summer time
+const ZONE: TimeZone = TimeZone::CHINA;
+
+const SYNC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const RETRY_INTERVAL: Duration = Duration::from_secs(15);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Turns RTC readings into local time, learns the RTC drift between syncs.
+pub static CLOCK: Mutex<CriticalSectionRawMutex, RefCell<WallClock>> =
+    Mutex::new(RefCell::new(WallClock::new(ZONE)));
+// Set after every sync
+static SYNCED: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+#[derive(Debug)]
+enum SyncError {
+    Dns,
+    Send,
+    Timeout,
+    Sntp(SntpError),
+}
+
+/// Local time, `None` until the first sync.
+pub fn now(rtc: &Rtc) -> Option<DateTime> {
+    CLOCK.lock(|clock| clock.borrow().local(rtc.current_time_us()))
+}
+
+pub async fn wait_for_sync() {
+    if !CLOCK.lock(|clock| clock.borrow().is_synced()) {
+        SYNCED.wait().await;
+    }
+}
+
+/// Sets the RTC from [`NTP_SERVER`] every hour, or sooner while that fails.
+#[embassy_executor::task]
+pub async fn sntp_task(stack: Stack<'static>, rtc: &'static Rtc<'static>) {
+    let mut rx_meta = [PacketMetadata::EMPTY; 2];
+    let mut rx_buffer = [0; 256];
+    let mut tx_meta = [PacketMetadata::EMPTY; 2];
+    let mut tx_buffer = [0; 256];
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    // Any free local port
+    socket.bind(0).unwrap();
+
+    loop {
+        stack.wait_config_up().await;
+        let wait = match sync(stack, &socket, rtc).await {
+            Ok(sample) => {
+                let (local, drift) = CLOCK.lock(|clock| {
+                    let mut clock = clock.borrow_mut();
+                    let set = clock.sync(rtc.current_time_us(), sample.offset_us);
+                    rtc.set_current_time_us(set);
+                    (clock.local(set), clock.drift())
+                });
+                SYNCED.signal(());
+                if let Some(local) = local {
+                    println!("Clock set to {}", local);
+                }
+                println!(
+                    "Off by {} ms, round trip {} ms, drift {:?} ppm",
+                    sample.offset_us / 1000,
+                    sample.delay_us / 1000,
+                    drift.ppm()
+                );
+                SYNC_INTERVAL
+            }
+            // The server asks to be left alone for a while
+            Err(SyncError::Sntp(e @ SntpError::KissOfDeath(_))) => {
+                println!("SNTP: {}", e);
+                SYNC_INTERVAL
+            }
+            Err(e) => {
+                println!("SNTP sync failed: {:?}", e);
+                RETRY_INTERVAL
+            }
+        };
+        Timer::after(wait).await;
+    }
+}
+
+async fn sync(
+    stack: Stack<'static>,
+    socket: &UdpSocket<'_>,
+    rtc: &Rtc<'_>,
+) -> Result<Sample, SyncError> {
+    // Pool names hand out a different server now and then
+    let addresses = stack
+        .dns_query(NTP_SERVER, DnsQueryType::A)
+        .await
+        .map_err(|_| SyncError::Dns)?;
+    let server = IpEndpoint::new(*addresses.first().ok_or(SyncError::Dns)?, sntp::PORT);
+
+    let mut packet = [0; sntp::PACKET_LEN];
+    let sent = rtc.current_time_us();
+    let len = sntp::request(sent, &mut packet);
+    socket
+        .send_to(&packet[..len], server)
+        .await
+        .map_err(|_| SyncError::Send)?;
+
+    let mut reply = [0; 128];
+    with_timeout(REPLY_TIMEOUT, async {
+        loop {
+            let Ok((len, _)) = socket.recv_from(&mut reply).await else {
+                continue;
+            };
+            let received = rtc.current_time_us();
+            match sntp::parse_reply(&reply[..len], sent, received) {
+                // Late answers to an earlier request
+                Err(SntpError::UnexpectedReply) => continue,
+                result => return result.map_err(SyncError::Sntp),
+            }
+        }
+    })
+    .await
+    .map_err(|_| SyncError::Timeout)?
+}
+
+/// File times for embedded-sdmmc, in local time.
+pub struct SdTimeSource {
+    rtc: &'static Rtc<'static>,
+}
+
+impl SdTimeSource {
+    pub fn new(rtc: &'static Rtc<'static>) -> Self {
+        Self { rtc }
+    }
+}
+
+impl TimeSource for SdTimeSource {
+    fn get_timestamp(&self) -> Timestamp {
+        // FAT dates start in 1980, which is also what files get before a sync
+        let now = now(self.rtc).unwrap_or(DateTime::from_unix(315_532_800));
+        Timestamp {
+            year_since_1970: (now.year - 1970).clamp(10, 255) as u8,
+            zero_indexed_month: now.month - 1,
+            zero_indexed_day: now.day - 1,
+            hours: now.hour,
+            minutes: now.minute,
+            seconds: now.second,
+        }
+    }
+}
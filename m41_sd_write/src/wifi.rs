@@ -0,0 +1,30 @@
+use embassy_executor::Spawner;
+use embassy_net::{DhcpConfig, Stack, StackResources};
+use esp32_kit_wifi::station;
+use esp_hal::rng::Rng;
+use esp_wifi::EspWifiController;
+
+use crate::mk_static;
+
+pub use station::wait_for_connection;
+
+/// Joins one of the known networks with DHCP, the stack is up once
+/// [`wait_for_connection`] returns.
+pub fn start_wifi(
+    esp_wifi_ctrl: &'static EspWifiController<'static>,
+    wifi: esp_hal::peripherals::WIFI<'static>,
+    rng: Rng,
+    spawner: &Spawner,
+) -> Stack<'static> {
+    let net_config = embassy_net::Config::dhcpv4(DhcpConfig::default());
+    // DHCP, DNS and the SNTP socket
+    station::start_station(
+        esp_wifi_ctrl,
+        wifi,
+        rng,
+        net_config,
+        mk_static!(StackResources<3>, StackResources::<3>::new()),
+        esp32_kit_wifi::known_networks!(),
+        spawner,
+    )
+}
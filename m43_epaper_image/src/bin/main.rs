@@ -8,8 +8,6 @@ use embedded_graphics::image::Image;
 use embedded_graphics::prelude::*;
 use embedded_graphics::primitives::{Circle, PrimitiveStyle};
 use epd_waveshare::prelude::WaveshareDisplay;
-use esp_hal::clock::CpuClock;
-use esp_hal::timer::timg::TimerGroup;
 use esp_println as _;
 
 use embedded_hal_bus::spi::ExclusiveDevice;
@@ -34,11 +32,9 @@ esp_bootloader_esp_idf::esp_app_desc!();
 async fn main(_spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
-    let timer0 = TimerGroup::new(peripherals.TIMG1);
-    esp_hal_embassy::init(timer0.timer0);
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
     info!("Embassy initialized!");
 
@@ -99,4 +95,3 @@ async fn main(_spawner: Spawner) {
         Timer::after(Duration::from_secs(60)).await;
     }
 }
-
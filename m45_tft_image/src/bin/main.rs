@@ -2,7 +2,6 @@
 #![no_main]
 
 // Usual imports
-use esp_hal::clock::CpuClock;
 use esp_hal::main;
 use esp_hal::time::{Duration, Instant};
 use esp_println as _;
@@ -28,17 +27,11 @@ use ili9341::{DisplaySize240x320, Ili9341, Orientation};
 // For managing GPIO state
 use esp_hal::gpio::{Level, Output, OutputConfig};
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[main]
 fn main() -> ! {
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     // Initialize SPI
     let spi = Spi::new(
@@ -80,4 +73,3 @@ fn main() -> ! {
         while delay_start.elapsed() < Duration::from_millis(5000) {}
     }
 }
-
@@ -0,0 +1,106 @@
+#![no_std]
+#![no_main]
+
+use defmt::info;
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Timer};
+use esp32_kit::adc::{self, AdcCalibration};
+use esp32_kit::filter::{oversample, Ema, Filter};
+use esp32_kit::hcsr04::{Hcsr04, Hcsr04Error, RangeEnd, MIN_DISTANCE_CM};
+use esp32_kit::thermistor::{Divider, Model, Thermistor};
+use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
+use esp_hal::gpio::{Input, InputConfig, Level, Output, OutputConfig, Pull};
+use esp_println as _;
+
+// Same 10K NTC divider as m26_thermitor
+const THERMISTOR: Thermistor = Thermistor::new(
+    Model::Beta {
+        beta: 3950.0,
+        r0: 10_000.0,
+        t0: 25.0,
+    },
+    Divider::NtcLow,
+    10_000.0,
+);
+
+// Correcting ADC non-linearity issue, see esp32-kit/calibration
+const ADC_CALIBRATION: AdcCalibration = AdcCalibration::new(adc::Attenuation::_11dB);
+const OVERSAMPLING: usize = 16;
+
+// Air temperature changes slowly, no need to read it on every ping
+const TEMPERATURE_INTERVAL: Duration = Duration::from_secs(5);
+// Beep when the car is closer than this
+const STOP_DISTANCE_CM: f64 = 30.0;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    info!("Embassy initialized!");
+
+    // Thermistor
+    let adc_pin = peripherals.GPIO13;
+    let mut adc2_config = AdcConfig::new();
+    let mut pin = adc2_config.enable_pin(adc_pin, Attenuation::_11dB);
+    let mut adc2 = Adc::new(peripherals.ADC2, adc2_config);
+    let mut temperature_filter = Ema::new(0.3);
+
+    // For HC-SR04 Ultrasonic
+    let trig = Output::new(peripherals.GPIO5, Level::Low, OutputConfig::default());
+    let echo = Input::new(
+        peripherals.GPIO18,
+        InputConfig::default().with_pull(Pull::Down),
+    );
+    let mut sensor = Hcsr04::new(trig, echo);
+
+    let mut buzzer = Output::new(peripherals.GPIO33, Level::Low, OutputConfig::default());
+
+    let mut last_temperature_read: Option<Instant> = None;
+
+    loop {
+        if last_temperature_read.is_none_or(|t| t.elapsed() >= TEMPERATURE_INTERVAL) {
+            last_temperature_read = Some(Instant::now());
+            let adc_value = oversample(OVERSAMPLING, || {
+                let raw = nb::block!(adc2.read_oneshot(&mut pin)).unwrap();
+                THERMISTOR.correct(raw, &ADC_CALIBRATION)
+            });
+            // On a sensor fault keep using the last temperature
+            match adc_value.and_then(|adc_value| THERMISTOR.temperature(adc_value)) {
+                Ok(celsius) => {
+                    let celsius = temperature_filter.update(celsius);
+                    sensor.set_temperature(celsius);
+                    esp_println::println!("Temperature:{:.2} °C", celsius);
+                }
+                Err(e) => esp_println::println!("Thermistor error: {:?}", e),
+            }
+        }
+
+        match sensor.measure().await {
+            Ok(distance) => {
+                esp_println::println!("Distance: {:.1} cm", distance.cm());
+                if distance.cm() < STOP_DISTANCE_CM {
+                    buzzer.set_high();
+                } else {
+                    buzzer.set_low();
+                }
+            }
+            // Too close to measure is the closest there is
+            Err(Hcsr04Error::OutOfRange(RangeEnd::Near)) => {
+                esp_println::println!("Distance: < {} cm", MIN_DISTANCE_CM);
+                buzzer.set_high();
+            }
+            Err(e) => {
+                esp_println::println!("HC-SR04 error: {:?}", e);
+                buzzer.set_low();
+            }
+        }
+
+        Timer::after(Duration::from_millis(60)).await;
+    }
+}
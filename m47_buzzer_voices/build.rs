@@ -0,0 +1,18 @@
+use esp32_kit::build::midi::{midi_to_melody, Selection};
+
+fn main() {
+    esp32_kit::build::linker_be_nice();
+    // One voice per track: track 1 holds the tune, track 2 the bass line
+    for (track, out_file) in [(1, "ode_to_joy_melody.rs"), (2, "ode_to_joy_bass.rs")] {
+        midi_to_melody(
+            "melodies/ode_to_joy.mid",
+            out_file,
+            Selection {
+                track: Some(track),
+                channel: None,
+            },
+        );
+    }
+    // make sure linkall.x is the last linker script (otherwise might cause problems with flip-link)
+    println!("cargo:rustc-link-arg=-Tlinkall.x");
+}
@@ -0,0 +1,47 @@
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Timer};
+use esp32_kit::mk_static;
+use esp32_kit::music::sequencer::Sequencer;
+use esp32_kit_hal::tone::LedcTone;
+use m47_buzzer_voices::{bass, melody};
+
+use esp_hal::ledc::{channel, timer, Ledc};
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    let ledc = mk_static!(Ledc<'static>, Ledc::new(peripherals.LEDC));
+    // Every voice needs its own timer, the 4 high-speed timers allow up to 4 voices
+    let mut voices = [
+        LedcTone::new(
+            ledc,
+            timer::Number::Timer0,
+            channel::Number::Channel0,
+            peripherals.GPIO33,
+        ),
+        LedcTone::new(
+            ledc,
+            timer::Number::Timer1,
+            channel::Number::Channel1,
+            peripherals.GPIO32,
+        ),
+    ];
+
+    // Both tracks share the tempo of the MIDI file
+    let mut sequencer = Sequencer::new(melody::TEMPO, [&melody::MELODY, &bass::MELODY]);
+
+    loop {
+        sequencer.play(&mut voices).await;
+        Timer::after(Duration::from_secs(2)).await;
+    }
+}
@@ -0,0 +1,12 @@
+#![no_std]
+
+// Generated by build.rs from melodies/ode_to_joy.mid
+pub mod melody {
+    use esp32_kit::music::*;
+    include!(concat!(env!("OUT_DIR"), "/ode_to_joy_melody.rs"));
+}
+
+pub mod bass {
+    use esp32_kit::music::*;
+    include!(concat!(env!("OUT_DIR"), "/ode_to_joy_bass.rs"));
+}
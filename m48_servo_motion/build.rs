@@ -0,0 +1,5 @@
+fn main() {
+    esp32_kit::build::linker_be_nice();
+    // make sure linkall.x is the last linker script (otherwise might cause problems with flip-link)
+    println!("cargo:rustc-link-arg=-Tlinkall.x");
+}
@@ -0,0 +1,107 @@
+#![no_std]
+#![no_main]
+
+use embassy_executor::Spawner;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::{Duration, Timer};
+use esp32_kit::mk_static;
+use esp32_kit::servo::planner::{Command, Planner};
+use esp32_kit::servo::trajectory::{Limits, Profile};
+use esp32_kit::servo::{Calibration, PwmServo};
+
+use esp_hal::ledc::{
+    channel::{self, ChannelIFace},
+    timer::{self, TimerIFace},
+    HighSpeed, Ledc,
+};
+use esp_hal::time::Rate;
+
+type LedcServo = PwmServo<channel::Channel<'static, HighSpeed>>;
+
+// Servo 0 pans, servo 1 tilts
+static PLANNER: Planner<CriticalSectionRawMutex, 2> = Planner::new();
+
+#[embassy_executor::task]
+async fn planner_task(mut servos: [LedcServo; 2]) {
+    let limits = [
+        Limits::default(),
+        // The tilt servo carries the load, so it moves more gently
+        Limits::new(90.0, 360.0).unwrap(),
+    ];
+    PLANNER.run(&mut servos, limits, [90.0, 90.0]).await
+}
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    let ledc = mk_static!(Ledc<'static>, Ledc::new(peripherals.LEDC));
+    // Both servos share one 50 Hz timer
+    let hstimer0 = mk_static!(
+        timer::Timer<'static, HighSpeed>,
+        ledc.timer::<HighSpeed>(timer::Number::Timer0)
+    );
+    hstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty12Bit,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency: Rate::from_hz(50),
+        })
+        .unwrap();
+
+    let pan = ledc.channel(channel::Number::Channel0, peripherals.GPIO33);
+    let tilt = ledc.channel(channel::Number::Channel1, peripherals.GPIO32);
+    let servos = [pan, tilt].map(|mut channel| {
+        channel
+            .configure(channel::config::Config {
+                timer: hstimer0,
+                duty_pct: 0,
+                pin_config: channel::config::PinConfig::PushPull,
+            })
+            .unwrap();
+        PwmServo::new(channel, Calibration::default()).unwrap()
+    });
+
+    spawner.spawn(planner_task(servos)).unwrap();
+
+    // Corners of a square in pan/tilt space, then back to the middle
+    let poses = [
+        (Some(30.0), Some(60.0)),
+        (Some(150.0), Some(60.0)),
+        (Some(150.0), Some(120.0)),
+        (Some(30.0), Some(120.0)),
+        (Some(90.0), Some(90.0)),
+    ];
+
+    loop {
+        // Both servos start and arrive together, so diagonals do not dog-leg
+        for (pan, tilt) in poses {
+            PLANNER
+                .send(Command::MoveTo {
+                    targets: [pan, tilt],
+                    profile: Profile::SCurve,
+                    synchronized: true,
+                })
+                .await;
+            Timer::after(Duration::from_millis(1500)).await;
+        }
+
+        // Full sweep of the pan servo alone, the tilt servo holds
+        for target in [0.0, 180.0, 90.0] {
+            PLANNER
+                .send(Command::MoveTo {
+                    targets: [Some(target), None],
+                    profile: Profile::Trapezoidal,
+                    synchronized: false,
+                })
+                .await;
+            Timer::after(Duration::from_millis(1500)).await;
+        }
+    }
+}
@@ -0,0 +1,155 @@
+#![no_std]
+#![no_main]
+
+use defmt::{info, warn};
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Ticker, Timer};
+use esp32_kit::adc::{self, AdcCalibration};
+use esp32_kit::filter::oversample;
+use esp32_kit::joystick::{Calibration, CalibrationCapture};
+use esp32_kit::pantilt::{Action, Mode, PanTilt, PanTiltConfig};
+use esp32_kit::servo::{self, PwmServo, Servo};
+use esp32_kit::storage;
+use esp_hal::analog::adc::{Adc, AdcConfig, Attenuation};
+use esp_hal::gpio::{Input, InputConfig, Pull};
+use esp_hal::ledc::channel::{self, ChannelIFace};
+use esp_hal::ledc::timer::{self, TimerIFace};
+use esp_hal::ledc::{HighSpeed, Ledc};
+use esp_hal::time::Rate;
+use esp_println as _;
+use esp_storage::FlashStorage;
+
+// Correcting ADC non-linearity issue, see esp32-kit/calibration
+const ADC_CALIBRATION: AdcCalibration = AdcCalibration::new(adc::Attenuation::_11dB);
+// Readings averaged into every sample
+const OVERSAMPLING: usize = 4;
+// First sector of the nvs partition, nothing else in this example uses NVS
+const CALIBRATION_OFFSET: u32 = 0x9000;
+// One update per servo frame
+const PERIOD: Duration = Duration::from_millis(20);
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    info!("Embassy initialized!");
+
+    let btn = Input::new(
+        peripherals.GPIO32,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+
+    let mut adc2_config = AdcConfig::new();
+    let mut vrx_pin = adc2_config.enable_pin(peripherals.GPIO13, Attenuation::_11dB);
+    let mut vry_pin = adc2_config.enable_pin(peripherals.GPIO14, Attenuation::_11dB);
+    let mut adc2 = Adc::new(peripherals.ADC2, adc2_config);
+
+    let mut read_stick = || -> Option<[f64; 2]> {
+        let vrx = oversample(OVERSAMPLING, || {
+            nb::block!(adc2.read_oneshot(&mut vrx_pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        });
+        let vry = oversample(OVERSAMPLING, || {
+            nb::block!(adc2.read_oneshot(&mut vry_pin)).map(|raw| ADC_CALIBRATION.correct(raw))
+        });
+        Some([vrx.ok()?, vry.ok()?])
+    };
+
+    let ledc = Ledc::new(peripherals.LEDC);
+    let mut hstimer0 = ledc.timer::<HighSpeed>(timer::Number::Timer0);
+    hstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty12Bit,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency: Rate::from_hz(50),
+        })
+        .unwrap();
+    let pan = ledc.channel(channel::Number::Channel0, peripherals.GPIO33);
+    let tilt = ledc.channel(channel::Number::Channel1, peripherals.GPIO26);
+    let mut servos = [pan, tilt].map(|mut channel| {
+        channel
+            .configure(channel::config::Config {
+                timer: &hstimer0,
+                duty_pct: 0,
+                pin_config: channel::config::PinConfig::PushPull,
+            })
+            .unwrap();
+        PwmServo::new(channel, servo::Calibration::default()).unwrap()
+    });
+
+    let mut flash = FlashStorage::new();
+    let mut calibration = match storage::load::<Calibration, _>(&mut flash, CALIBRATION_OFFSET) {
+        Ok(Some(calibration)) => calibration,
+        _ => {
+            warn!("No joystick calibration stored, hold the button while resetting to calibrate");
+            Calibration::default()
+        }
+    };
+
+    // Holding the button during boot starts the calibration
+    if btn.is_low() {
+        info!("Calibrating: let go of the stick");
+        Timer::after(Duration::from_secs(2)).await;
+        let mut capture = CalibrationCapture::new();
+        for _ in 0..50 {
+            if let Some(raw) = read_stick() {
+                capture.rest(raw);
+            }
+            Timer::after(PERIOD).await;
+        }
+        info!("Calibrating: circle the stick along its edge for 5 seconds");
+        for _ in 0..250 {
+            if let Some(raw) = read_stick() {
+                capture.sweep(raw);
+            }
+            Timer::after(PERIOD).await;
+        }
+        match capture.finish() {
+            Some(captured) => {
+                calibration = captured;
+                match storage::save(&mut flash, CALIBRATION_OFFSET, &calibration) {
+                    Ok(()) => info!("Calibration saved"),
+                    Err(_) => warn!("Could not write the calibration to flash"),
+                }
+            }
+            None => warn!("The stick did not move far enough, calibration discarded"),
+        }
+    }
+    info!(
+        "X: {}..{}..{} Y: {}..{}..{}",
+        calibration.x.min,
+        calibration.x.center,
+        calibration.x.max,
+        calibration.y.min,
+        calibration.y.center,
+        calibration.y.max
+    );
+
+    let mut head = PanTilt::new(PanTiltConfig::default(), calibration);
+    let mut ticker = Ticker::every(PERIOD);
+
+    loop {
+        let now_ms = Instant::now().as_millis();
+        if let Some(raw) = read_stick() {
+            let angles = head.update(now_ms, raw);
+            for (servo, angle) in servos.iter_mut().zip(angles) {
+                servo.set_angle(angle).unwrap();
+            }
+        }
+
+        // Sampling every 20 ms is enough debouncing for the button
+        match head.button(now_ms, btn.is_low()) {
+            Some(Action::Recentered) => info!("Recentered"),
+            Some(Action::ModeChanged(Mode::Rate)) => info!("Rate mode"),
+            Some(Action::ModeChanged(Mode::Absolute)) => info!("Absolute mode"),
+            None => {}
+        }
+
+        ticker.next().await;
+    }
+}
@@ -0,0 +1,120 @@
+#![no_std]
+#![no_main]
+
+use defmt::info;
+use embassy_executor::Spawner;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use esp32_kit::button::{self, Button, GestureConfig};
+use esp32_kit::led::{Effect, LedEffect};
+use esp32_kit::mk_static;
+use esp_hal::gpio::{Input, InputConfig, Pull};
+use esp_hal::ledc::{
+    channel::{self, ChannelIFace},
+    timer::{self, TimerIFace},
+    HighSpeed, Ledc,
+};
+use esp_hal::time::Rate;
+use esp_println as _;
+
+// What the LED can tell, e.g. the state of a network connection
+const EFFECTS: [(&str, Effect); 6] = [
+    (
+        "connecting",
+        Effect::Blink {
+            period_ms: 200,
+            duty: 0.5,
+        },
+    ),
+    (
+        "waiting",
+        Effect::Blink {
+            period_ms: 2000,
+            duty: 0.05,
+        },
+    ),
+    ("ok", Effect::Solid(1.0)),
+    ("idle", Effect::Breathe { period_ms: 3000 }),
+    ("alive", Effect::Heartbeat { period_ms: 1200 }),
+    ("error", Effect::SOS),
+];
+
+static STATUS_LED: LedEffect<CriticalSectionRawMutex> = LedEffect::new();
+static BUTTON_EVENTS: Channel<CriticalSectionRawMutex, button::Event, 8> = Channel::new();
+
+#[embassy_executor::task]
+async fn led_task(mut channel: channel::Channel<'static, HighSpeed>) {
+    STATUS_LED.run(&mut channel).await
+}
+
+#[embassy_executor::task]
+async fn button_task(mut button: Button<Input<'static>>) {
+    button.run(&BUTTON_EVENTS).await
+}
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    let ledc = mk_static!(Ledc<'static>, Ledc::new(peripherals.LEDC));
+    let hstimer0 = mk_static!(
+        timer::Timer<'static, HighSpeed>,
+        ledc.timer::<HighSpeed>(timer::Number::Timer0)
+    );
+    // 10 bits leave enough steps for the dim end of the gamma curve
+    hstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::HSClockSource::APBClk,
+            frequency: Rate::from_khz(5),
+        })
+        .unwrap();
+    // On-board blue LED
+    let mut channel0 = ledc.channel(channel::Number::Channel0, peripherals.GPIO2);
+    channel0
+        .configure(channel::config::Config {
+            timer: hstimer0,
+            duty_pct: 0,
+            pin_config: channel::config::PinConfig::PushPull,
+        })
+        .unwrap();
+    spawner.spawn(led_task(channel0)).unwrap();
+
+    // On-board BOOT button: click shows the next effect, a long press flashes
+    let boot_button = Input::new(
+        peripherals.GPIO0,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+    let config = GestureConfig {
+        repeat_ms: None,
+        ..GestureConfig::default()
+    };
+    spawner
+        .spawn(button_task(Button::new(boot_button, true, config)))
+        .unwrap();
+
+    let mut index = 0;
+    let (name, effect) = EFFECTS[index];
+    info!("{}", name);
+    STATUS_LED.set(effect).await;
+
+    loop {
+        match BUTTON_EVENTS.receive().await {
+            button::Event::Click => {
+                index = (index + 1) % EFFECTS.len();
+                let (name, effect) = EFFECTS[index];
+                info!("{}", name);
+                STATUS_LED.set(effect).await;
+            }
+            // The current effect carries on after the flash
+            button::Event::LongPress(_) => STATUS_LED.set(Effect::Flash { duration_ms: 300 }).await,
+            _ => {}
+        }
+    }
+}
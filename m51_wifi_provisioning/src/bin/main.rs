@@ -0,0 +1,68 @@
+#![no_std]
+#![no_main]
+
+use defmt::info;
+use embassy_executor::Spawner;
+use esp_hal::gpio::{Input, InputConfig, Pull};
+use esp_hal::rng::Rng;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println::{self as _, println};
+
+extern crate alloc;
+
+use esp_wifi::EspWifiController;
+use m51_wifi_provisioning as lib;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp_alloc::heap_allocator!(size: 72 * 1024);
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    info!("Embassy initialized!");
+
+    let timer1 = TimerGroup::new(peripherals.TIMG0);
+
+    // On-board LED shows the connection status
+    lib::status::start_status_led(peripherals.LEDC, peripherals.GPIO2, &spawner);
+
+    let rng = Rng::new(peripherals.RNG);
+    let esp_wifi_ctrl = &*lib::mk_static!(
+        EspWifiController<'static>,
+        esp_wifi::init(timer1.timer0, rng.clone()).unwrap()
+    );
+
+    // BOOT is a strapping pin, so it is only read once the board runs: holding it
+    // forgets the saved network
+    let boot = Input::new(
+        peripherals.GPIO0,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+    // The station asks for the portal when the saved network keeps failing
+    let setup_requested = lib::take_setup_request();
+    let credentials = lib::load_credentials().filter(|_| !setup_requested);
+
+    match credentials {
+        Some(credentials) => {
+            println!("Joining the saved network {}", credentials.ssid);
+            lib::station::start_station(
+                esp_wifi_ctrl,
+                peripherals.WIFI,
+                rng,
+                credentials,
+                boot,
+                &spawner,
+            );
+        }
+        None => {
+            println!("No network to join, starting the setup portal");
+            lib::portal::start_portal(esp_wifi_ctrl, peripherals.WIFI, rng, &spawner).await;
+        }
+    }
+}
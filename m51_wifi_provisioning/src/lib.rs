@@ -0,0 +1,67 @@
+#![no_std]
+#![feature(impl_trait_in_assoc_type)]
+
+pub mod portal;
+pub mod station;
+
+pub use esp32_kit::mk_static;
+pub use esp32_kit_wifi::status;
+
+use esp32_kit::storage::{self, Record, StorageError};
+use esp32_kit::wifi::Credentials;
+use esp_storage::{FlashStorage, FlashStorageError};
+
+/// Where the chosen network is kept, next to the joystick calibration of m33.
+pub const CREDENTIALS_OFFSET: u32 = 0xA000;
+/// Where a restart into the portal is requested, see [`request_setup`].
+pub const SETUP_OFFSET: u32 = 0xB000;
+
+// Present while the next start should open the portal despite a saved network
+struct SetupRequest;
+
+impl Record for SetupRequest {
+    const MAGIC: u32 = u32::from_le_bytes(*b"SETU");
+    const LEN: usize = 0;
+
+    fn encode(&self, _buf: &mut [u8]) {}
+
+    fn decode(_buf: &[u8]) -> Option<Self> {
+        Some(Self)
+    }
+}
+
+/// The network saved by the portal, `None` on a fresh board.
+pub fn load_credentials() -> Option<Credentials> {
+    let mut flash = FlashStorage::new();
+    storage::load(&mut flash, CREDENTIALS_OFFSET).ok().flatten()
+}
+
+pub fn save_credentials(credentials: &Credentials) -> Result<(), StorageError<FlashStorageError>> {
+    let mut flash = FlashStorage::new();
+    storage::save(&mut flash, CREDENTIALS_OFFSET, credentials)
+}
+
+/// Forgets the saved network, the next start opens the portal.
+pub fn erase_credentials() -> Result<(), StorageError<FlashStorageError>> {
+    let mut flash = FlashStorage::new();
+    storage::clear::<Credentials, _>(&mut flash, CREDENTIALS_OFFSET)
+}
+
+/// Opens the portal on the next start only, the saved network is kept.
+pub fn request_setup() -> Result<(), StorageError<FlashStorageError>> {
+    let mut flash = FlashStorage::new();
+    storage::save(&mut flash, SETUP_OFFSET, &SetupRequest)
+}
+
+/// Whether [`request_setup`] was called before this start, the request is used up.
+pub fn take_setup_request() -> bool {
+    let mut flash = FlashStorage::new();
+    let requested = matches!(
+        storage::load::<SetupRequest, _>(&mut flash, SETUP_OFFSET),
+        Ok(Some(_))
+    );
+    if requested {
+        storage::clear::<SetupRequest, _>(&mut flash, SETUP_OFFSET).ok();
+    }
+    requested
+}
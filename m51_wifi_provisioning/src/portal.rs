@@ -0,0 +1,318 @@
+//! Provisioning mode: an open access point that sends every device to the setup page.
+//!
+//! Phones and laptops get an address from the DHCP server and every name they
+//! look up resolves to the portal, so their captive portal check opens the page.
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+
+use embassy_executor::Spawner;
+use embassy_net::udp::{PacketMetadata, UdpSocket};
+use embassy_net::{Ipv4Cidr, Runner, Stack, StackResources, StaticConfigV4};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{Duration, Timer};
+use esp32_kit::dhcp::ServerConfig;
+use esp32_kit::dns;
+use esp32_kit::status::Event;
+use esp32_kit::wifi::manager::MAX_SCAN;
+use esp32_kit::wifi::portal::{parse_form, write_page, FormError};
+use esp32_kit::wifi::{AccessPoint, Credentials};
+use esp32_kit_wifi::dhcp::dhcp_task;
+use esp_hal::rng::Rng;
+use esp_println::println;
+use esp_wifi::wifi::{self, AuthMethod, WifiController, WifiDevice, WifiEvent};
+use esp_wifi::EspWifiController;
+use picoserve::extract::FromRequest;
+use picoserve::request::{RequestBody, RequestParts};
+use picoserve::response::{Content, IntoResponse, Response, StatusCode};
+use picoserve::routing::get;
+use picoserve::{AppBuilder, AppRouter, Router};
+
+use crate::mk_static;
+
+/// Name of the setup network, it is open so any phone can join.
+pub const AP_SSID: &str = "ESP32-Setup";
+
+const NETWORK: ServerConfig = ServerConfig::soft_ap();
+// Room for the page, networks past this are left off the list
+const PAGE_LEN: usize = 4096;
+
+// Networks found before the access point came up
+static SCAN: Mutex<CriticalSectionRawMutex, RefCell<heapless::Vec<AccessPoint, MAX_SCAN>>> =
+    Mutex::new(RefCell::new(heapless::Vec::new()));
+// Set once the credentials are saved
+static RESTART: Signal<CriticalSectionRawMutex, ()> = Signal::new();
+
+pub async fn start_portal(
+    esp_wifi_ctrl: &'static EspWifiController<'static>,
+    wifi: esp_hal::peripherals::WIFI<'static>,
+    mut rng: Rng,
+    spawner: &Spawner,
+) -> Stack<'static> {
+    let (mut controller, interfaces) = esp_wifi::wifi::new(esp_wifi_ctrl, wifi).unwrap();
+    let net_seed = rng.random() as u64 | ((rng.random() as u64) << 32);
+
+    // The station half is only there to scan
+    let config = wifi::Configuration::Mixed(
+        wifi::ClientConfiguration::default(),
+        wifi::AccessPointConfiguration {
+            ssid: AP_SSID.try_into().unwrap(),
+            auth_method: AuthMethod::None,
+            ..Default::default()
+        },
+    );
+    controller.set_configuration(&config).unwrap();
+    println!("Starting wifi");
+    if let Err(e) = controller.start_async().await {
+        println!("Failed to start wifi: {:?}", e);
+        crate::status::fatal().await;
+    }
+    crate::status::publish(Event::ApStarted);
+    scan(&mut controller).await;
+
+    let net_config = embassy_net::Config::ipv4_static(StaticConfigV4 {
+        address: Ipv4Cidr::new(NETWORK.address, 24),
+        gateway: Some(NETWORK.address),
+        dns_servers: Default::default(),
+    });
+    let (stack, runner) = embassy_net::new(
+        interfaces.ap,
+        net_config,
+        mk_static!(StackResources<6>, StackResources::<6>::new()),
+        net_seed,
+    );
+
+    spawner.must_spawn(ap_task(controller));
+    spawner.must_spawn(net_task(runner));
+    spawner.must_spawn(dhcp_task(stack, NETWORK));
+    spawner.must_spawn(dns_task(stack));
+    spawner.must_spawn(restart_task());
+
+    let router = picoserve::make_static!(AppRouter<Portal>, Portal.build_app());
+    let config = picoserve::make_static!(
+        picoserve::Config<Duration>,
+        picoserve::Config::new(picoserve::Timeouts {
+            start_read_request: Some(Duration::from_secs(5)),
+            read_request: Some(Duration::from_secs(1)),
+            write: Some(Duration::from_secs(1)),
+        })
+    );
+    for id in 0..WEB_TASK_POOL_SIZE {
+        spawner.must_spawn(web_task(id, stack, router, config));
+    }
+
+    println!(
+        "Join the open network `{}`, the setup page opens at http://{}/",
+        AP_SSID, NETWORK.address
+    );
+    stack
+}
+
+async fn scan(controller: &mut WifiController<'static>) {
+    match controller.scan_n_async(MAX_SCAN).await {
+        Ok(found) => SCAN.lock(|scan| {
+            let mut scan = scan.borrow_mut();
+            scan.clear();
+            for ap in found {
+                let Ok(ssid) = ap.ssid.as_str().try_into() else {
+                    continue;
+                };
+                scan.push(AccessPoint {
+                    ssid,
+                    rssi: ap.signal_strength,
+                    channel: ap.channel,
+                    secured: ap.auth_method.is_some_and(|auth| auth != AuthMethod::None),
+                })
+                .ok();
+            }
+            println!("Found {} networks", scan.len());
+        }),
+        Err(e) => println!("Scan failed: {:?}", e),
+    }
+}
+
+#[embassy_executor::task]
+async fn ap_task(mut controller: WifiController<'static>) {
+    loop {
+        let events = controller
+            .wait_for_events(
+                WifiEvent::ApStaconnected | WifiEvent::ApStadisconnected,
+                true,
+            )
+            .await;
+        if events.contains(WifiEvent::ApStaconnected) {
+            crate::status::publish(Event::ApStationConnected);
+        }
+        if events.contains(WifiEvent::ApStadisconnected) {
+            crate::status::publish(Event::ApStationDisconnected);
+        }
+    }
+}
+
+#[embassy_executor::task]
+async fn net_task(mut runner: Runner<'static, WifiDevice<'static>>) {
+    runner.run().await
+}
+
+#[embassy_executor::task]
+async fn dns_task(stack: Stack<'static>) {
+    let mut rx_meta = [PacketMetadata::EMPTY; 4];
+    let mut rx_buffer = [0; 1024];
+    let mut tx_meta = [PacketMetadata::EMPTY; 4];
+    let mut tx_buffer = [0; 1024];
+    let mut socket = UdpSocket::new(
+        stack,
+        &mut rx_meta,
+        &mut rx_buffer,
+        &mut tx_meta,
+        &mut tx_buffer,
+    );
+    socket.bind(dns::PORT).unwrap();
+
+    let mut query = [0; 512];
+    let mut reply = [0; 512];
+    loop {
+        let Ok((len, from)) = socket.recv_from(&mut query).await else {
+            continue;
+        };
+        if let Some(len) = dns::captive_response(&query[..len], NETWORK.address, &mut reply) {
+            socket.send_to(&reply[..len], from).await.ok();
+        }
+    }
+}
+
+#[embassy_executor::task]
+async fn restart_task() {
+    RESTART.wait().await;
+    println!("Credentials saved, restarting in station mode");
+    // Let the last page reach the browser
+    Timer::after(Duration::from_secs(2)).await;
+    esp_hal::system::software_reset()
+}
+
+/// A page rendered at request time.
+struct Html(heapless::String<PAGE_LEN>);
+
+impl Content for Html {
+    fn content_type(&self) -> &'static str {
+        "text/html; charset=utf-8"
+    }
+
+    fn content_length(&self) -> usize {
+        self.0.len()
+    }
+
+    async fn write_content<W: picoserve::io::Write>(self, mut writer: W) -> Result<(), W::Error> {
+        writer.write_all(self.0.as_bytes()).await
+    }
+}
+
+/// The submitted form, read with the kit's parser rather than serde.
+struct Form(Result<Credentials, FormError>);
+
+impl<'r, State> FromRequest<'r, State> for Form {
+    type Rejection = Infallible;
+
+    async fn from_request<R: picoserve::io::Read>(
+        _state: &'r State,
+        _request_parts: RequestParts<'r>,
+        request_body: RequestBody<'r, R>,
+    ) -> Result<Self, Infallible> {
+        Ok(Form(match request_body.read_all().await {
+            Ok(body) => parse_form(body),
+            Err(_) => Err(FormError::Malformed),
+        }))
+    }
+}
+
+// The setup page with the scan results, sent with `status` unless it does not fit
+fn setup_page(status: StatusCode, message: Option<&str>) -> (StatusCode, Html) {
+    let mut page = heapless::String::new();
+    match SCAN.lock(|scan| write_page(&mut page, &scan.borrow(), message, "/")) {
+        Ok(()) => (status, Html(page)),
+        Err(_) => {
+            println!("The setup page does not fit into {} bytes", PAGE_LEN);
+            page.clear();
+            page.push_str("<!DOCTYPE html><html><body>Setup page too large</body></html>")
+                .ok();
+            (StatusCode::INTERNAL_SERVER_ERROR, Html(page))
+        }
+    }
+}
+
+async fn show_form() -> impl IntoResponse {
+    let (status, page) = setup_page(StatusCode::OK, None);
+    Response::new(status, page)
+}
+
+async fn connect(Form(form): Form) -> impl IntoResponse {
+    let (status, page) = match form {
+        Err(e) => setup_page(StatusCode::BAD_REQUEST, Some(e.message())),
+        Ok(credentials) => match crate::save_credentials(&credentials) {
+            Ok(()) => {
+                println!("Saved network {}", credentials.ssid);
+                RESTART.signal(());
+                let mut page = heapless::String::new();
+                page.push_str(concat!(
+                    "<!DOCTYPE html><html><head><meta charset=\"utf-8\">",
+                    "<title>WiFi setup</title></head><body><h1>Saved</h1>",
+                    "<p>The device restarts and joins the network, ",
+                    "you can switch back to yours now.</p></body></html>",
+                ))
+                .ok();
+                (StatusCode::OK, Html(page))
+            }
+            Err(e) => {
+                println!("Could not save the credentials: {:?}", e);
+                let message = "The settings could not be saved, please try again";
+                setup_page(StatusCode::INTERNAL_SERVER_ERROR, Some(message))
+            }
+        },
+    };
+    Response::new(status, page)
+}
+
+struct Portal;
+
+impl AppBuilder for Portal {
+    type PathRouter = impl picoserve::routing::PathRouter;
+
+    fn build_app(self) -> Router<Self::PathRouter> {
+        // The other paths are what Android, Apple and Windows devices fetch to
+        // detect a captive portal, anything but the expected answer shows the page
+        Router::new()
+            .route("/", get(show_form).post(connect))
+            .route("/generate_204", get(show_form))
+            .route("/hotspot-detect.html", get(show_form))
+            .route("/connecttest.txt", get(show_form))
+    }
+}
+
+const WEB_TASK_POOL_SIZE: usize = 2;
+
+#[embassy_executor::task(pool_size = WEB_TASK_POOL_SIZE)]
+async fn web_task(
+    id: usize,
+    stack: Stack<'static>,
+    router: &'static AppRouter<Portal>,
+    config: &'static picoserve::Config<Duration>,
+) -> ! {
+    let port = 80;
+    let mut tcp_rx_buffer = [0; 1024];
+    let mut tcp_tx_buffer = [0; 1024];
+    let mut http_buffer = [0; 2048];
+
+    picoserve::listen_and_serve(
+        id,
+        router,
+        config,
+        stack,
+        port,
+        &mut tcp_rx_buffer,
+        &mut tcp_tx_buffer,
+        &mut http_buffer,
+    )
+    .await
+}
@@ -0,0 +1,105 @@
+//! Station mode: joins the network saved by the portal.
+//!
+//! Holding BOOT forgets the network, and a network that keeps refusing the
+//! device opens the portal once, so both lead back to the setup page.
+
+use embassy_executor::Spawner;
+use embassy_futures::select::{select, Either};
+use embassy_net::{DhcpConfig, Stack, StackResources};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use esp32_kit::button::{self, Button, GestureConfig};
+use esp32_kit::wifi::manager::EventSubscriber;
+use esp32_kit::wifi::{Credentials, Event};
+use esp32_kit_wifi::station::{self, Networks, WIFI};
+use esp_hal::gpio::Input;
+use esp_hal::rng::Rng;
+use esp_println::println;
+use esp_wifi::EspWifiController;
+
+use crate::mk_static;
+
+// Failed attempts in a row before the portal opens
+const MAX_FAILED_CONNECTS: u32 = 5;
+// Long enough not to forget the network by accident
+const FORGET_PRESS_MS: u64 = 3000;
+
+static BUTTON_EVENTS: Channel<CriticalSectionRawMutex, button::Event, 4> = Channel::new();
+
+pub fn start_station(
+    esp_wifi_ctrl: &'static EspWifiController<'static>,
+    wifi: esp_hal::peripherals::WIFI<'static>,
+    rng: Rng,
+    credentials: Credentials,
+    boot: Input<'static>,
+    spawner: &Spawner,
+) -> Stack<'static> {
+    let net_config = embassy_net::Config::dhcpv4(DhcpConfig::default());
+
+    let config = GestureConfig {
+        long_press_ms: FORGET_PRESS_MS,
+        repeat_ms: None,
+        ..GestureConfig::default()
+    };
+    spawner.must_spawn(boot_button_task(Button::new(boot, true, config)));
+    // Subscribe before the manager starts, so the first attempt is counted
+    spawner.must_spawn(fallback_task(WIFI.subscriber().unwrap()));
+
+    let credentials: &'static Credentials = mk_static!(Credentials, credentials);
+    let mut networks = Networks::new();
+    networks.push(credentials.network()).ok();
+    station::start_station(
+        esp_wifi_ctrl,
+        wifi,
+        rng,
+        net_config,
+        mk_static!(StackResources<3>, StackResources::<3>::new()),
+        networks,
+        spawner,
+    )
+}
+
+#[embassy_executor::task]
+async fn boot_button_task(mut button: Button<Input<'static>>) {
+    let long_press = async {
+        loop {
+            if let button::Event::LongPress(_) = BUTTON_EVENTS.receive().await {
+                break;
+            }
+        }
+    };
+    match select(button.run(&BUTTON_EVENTS), long_press).await {
+        Either::First(never) => never,
+        Either::Second(()) => {}
+    }
+
+    println!("BOOT held, forgetting the network");
+    if let Err(e) = crate::erase_credentials() {
+        println!("Could not erase the credentials: {:?}", e);
+        return;
+    }
+    esp_hal::system::software_reset()
+}
+
+#[embassy_executor::task]
+async fn fallback_task(mut events: EventSubscriber<'static, CriticalSectionRawMutex>) {
+    // Attempts since the last successful one, the latest may still be running
+    let mut attempts = 0;
+    while attempts <= MAX_FAILED_CONNECTS {
+        match events.next_message_pure().await {
+            Event::Connecting { .. } => attempts += 1,
+            Event::Connected { .. } => attempts = 0,
+            _ => {}
+        }
+    }
+
+    println!(
+        "{} attempts failed, restarting into the setup portal",
+        MAX_FAILED_CONNECTS
+    );
+    if let Err(e) = crate::request_setup() {
+        println!("Could not request the portal: {:?}", e);
+        return;
+    }
+    esp_hal::system::software_reset()
+}
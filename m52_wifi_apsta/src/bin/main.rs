@@ -0,0 +1,61 @@
+#![no_std]
+#![no_main]
+
+use defmt::info;
+use embassy_executor::Spawner;
+use esp_hal::rng::Rng;
+use esp_hal::timer::timg::TimerGroup;
+use esp_println as _;
+
+extern crate alloc;
+
+esp_bootloader_esp_idf::esp_app_desc!();
+
+use esp_wifi::EspWifiController;
+use m52_wifi_apsta as lib;
+
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
+    // generator version: 0.3.1
+
+    let peripherals = esp32_kit::init!();
+
+    esp_alloc::heap_allocator!(size: 72 * 1024);
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    info!("Embassy initialized!");
+
+    let timer1 = TimerGroup::new(peripherals.TIMG0);
+    // let _init = esp_wifi::init(
+    //     timer1.timer0,
+    //     esp_hal::rng::Rng::new(peripherals.RNG),
+    //     peripherals.RADIO_CLK,
+    // )
+    // .unwrap();
+
+    // On-board LED shows the connection status
+    lib::status::start_status_led(peripherals.LEDC, peripherals.GPIO2, &spawner);
+
+    let rng = Rng::new(peripherals.RNG);
+    let esp_wifi_ctrl = &*lib::mk_static!(
+        EspWifiController<'static>,
+        esp_wifi::init(timer1.timer0, rng.clone(),).unwrap()
+    );
+
+    let stacks = lib::wifi::start_wifi(esp_wifi_ctrl, peripherals.WIFI, rng, &spawner).await;
+
+    // The same router answers on the home network and on the AP
+    let web_app = lib::web::WebApp::default();
+    for (i, stack) in [stacks.sta, stacks.ap].into_iter().enumerate() {
+        for n in 0..lib::web::WEB_TASKS_PER_STACK {
+            spawner.must_spawn(lib::web::web_task(
+                i * lib::web::WEB_TASKS_PER_STACK + n,
+                stack,
+                web_app.router,
+                web_app.config,
+            ));
+        }
+    }
+    info!("Web server started...");
+}
@@ -0,0 +1,8 @@
+#![no_std]
+#![feature(impl_trait_in_assoc_type)]
+
+pub mod web;
+pub mod wifi;
+
+pub use esp32_kit::mk_static;
+pub use esp32_kit_wifi::status;
@@ -0,0 +1,70 @@
+use embassy_net::Stack;
+use embassy_time::Duration;
+use esp_alloc as _;
+use picoserve::{response::File, routing, AppBuilder, AppRouter, Router};
+
+pub struct Application;
+
+impl AppBuilder for Application {
+    type PathRouter = impl routing::PathRouter;
+
+    fn build_app(self) -> picoserve::Router<Self::PathRouter> {
+        picoserve::Router::new().route(
+            "/",
+            routing::get_service(File::html(include_str!("index.html"))),
+        )
+    }
+}
+
+/// Connections served at the same time on each interface.
+pub const WEB_TASKS_PER_STACK: usize = 2;
+// For the station and the AP
+pub const WEB_TASK_POOL_SIZE: usize = 2 * WEB_TASKS_PER_STACK;
+
+#[embassy_executor::task(pool_size = WEB_TASK_POOL_SIZE)]
+pub async fn web_task(
+    id: usize,
+    stack: Stack<'static>,
+    router: &'static AppRouter<Application>,
+    config: &'static picoserve::Config<Duration>,
+) -> ! {
+    let port = 80;
+    let mut tcp_rx_buffer = [0; 1024];
+    let mut tcp_tx_buffer = [0; 1024];
+    let mut http_buffer = [0; 2048];
+
+    picoserve::listen_and_serve(
+        id,
+        router,
+        config,
+        stack,
+        port,
+        &mut tcp_rx_buffer,
+        &mut tcp_tx_buffer,
+        &mut http_buffer,
+    )
+    .await
+}
+
+pub struct WebApp {
+    pub router: &'static Router<<Application as AppBuilder>::PathRouter>,
+    pub config: &'static picoserve::Config<Duration>,
+}
+
+impl Default for WebApp {
+    fn default() -> Self {
+        let router = picoserve::make_static!(AppRouter<Application>, Application.build_app());
+
+        let config = picoserve::make_static!(
+            picoserve::Config<Duration>,
+            picoserve::Config::new(picoserve::Timeouts {
+                start_read_request: Some(Duration::from_secs(5)),
+                read_request: Some(Duration::from_secs(1)),
+                write: Some(Duration::from_secs(1)),
+            })
+            .keep_connection_alive()
+        );
+
+        Self { router, config }
+    }
+}
@@ -0,0 +1,103 @@
+use core::net::Ipv4Addr;
+use core::str::FromStr;
+
+use embassy_executor::Spawner;
+use embassy_net::{Ipv4Cidr, Stack, StackResources, StaticConfigV4};
+use esp32_kit::dhcp::ServerConfig;
+use esp32_kit_wifi::dhcp::dhcp_task;
+use esp32_kit_wifi::station::{self, EspStation};
+use esp_hal::rng::Rng;
+use esp_println as _;
+use esp_println::println;
+use esp_wifi::wifi::{AccessPointConfiguration, AuthMethod};
+use esp_wifi::EspWifiController;
+use heapless::Vec;
+
+use crate::mk_static;
+use crate::status;
+
+// IP Address/Subnet mask eg: STATIC_IP=192.168.0.50/24
+const STATIC_IP: &str = env!("STATIC_IP");
+const GATEWAY_IP: &str = env!("GATEWAY_IP");
+
+// The fallback AP, reachable when the home network is not
+const AP_SSID: &str = match option_env!("AP_SSID") {
+    Some(ssid) => ssid,
+    None => "esp-wifi",
+};
+const AP_PASSWORD: &str = env!("AP_PASSWORD");
+const AP_IP: Ipv4Addr = Ipv4Addr::new(192, 168, 4, 1);
+
+/// Both network stacks, the web UI is served on each.
+pub struct Stacks {
+    /// The home network, up while the station is connected.
+    pub sta: Stack<'static>,
+    /// The fallback AP, up all the time.
+    pub ap: Stack<'static>,
+}
+
+pub async fn start_wifi(
+    esp_wifi_ctrl: &'static EspWifiController<'static>,
+    wifi: esp_hal::peripherals::WIFI<'static>,
+    mut rng: Rng,
+    spawner: &Spawner,
+) -> Stacks {
+    let (controller, interfaces) = esp_wifi::wifi::new(&esp_wifi_ctrl, wifi).unwrap();
+
+    let Ok(ip_addr) = Ipv4Cidr::from_str(STATIC_IP) else {
+        println!("Invalid STATIC_IP");
+        status::fatal().await
+    };
+
+    let Ok(gateway) = Ipv4Addr::from_str(GATEWAY_IP) else {
+        println!("Invalid GATEWAY_IP");
+        status::fatal().await
+    };
+
+    let sta_config = embassy_net::Config::ipv4_static(StaticConfigV4 {
+        address: ip_addr,
+        gateway: Some(gateway),
+        dns_servers: Vec::new(),
+    });
+    let (sta, sta_runner) = embassy_net::new(
+        interfaces.sta,
+        sta_config,
+        mk_static!(StackResources<3>, StackResources::<3>::new()),
+        rng.random() as u64 | ((rng.random() as u64) << 32),
+    );
+
+    let ap_config = embassy_net::Config::ipv4_static(StaticConfigV4 {
+        address: Ipv4Cidr::new(AP_IP, 24),
+        gateway: Some(AP_IP),
+        dns_servers: Vec::new(),
+    });
+    // The web tasks plus the DHCP server
+    let (ap, ap_runner) = embassy_net::new(
+        interfaces.ap,
+        ap_config,
+        mk_static!(StackResources<4>, StackResources::<4>::new()),
+        rng.random() as u64 | ((rng.random() as u64) << 32),
+    );
+
+    let access_point = AccessPointConfiguration {
+        ssid: AP_SSID.try_into().unwrap(),
+        password: AP_PASSWORD.try_into().unwrap(),
+        auth_method: AuthMethod::WPA2Personal,
+        ..Default::default()
+    };
+
+    let station = EspStation::with_access_point(controller, access_point);
+    station::spawn_station(
+        station,
+        esp32_kit_wifi::known_networks!(),
+        sta,
+        rng,
+        spawner,
+    );
+    spawner.spawn(station::net_task(sta_runner)).ok();
+    spawner.spawn(station::net_task(ap_runner)).ok();
+    spawner.spawn(dhcp_task(ap, ServerConfig::soft_ap())).ok();
+
+    println!("Fallback AP `{}` at http://{}/", AP_SSID, AP_IP);
+    Stacks { sta, ap }
+}
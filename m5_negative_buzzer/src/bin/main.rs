@@ -1,87 +1,65 @@
 #![no_std]
 #![no_main]
 
-use m5_negative_buzzer::{
-    music::{self, Song},
-    pink_panther,
-};
-
-use esp_hal::{
-    clock::CpuClock,
-    ledc::{
-        channel::{self, ChannelIFace},
-        timer::TimerIFace,
-        Ledc,
-    },
-    time::Rate,
-};
-use esp_hal::{ledc::timer, main};
-use esp_hal::{
-    ledc::HighSpeed,
-    time::{Duration, Instant},
-};
-
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
+use embassy_executor::Spawner;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_time::{Duration, Timer};
+use esp32_kit::mk_static;
+use esp32_kit::music::player::{Control, Player, Tune};
+use esp32_kit::music::rtttl::Rtttl;
+use esp32_kit::music::Melody;
+use esp32_kit_hal::tone::LedcTone;
+use m5_negative_buzzer::{ode_to_joy, pink_panther};
+
+use esp_hal::gpio::{Input, InputConfig, Pull};
+use esp_hal::ledc::{channel, timer, Ledc};
+
+// Parsed when the firmware is built
+const ALERT: Melody = esp32_kit::rtttl!("Alert:d=16,o=6,b=180:c,e,g,c7,p,c7,g,e,c");
+// Could just as well come from WiFi or BLE, parsed at runtime
+const TETRIS: &str = "Tetris:d=4,o=5,b=160:e6,8b,8c6,8d6,16e6,16d6,8c6,8b,a,8a,8c6,e6,8d6,8c6,b,8b,8c6,d6,e6,c6,a,2a";
+
+static PLAYER: Player<CriticalSectionRawMutex, 4> = Player::new();
+
+#[embassy_executor::task]
+async fn player_task(mut buzzer: LedcTone) {
+    PLAYER.run(&mut buzzer).await
 }
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
-#[main]
-fn main() -> ! {
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
-
-    let mut buzzer = peripherals.GPIO33;
-
-    let ledc = Ledc::new(peripherals.LEDC);
-
-    let song = Song::new(pink_panther::TEMPO);
-
-
-    for (note, duration_type) in pink_panther::MELODY {
-        let note_duration = song.calc_note_duration(duration_type) as u64;
-        let pause_duration = note_duration / 10; // 10% of note_duration
-        if note == music::REST {
-            blocking_delay(Duration::from_millis(note_duration));
-            continue;
-        }
-
-        let freq = Rate::from_hz(note as u32);
-
-        let mut hstimer0 = ledc.timer::<HighSpeed>(timer::Number::Timer0);
-        hstimer0
-            .configure(timer::config::Config {
-                duty: timer::config::Duty::Duty10Bit,
-                clock_source: timer::HSClockSource::APBClk,
-                frequency: freq,
-            })
-            .unwrap();
-
-        let mut channel0 = ledc.channel(channel::Number::Channel0, buzzer);
-        channel0
-            .configure(channel::config::Config {
-                timer: &hstimer0,
-                duty_pct: 50,
-                pin_config: channel::config::PinConfig::PushPull,
-            })
-            .unwrap();
-        
-        blocking_delay(Duration::from_millis(note_duration - pause_duration)); // play 90%
-
-        channel0.set_duty(0).unwrap();
-        blocking_delay(Duration::from_millis(pause_duration)); // Pause for 10%
-    }
-
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    let ledc = mk_static!(Ledc<'static>, Ledc::new(peripherals.LEDC));
+    let buzzer = LedcTone::new(
+        ledc,
+        timer::Number::Timer0,
+        channel::Number::Channel0,
+        peripherals.GPIO33,
+    );
+    spawner.spawn(player_task(buzzer)).unwrap();
+
+    PLAYER.play(Tune::Melody(ALERT)).await;
+    PLAYER.play(Tune::Melody(pink_panther::PINK_PANTHER)).await;
+    PLAYER.play(Tune::Melody(ode_to_joy::ODE_TO_JOY)).await;
+    PLAYER
+        .play(Tune::Rtttl(Rtttl::parse(TETRIS).unwrap()))
+        .await;
+
+    // BOOT button skips to the next song while the player keeps going in the background
+    let mut button = Input::new(
+        peripherals.GPIO0,
+        InputConfig::default().with_pull(Pull::Up),
+    );
     loop {
-        blocking_delay(Duration::from_millis(5));
+        button.wait_for_falling_edge().await;
+        PLAYER.control(Control::Skip).await;
+        Timer::after(Duration::from_millis(200)).await;
     }
 }
-
-fn blocking_delay(duration: Duration) {
-    let delay_start = Instant::now();
-    while delay_start.elapsed() < duration {}
-}
@@ -1,3 +1,3 @@
 #![no_std]
-pub mod music;
-pub mod pink_panther;
\ No newline at end of file
+pub mod ode_to_joy;
+pub mod pink_panther;
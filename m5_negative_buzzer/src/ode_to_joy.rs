@@ -0,0 +1,10 @@
+use esp32_kit::music::*;
+
+// Generated by build.rs from melodies/ode_to_joy.mid
+include!(concat!(env!("OUT_DIR"), "/ode_to_joy.rs"));
+
+pub const ODE_TO_JOY: Melody = Melody {
+    name: "Ode to Joy",
+    tempo: TEMPO,
+    notes: &MELODY,
+};
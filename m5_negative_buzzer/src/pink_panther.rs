@@ -1,4 +1,4 @@
-use crate::music::*;
+use esp32_kit::music::*;
 
 // change this to make the song slower or faster
 pub const TEMPO: u16 = 120;
@@ -95,4 +95,9 @@ pub const MELODY: [(f64, i16); 88] = [
     (NOTE_E4, 16),
     (NOTE_E4, 16),
     (NOTE_E4, 2),
-];
\ No newline at end of file
+];
+pub const PINK_PANTHER: Melody = Melody {
+    name: "Pink Panther",
+    tempo: TEMPO,
+    notes: &MELODY,
+};
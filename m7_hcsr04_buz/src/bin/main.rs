@@ -1,66 +1,89 @@
 #![no_std]
 #![no_main]
 
-use esp_hal::clock::CpuClock;
+use embassy_executor::Spawner;
+use embassy_time::{Duration, Instant, Timer};
+use esp32_kit::hcsr04::{Distance, Hcsr04, Hcsr04Error, RangeEnd, MIN_DISTANCE_CM};
+use esp32_kit::proximity::{ProximityAlarm, ProximityConfig};
 use esp_hal::gpio::{Input, InputConfig, Level, Output, OutputConfig, Pull};
-use esp_hal::main;
-use esp_hal::time::{Duration, Instant};
+use esp_hal::ledc::{
+    channel::{self, ChannelIFace},
+    timer::{self, TimerIFace},
+    LSGlobalClkSource, Ledc, LowSpeed,
+};
+use esp_hal::time::Rate;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
+// Time between two distance measurements
+const PING_INTERVAL: Duration = Duration::from_millis(60);
+// Buzzer tone, close to the resonant frequency of most small buzzers
+const BEEP_FREQUENCY: Rate = Rate::from_hz(2_700);
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
-#[main]
-fn main() -> ! {
+#[esp_hal_embassy::main]
+async fn main(_spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
+
+    esp32_kit::init_embassy!(peripherals.TIMG1);
 
-    let mut buzzer = Output::new(peripherals.GPIO33, Level::Low, OutputConfig::default());
+    // Buzzer driven by LEDC PWM
+    let mut ledc = Ledc::new(peripherals.LEDC);
+    ledc.set_global_slow_clock(LSGlobalClkSource::APBClk);
+    let mut lstimer0 = ledc.timer::<LowSpeed>(timer::Number::Timer0);
+    lstimer0
+        .configure(timer::config::Config {
+            duty: timer::config::Duty::Duty10Bit,
+            clock_source: timer::LSClockSource::APBClk,
+            frequency: BEEP_FREQUENCY,
+        })
+        .unwrap();
+    let mut buzzer = ledc.channel(channel::Number::Channel0, peripherals.GPIO33);
+    buzzer
+        .configure(channel::config::Config {
+            timer: &lstimer0,
+            duty_pct: 0,
+            pin_config: channel::config::PinConfig::PushPull,
+        })
+        .unwrap();
 
     // For HC-SR04 Ultrasonic
-    let mut trig = Output::new(peripherals.GPIO5, Level::Low, OutputConfig::default());
+    let trig = Output::new(peripherals.GPIO5, Level::Low, OutputConfig::default());
     let echo = Input::new(
         peripherals.GPIO18,
         InputConfig::default().with_pull(Pull::Down),
     );
+    let mut sensor = Hcsr04::new(trig, echo);
 
-    loop {
-        blocking_delay(Duration::from_millis(5));
-
-        // Trigger ultrasonic waves
-        trig.set_low();
-        blocking_delay(Duration::from_micros(2));
-        trig.set_high();
-        blocking_delay(Duration::from_micros(10));
-        trig.set_low();
+    // far > 100cm 静音, near 慢速, close 快速, critical < 20cm 长鸣
+    let mut alarm = ProximityAlarm::new(ProximityConfig::default());
+    let boot = Instant::now();
 
-        // Measure the duration the signal remains high
-        while echo.is_low() {}
-        let time1 = Instant::now();
-        while echo.is_high() {}
-        let pulse_width = time1.elapsed().as_micros();
+    loop {
+        let distance = match sensor.measure().await {
+            Ok(distance) => Some(distance),
+            // Closer than the sensor can measure is as close as it gets
+            Err(Hcsr04Error::OutOfRange(RangeEnd::Near)) => {
+                Some(Distance::from_cm(MIN_DISTANCE_CM))
+            }
+            // Out of range or no answer from the sensor counts as nothing nearby
+            Err(Hcsr04Error::OutOfRange(RangeEnd::Far) | Hcsr04Error::Timeout) => None,
+        };
+        alarm.update(boot.elapsed().as_millis(), distance);
 
-        // Derive distance from the pulse width
-        let distance = (pulse_width as f64 * 0.0343) / 2.0;
-        // esp_println::println!("Pulse Width: {}", pulse_width);
-        // esp_println::println!("Distance: {}", distance);
+        // Play the zone's cadence until the next measurement is due
+        let next_ping = Instant::now() + PING_INTERVAL;
+        while Instant::now() < next_ping {
+            let now_ms = boot.elapsed().as_millis();
+            let duty_pct = if alarm.buzzer(now_ms) { 50 } else { 0 };
+            buzzer.set_duty(duty_pct).unwrap();
 
-        if distance < 30.0 {
-            buzzer.set_high();
-        } else {
-            buzzer.set_low();
+            let until = match alarm.next_change_in(now_ms) {
+                Some(ms) => (Instant::now() + Duration::from_millis(ms)).min(next_ping),
+                None => next_ping,
+            };
+            Timer::at(until).await;
         }
-
-        blocking_delay(Duration::from_millis(60));
     }
 }
-
-fn blocking_delay(duration: Duration) {
-    let delay_start = Instant::now();
-    while delay_start.elapsed() < duration {}
-}
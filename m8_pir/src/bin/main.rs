@@ -2,25 +2,18 @@
 #![no_main]
 
 use defmt::info;
-use esp_hal::clock::CpuClock;
 use esp_hal::gpio::{Input, InputConfig, Pull};
 use esp_hal::main;
-use esp_hal::time::{Duration, Instant};
+use esp_hal::time::Duration;
 use esp_println as _;
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
-}
-
 esp_bootloader_esp_idf::esp_app_desc!();
 
 #[main]
 fn main() -> ! {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
     let sensor_pin = Input::new(
         peripherals.GPIO33,
@@ -30,13 +23,8 @@ fn main() -> ! {
     loop {
         if sensor_pin.is_high() {
             info!("Motion detected");
-            blocking_delay(Duration::from_millis(100));
+            esp32_kit::blocking_delay!(Duration::from_millis(100));
         }
-        blocking_delay(Duration::from_millis(100));
+        esp32_kit::blocking_delay!(Duration::from_millis(100));
     }
 }
-
-fn blocking_delay(duration: Duration) {
-    let delay_start = Instant::now();
-    while delay_start.elapsed() < duration {}
-}
@@ -1,46 +1,111 @@
 #![no_std]
 #![no_main]
 
-use esp_hal::clock::CpuClock;
+use core::future::pending;
+
+use defmt::info;
+use embassy_executor::Spawner;
+use embassy_futures::select::{select3, Either3};
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::channel::Channel;
+use embassy_time::{Duration, Instant, Timer};
+use esp32_kit::button::{self, Button, GestureConfig};
+use esp32_kit::motion::{MotionAlarm, MotionConfig, State};
 use esp_hal::gpio::{Input, InputConfig, Level, Output, OutputConfig, Pull};
-use esp_hal::main;
-use esp_hal::time::{Duration, Instant};
+use esp_println as _;
+
+// Number of events kept in the log
+const EVENT_LOG_LEN: usize = 16;
+
+static BUTTON_EVENTS: Channel<CriticalSectionRawMutex, button::Event, 8> = Channel::new();
 
-#[panic_handler]
-fn panic(_: &core::panic::PanicInfo) -> ! {
-    loop {}
+#[embassy_executor::task]
+async fn button_task(mut button: Button<Input<'static>>) {
+    button.run(&BUTTON_EVENTS).await
 }
 
 esp_bootloader_esp_idf::esp_app_desc!();
 
-#[main]
-fn main() -> ! {
+#[esp_hal_embassy::main]
+async fn main(spawner: Spawner) {
     // generator version: 0.3.1
 
-    let config = esp_hal::Config::default().with_cpu_clock(CpuClock::max());
-    let peripherals = esp_hal::init(config);
+    let peripherals = esp32_kit::init!();
 
-    let sensor_pin = Input::new(
+    esp32_kit::init_embassy!(peripherals.TIMG1);
+
+    let mut sensor_pin = Input::new(
         peripherals.GPIO33,
         InputConfig::default().with_pull(Pull::Down),
     );
+    // On-board BOOT button: click arms and disarms, a long press prints the event log
+    let boot_button = Input::new(
+        peripherals.GPIO0,
+        InputConfig::default().with_pull(Pull::Up),
+    );
+    let config = GestureConfig {
+        repeat_ms: None,
+        ..GestureConfig::default()
+    };
+    spawner
+        .spawn(button_task(Button::new(boot_button, true, config)))
+        .unwrap();
 
     let mut buzzer_pin = Output::new(peripherals.GPIO18, Level::Low, OutputConfig::default());
     let mut led = Output::new(peripherals.GPIO2, Level::Low, OutputConfig::default());
 
+    let boot = Instant::now();
+    let now_ms = || boot.elapsed().as_millis();
+
+    let mut alarm = MotionAlarm::<EVENT_LOG_LEN>::new(MotionConfig::default(), now_ms());
+    let mut last_state = alarm.state();
+    info!("PIR warming up");
+
     loop {
-        if sensor_pin.is_high() {
-            buzzer_pin.set_high();
-            led.set_high();
-            blocking_delay(Duration::from_millis(100));
-            buzzer_pin.set_low();
-            led.set_low();
+        let deadline = alarm.next_deadline();
+        let timeout = async {
+            match deadline {
+                Some(ms) => Timer::at(boot + Duration::from_millis(ms)).await,
+                None => pending().await,
+            }
+        };
+
+        let state = match select3(
+            sensor_pin.wait_for_any_edge(),
+            BUTTON_EVENTS.receive(),
+            timeout,
+        )
+        .await
+        {
+            Either3::First(()) => alarm.sensor(now_ms(), sensor_pin.is_high()),
+            Either3::Second(button::Event::Click) => alarm.toggle_armed(now_ms()),
+            Either3::Second(button::Event::LongPress(_)) => {
+                print_log(&alarm);
+                alarm.poll(now_ms())
+            }
+            Either3::Second(_) => alarm.poll(now_ms()),
+            Either3::Third(()) => alarm.poll(now_ms()),
+        };
+
+        // LED on while armed, buzzer while the alarm is going off
+        led.set_level(Level::from(matches!(
+            state,
+            State::Armed | State::Triggered
+        )));
+        buzzer_pin.set_level(Level::from(state == State::Triggered));
+
+        if state != last_state {
+            esp_println::println!("{:?} -> {:?}", last_state, state);
+            if state == State::Disarmed {
+                print_log(&alarm);
+            }
+            last_state = state;
         }
-        blocking_delay(Duration::from_millis(100));
     }
 }
 
-fn blocking_delay(duration: Duration) {
-    let delay_start = Instant::now();
-    while delay_start.elapsed() < duration {}
-}
\ No newline at end of file
+fn print_log(alarm: &MotionAlarm<EVENT_LOG_LEN>) {
+    for event in alarm.log().iter() {
+        esp_println::println!("  {} ms: {:?}", event.at_ms, event.kind);
+    }
+}